@@ -0,0 +1,60 @@
+
+use common::AocError;
+use common::hash::HashSet;
+
+fn parse_changes(input: &str) -> Result<Vec<i32>, AocError> {
+    let mut changes = vec![];
+    for (i, line) in input.lines().enumerate() {
+        changes.push(line.trim().parse::<i32>().map_err(|e| AocError::parse(i + 1, line, e))?);
+    }
+    Ok(changes)
+}
+
+pub fn part1(input: &str) -> Result<String, AocError> {
+    let total: i32 = parse_changes(input)?.iter().sum();
+    Ok(total.to_string())
+}
+
+pub fn part2(input: &str) -> Result<String, AocError> {
+    let changes = parse_changes(input)?;
+    if changes.is_empty() {
+        return Err(AocError::NoSolution("there are no frequency changes in the input, so \
+                                         no frequency is reached twice".to_string()));
+    }
+
+    let mut frequency = 0;
+    let mut previous_frequencies = HashSet::default();
+    previous_frequencies.insert(frequency);
+
+    // Keep looping through the list of changes until we see a repeat
+    for change in changes.iter().cycle() {
+        frequency += change;
+        if !previous_frequencies.insert(frequency) {
+            break;
+        }
+    }
+
+    Ok(frequency.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(part1("+1\n-2\n+3\n+1").unwrap(), "3");
+        assert_eq!(part1("+1\n+1\n+1").unwrap(), "3");
+        assert_eq!(part1("+1\n+1\n-2").unwrap(), "0");
+        assert_eq!(part1("-1\n-2\n-3").unwrap(), "-6");
+    }
+
+    #[test]
+    fn part2_examples() {
+        assert_eq!(part2("+1\n-2\n+3\n+1").unwrap(), "2");
+        assert_eq!(part2("+1\n-1").unwrap(), "0");
+        assert_eq!(part2(common::fixtures::DAY01).unwrap(), "10");
+        assert_eq!(part2("-6\n+3\n+8\n+5\n-6").unwrap(), "5");
+        assert_eq!(part2("+7\n+7\n-2\n-7\n-4").unwrap(), "14");
+    }
+}
@@ -0,0 +1,10 @@
+use common::AocError;
+
+fn main() -> Result<(), AocError> {
+    let input = common::read_input()?;
+
+    println!("Resulting frequency = {}", day01::part1(&input)?);
+    println!("First repeated frequency is {}", day01::part2(&input)?);
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use std::collections::BTreeMap;
+
+use common::AocError;
+use common::combin;
+
+// How many times each letter, a to z, appears in [id]. IDs are all lowercase
+// letters so they're counted a byte at a time
+fn letter_counts(id: &str) -> Result<[u8; 26], String> {
+    let mut counts = [0u8; 26];
+    for &b in id.as_bytes() {
+        if !b.is_ascii_lowercase() {
+            return Err(format!("'{}' isn't a lowercase letter", char::from(b)));
+        }
+        let count = &mut counts[usize::from(b - b'a')];
+        *count = count.saturating_add(1);
+    }
+    Ok(counts)
+}
+
+pub fn part1(input: &str) -> Result<String, AocError> {
+    let mut double_count = 0;
+    let mut triple_count = 0;
+    for (i, id) in input.lines().enumerate() {
+        let counts = letter_counts(id).map_err(|e| AocError::parse(i + 1, id, e))?;
+        if counts.contains(&2) {
+            double_count += 1;
+        }
+        if counts.contains(&3) {
+            triple_count += 1;
+        }
+    }
+
+    let checksum = double_count * triple_count;
+    Ok(checksum.to_string())
+}
+
+// Where [id] and [other_id], which are the same length, differ, if they
+// differ in exactly one place
+fn single_difference(id: &[u8], other_id: &[u8]) -> Option<usize> {
+    let mut diff_index = None;
+    for (i, (letter, other_letter)) in id.iter().zip(other_id).enumerate() {
+        if letter != other_letter {
+            if diff_index.is_some() {
+                return None;
+            }
+            diff_index = Some(i);
+        }
+    }
+    diff_index
+}
+
+pub fn part2(input: &str) -> Result<String, AocError> {
+    // Only IDs of the same length can differ by exactly one letter, so they're
+    // only compared with the others of their length
+    let mut by_length: BTreeMap<usize, Vec<&str>> = BTreeMap::new();
+    for id in input.lines() {
+        by_length.entry(id.len()).or_default().push(id);
+    }
+
+    for ids in by_length.values() {
+        for (id, other_id) in combin::pairs(ids.iter()) {
+            if let Some(i) = single_difference(id.as_bytes(), other_id.as_bytes()) {
+                let mut common_letters = id.to_string();
+                common_letters.remove(i);
+                return Ok(common_letters);
+            }
+        }
+    }
+
+    Err(AocError::NoSolution("no two IDs differ by exactly one letter".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        let ids = "abcdef\nbababc\nabbcde\nabcccd\naabcdd\nabcdee\nababab";
+        assert_eq!(part1(ids).unwrap(), "12");
+    }
+
+    #[test]
+    fn part2_example() {
+        let ids = "abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz";
+        assert_eq!(part2(ids).unwrap(), "fgij");
+    }
+}
@@ -0,0 +1,11 @@
+use common::AocError;
+
+fn main() -> Result<(), AocError> {
+    let input = common::read_input()?;
+
+    println!("Checksum is {}", day02::part1(&input)?);
+    println!("The common letters between the correct box IDs are \"{}\"",
+             day02::part2(&input)?);
+
+    Ok(())
+}
@@ -0,0 +1,124 @@
+use common::AocError;
+use common::parse;
+use common::viz::svg::{Style, Svg};
+
+#[derive(Debug)]
+struct Claim {
+    id: usize,
+    x_offset: usize,
+    y_offset: usize,
+    x_len: usize,
+    y_len: usize,
+}
+
+// Claims look like "#1 @ 1,3: 4x4", an ID, then the offsets and then the
+// lengths
+fn make_claim(input_line: &str) -> Result<Claim, String> {
+    let [id, x_offset, y_offset, x_len, y_len] = parse::fixed_ints(input_line)?;
+    Ok(Claim { id, x_offset, y_offset, x_len, y_len })
+}
+
+fn parse_claims(input: &str) -> Result<Vec<Claim>, AocError> {
+    input.lines().enumerate()
+        .map(|(i, line)| make_claim(line).map_err(|e| AocError::parse(i + 1, line, e)))
+        .collect()
+}
+
+// Count how many claims cover each square inch of fabric
+fn claim_fabric(claims: &[Claim]) -> Vec<Vec<usize>> {
+    let mut grid = vec![vec![0usize; 1000]; 1000];
+
+    for claim in claims {
+        let x1 = claim.x_offset;
+        let x2 = claim.x_offset + claim.x_len;
+        let y1 = claim.y_offset;
+        let y2 = claim.y_offset + claim.y_len;
+
+        for vec in &mut grid[x1..x2] {
+            for point in &mut vec[y1..y2] {
+                *point += 1;
+            }
+        }
+    }
+    grid
+}
+
+pub fn part1(input: &str) -> Result<String, AocError> {
+    let claims = parse_claims(input)?;
+    let grid = claim_fabric(&claims);
+
+    let mut two_or_more_count = 0;
+    for vec in &grid {
+        two_or_more_count += vec.iter().filter(|p| **p > 1).count();
+    }
+
+    Ok(two_or_more_count.to_string())
+}
+
+// Find the claim which does not overlap with any other
+fn find_intact_claim<'a>(claims: &'a [Claim], grid: &[Vec<usize>]) -> Option<&'a Claim> {
+    'claim_loop: for claim in claims {
+        let x1 = claim.x_offset;
+        let x2 = claim.x_offset + claim.x_len;
+        let y1 = claim.y_offset;
+        let y2 = claim.y_offset + claim.y_len;
+
+        for vec in &grid[x1..x2] {
+            for point in &vec[y1..y2] {
+                if *point > 1 {
+                    // There must be overlap so skip this claim
+                    continue 'claim_loop;
+                }
+            }
+        }
+
+        return Some(claim);
+    }
+    None
+}
+
+pub fn part2(input: &str) -> Result<String, AocError> {
+    let claims = parse_claims(input)?;
+    let grid = claim_fabric(&claims);
+    let claim = find_intact_claim(&claims, &grid)
+        .ok_or_else(|| AocError::NoSolution("every claim overlaps another".to_string()))?;
+    Ok(claim.id.to_string())
+}
+
+// The fabric with every claim laid over it. Claims are see through so the
+// more claims overlap the darker it gets, apart from the one which doesn't
+// overlap anything, which is green
+pub fn draw(input: &str) -> Result<Svg, AocError> {
+    let claims = parse_claims(input)?;
+    let grid = claim_fabric(&claims);
+    let intact = find_intact_claim(&claims, &grid).map(|c| c.id);
+
+    let mut svg = Svg::new().scale(1.0);
+    svg.rect(0.0, 0.0, 1000.0, 1000.0, &Style::fill("linen"));
+    for claim in &claims {
+        let style = match Some(claim.id) == intact {
+            true => Style::fill("green"),
+            false => Style::fill("darkred").opacity(0.3),
+        };
+        svg.rect(claim.x_offset as f64, claim.y_offset as f64,
+                 claim.x_len as f64, claim.y_len as f64, &style);
+    }
+    Ok(svg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::fixtures::DAY03 as EXAMPLE;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), "4");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), "3");
+    }
+}
@@ -0,0 +1,11 @@
+use common::AocError;
+
+fn main() -> Result<(), AocError> {
+    let input = common::read_input()?;
+
+    println!("There are {} squares within two or more claims",
+             day03::part1(&input)?);
+    println!("The claim with no overlap has ID {}", day03::part2(&input)?);
+
+    Ok(())
+}
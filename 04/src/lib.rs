@@ -0,0 +1,180 @@
+use std::collections::BTreeMap;
+
+use common::AocError;
+use common::parse;
+
+// Times look like "00:59]". They're either 00:XX or 23:XX, and we only care
+// about their ordering
+fn get_time(s: &str) -> Result<i32, String> {
+    let [hour, min]: [i32; 2] = parse::fixed_ints(s)?;
+    Ok(if hour == 0 { min } else { min - 60 })
+}
+
+#[derive(Debug)]
+enum Observation {
+    FallAsleep,
+    WakeUp,
+    GuardId(i32),
+}
+
+// For each guard, how many times they were asleep during each minute
+type GuardsTimes = BTreeMap<i32, BTreeMap<i32, u32>>;
+
+fn parse_guards_times(input: &str) -> Result<GuardsTimes, AocError> {
+    // Use BTreeMap so that the keys are ordered
+    let mut days: BTreeMap<&str, Vec<(i32, Observation)>> = BTreeMap::new();
+    let mut guards_times = BTreeMap::new();
+
+    // Parse all the observations
+    for (i, line) in input.lines().enumerate() {
+        // Lines look like "[1518-11-01 00:00] Guard #10 begins shift"
+        let unexpected = || AocError::parse(i + 1, line, format!("unexpected observation '{}'", line));
+        let mut words = line.split(' ');
+        let (date, time, what) = match (words.next(), words.next(), words.next()) {
+            (Some(date), Some(time), Some(what)) => (date.trim_start_matches('['), time, what),
+            _ => return Err(unexpected()),
+        };
+        let time = get_time(time).map_err(|e| AocError::parse(i + 1, line, e))?;
+        let observation = match what {
+            "falls" => Observation::FallAsleep,
+            "wakes" => Observation::WakeUp,
+            "Guard" => {
+                let id = words.next().ok_or_else(unexpected)?.trim_start_matches('#');
+                Observation::GuardId(parse::value(id).map_err(|e| AocError::parse(i + 1, line, e))?)
+            },
+            _ => return Err(unexpected()),
+        };
+
+        let day = days.entry(date).or_insert(vec![]);
+        day.push((time, observation));
+    }
+
+    // Iterate through the dates so we can move events before midnight to the correct 'day'
+    // (putting them the day after they really are with a negative time to make things simpler)
+    let mut previously_moved_events: Vec<(i32, Observation)> = vec![];
+    for value in days.values_mut() {
+
+        // Find out which events should move
+        let mut events_to_move_indexes = vec![];
+        for (i, v) in value.iter().enumerate() {
+            let (time, _) = *v;
+            // We should move this event to the day after
+            if time < 0 {
+                events_to_move_indexes.push(i);
+            }
+        }
+
+        // Remove these events and put them in a temporary vector
+        let mut events_to_move = vec![];
+        for i in events_to_move_indexes {
+            events_to_move.push(value.remove(i));
+        }
+
+        // Now put any events which we've moved from the previous day into this day
+        for event in previously_moved_events.drain(..) {
+            value.push(event);
+        }
+
+        // Put the events which are being moved into the persistent vector
+        previously_moved_events = events_to_move;
+    }
+
+    // Order the events by time
+    for timed_observations in days.values_mut() {
+        timed_observations.sort_unstable_by_key(|(t1, _)| *t1);  
+
+        // Sanity check that there's no duplicate times
+        let mut last_t = None;
+        for (t, _) in timed_observations {
+            let this_t = Some(t);
+            if this_t == last_t {
+                return Err(AocError::parse_input("two observations on the same day are at the \
+                                                  same time"));
+            }
+            last_t = this_t;
+        }
+    }
+
+    let mut current_guard = None;
+    let mut fell_asleep_time: Option<i32> = None;
+    for (date, value) in &days {
+        for v in value {
+            let (time, ref observation) = *v;
+            match observation {
+                Observation::GuardId(id) => current_guard = Some(*id),
+                Observation::FallAsleep => fell_asleep_time = Some(time),
+                Observation::WakeUp => {
+                    let id = current_guard.ok_or_else(|| AocError::parse_input(format!(
+                        "a guard wakes up on {} before any guard is on duty", date)))?;
+                    let fell_asleep = fell_asleep_time.ok_or_else(|| AocError::parse_input(
+                        format!("a guard wakes up on {} without falling asleep", date)))?;
+
+                    let times = guards_times.entry(id).or_insert(BTreeMap::new());
+                    for i in fell_asleep..time {
+                        let t = times.entry(i).or_insert(0);
+                        *t += 1;
+                    }
+
+                    fell_asleep_time = None;
+                },
+            }
+        }
+
+        // Check we've woken up after every sleep for a given day
+        if fell_asleep_time.is_some() {
+            return Err(AocError::parse_input(format!("a guard falls asleep on {} and doesn't \
+                                                      wake up", date)));
+        }
+    }
+
+    Ok(guards_times)
+}
+
+// The answer is the guard's ID multiplied by the minute they were asleep most
+fn answer(guards_times: &GuardsTimes, id: i32) -> String {
+    let (min, _) = guards_times[&id].iter().max_by(|(_, m1), (_, m2)| m1.cmp(m2)).unwrap();
+    (id * min).to_string()
+}
+
+pub fn part1(input: &str) -> Result<String, AocError> {
+    let guards_times = parse_guards_times(input)?;
+
+    // Find the guard who was asleep most in total
+    let (&id, _) = guards_times.iter().max_by(
+                    |(_, times1), (_, times2)|
+                        times1.values().sum::<u32>().cmp(
+                            &times2.values().sum()
+                        )
+                    ).ok_or_else(|| AocError::NoSolution("there are no guards".to_string()))?;
+    Ok(answer(&guards_times, id))
+}
+
+pub fn part2(input: &str) -> Result<String, AocError> {
+    let guards_times = parse_guards_times(input)?;
+
+    // Find the guard who was asleep most frequently at any one minute
+    let (&id, _) = guards_times.iter().max_by(
+                    |(_, times1), (_, times2)|
+                        times1.values().max().cmp(
+                            &times2.values().max()
+                        )
+                    ).ok_or_else(|| AocError::NoSolution("there are no guards".to_string()))?;
+    Ok(answer(&guards_times, id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::fixtures::DAY04 as EXAMPLE;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), "240");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), "4455");
+    }
+}
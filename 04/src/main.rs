@@ -0,0 +1,10 @@
+use common::AocError;
+
+fn main() -> Result<(), AocError> {
+    let input = common::read_input()?;
+
+    println!("The answer to part 1 is {}", day04::part1(&input)?);
+    println!("The answer to part 2 is {}", day04::part2(&input)?);
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use common::AocError;
+
+// A unit is its type, and whether it has the upper case polarity
+type Unit = (char, bool);
+
+fn to_polarity_tuple(c: char) -> Option<Unit> {
+    if !c.is_ascii_alphabetic() {
+        return None;
+    }
+    Some((c.to_ascii_lowercase(), c.is_ascii_uppercase()))
+}
+
+// The polymer left once every reaction has happened. Units are pushed onto a
+// stack one at a time, and a unit which reacts with the one on top of the
+// stack destroys it instead. Anything a reaction exposes is then on top, ready
+// to react with the next unit, so one pass is enough
+fn react_polymer(units: impl IntoIterator<Item = Unit>) -> Vec<Unit> {
+    let mut reacted: Vec<Unit> = vec![];
+    for unit in units {
+        if reacted.last() == Some(&(unit.0, !unit.1)) {
+            reacted.pop();
+        }
+        else {
+            reacted.push(unit);
+        }
+    }
+    reacted
+}
+
+fn parse_units(input: &str) -> Result<Vec<Unit>, AocError> {
+    input.trim().chars().map(|c| {
+        to_polarity_tuple(c).ok_or_else(|| AocError::parse(1, input, format!("'{}' isn't a unit", c)))
+    }).collect()
+}
+
+pub fn part1(input: &str) -> Result<String, AocError> {
+    let units = parse_units(input)?;
+    Ok(react_polymer(units).len().to_string())
+}
+
+pub fn part2(input: &str) -> Result<String, AocError> {
+    // Removing a type of unit and then reacting gives the same result as
+    // reacting, removing it and reacting again, since the removed units never
+    // stopped anything else reacting. The reacted polymer is much shorter, so
+    // it's what each type is removed from
+    let units = react_polymer(parse_units(input)?);
+
+    let mut unique_chars: Vec<_> = units.iter().map(|&(c, _)| c).collect();
+    unique_chars.sort_unstable();
+    unique_chars.dedup();
+
+    let min_len = unique_chars.iter().map(|&u| {
+        react_polymer(units.iter().copied().filter(|&(c, _)| c != u)).len()
+    }).min().ok_or_else(|| AocError::NoSolution("the polymer is empty".to_string()))?;
+
+    Ok(min_len.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::fixtures::DAY05 as EXAMPLE;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), "10");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), "4");
+    }
+}
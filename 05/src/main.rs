@@ -0,0 +1,11 @@
+use common::AocError;
+
+fn main() -> Result<(), AocError> {
+    let input = common::read_input()?;
+
+    println!("The length of the reacted polymer is {}", day05::part1(&input)?);
+    println!("The minimum reacted length after removing one unit type is {}",
+             day05::part2(&input)?);
+
+    Ok(())
+}
@@ -0,0 +1,179 @@
+use common::AocError;
+use common::grid::SparseGrid;
+use common::parse;
+use common::point::Point;
+
+#[derive(Debug)]
+struct Coordinate {
+    point: Point,
+    area: Option<i32>, // None means infinte (and hence invalid) area
+}
+
+impl Coordinate {
+    fn new(point: Point) -> Coordinate {
+        Coordinate { point, area: Some(0) }
+    }
+}
+
+// The coordinates, and a grid with the index of each coordinate at its
+// position. Only the area covered by the grid's bounds needs to be considered
+fn parse_coords(input: &str) -> Result<(Vec<Coordinate>, SparseGrid<usize>), AocError> {
+    let mut coords = vec![];
+    let mut grid = SparseGrid::new();
+
+    for (i, line) in input.lines().enumerate() {
+        let [x, y] = parse::fixed_ints(line).map_err(|e| AocError::parse(i + 1, line, e))?;
+        let point = Point::new(x, y);
+        grid.insert(point, coords.len());
+        coords.push(Coordinate::new(point));
+    }
+
+    Ok((coords, grid))
+}
+
+// The index of the coordinate closest to [p], unless two or more are equally
+// close, in which case [p] doesn't count towards anyone's area
+fn closest(coords: &[Coordinate], p: Point) -> Option<usize> {
+    let mut closest = None;
+    let mut min_distance = i32::MAX;
+    for (i, coord) in coords.iter().enumerate() {
+        let distance = coord.point.distance(p);
+        if distance < min_distance {
+            min_distance = distance;
+            closest = Some(i);
+        }
+        else if distance == min_distance {
+            closest = None;
+        }
+    }
+    closest
+}
+
+// Every point on the edge of the rectangle from [min] to [max], once each
+fn perimeter(min: Point, max: Point) -> impl Iterator<Item = Point> {
+    let rows = (min.x..=max.x).flat_map(move |x| {
+        let bottom = if max.y > min.y { Some(Point::new(x, max.y)) } else { None };
+        std::iter::once(Point::new(x, min.y)).chain(bottom)
+    });
+    let columns = (min.y + 1..max.y).flat_map(move |y| {
+        let right = if max.x > min.x { Some(Point::new(max.x, y)) } else { None };
+        std::iter::once(Point::new(min.x, y)).chain(right)
+    });
+    rows.chain(columns)
+}
+
+pub fn part1(input: &str) -> Result<String, AocError> {
+    let (mut coords, grid) = parse_coords(input)?;
+    let (min, max) = match (grid.min(), grid.max()) {
+        (Some(min), Some(max)) => (min, max),
+        _ => return Err(AocError::NoSolution("there are no coordinates".to_string())),
+    };
+
+    // A coordinate's area is infinite exactly when it's closest to somewhere
+    // on the edge of the bounds. Every coordinate is inside the bounds, so
+    // stepping straight out from the edge takes you one further from all of
+    // them and whoever was closest stays closest all the way out. And
+    // anywhere outside is closest to whoever is closest to the nearest point
+    // on the edge, because every coordinate is the same distance further
+    // from it. So nothing outside the bounds needs looking at
+    for p in perimeter(min, max) {
+        if let Some(i) = closest(&coords, p) {
+            coords[i].area = None;
+        }
+    }
+    for p in grid.positions() {
+        if let Some(i) = closest(&coords, p) {
+            if let Some(area) = &mut coords[i].area {
+                *area += 1;
+            }
+        }
+    }
+
+    let largest_area = coords.iter().filter_map(|c| c.area).max()
+        .ok_or_else(|| AocError::NoSolution("every coordinate has an infinite area"
+                                            .to_string()))?;
+    Ok(largest_area.to_string())
+}
+
+// How many points have a total distance to all the coords of less than [limit]
+fn safe_area(input: &str, limit: i32) -> Result<usize, AocError> {
+    let (coords, grid) = parse_coords(input)?;
+    Ok(grid.positions()
+        .filter(|&p| coords.iter().map(|c| c.point.distance(p)).sum::<i32>() < limit)
+        .count())
+}
+
+pub fn part2(input: &str) -> Result<String, AocError> {
+    Ok(safe_area(input, 10000)?.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::fixtures::DAY06 as EXAMPLE;
+
+    // The area closest to each coordinate within the bounds grown by [margin]
+    // on every side
+    fn areas_within(coords: &[Coordinate], min: Point, max: Point, margin: i32) -> Vec<usize> {
+        let mut areas = vec![0; coords.len()];
+        for y in min.y - margin..=max.y + margin {
+            for x in min.x - margin..=max.x + margin {
+                if let Some(i) = closest(coords, Point::new(x, y)) {
+                    areas[i] += 1;
+                }
+            }
+        }
+        areas
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), "17");
+    }
+
+    // The example's safe region is for a total distance of less than 32
+    #[test]
+    fn part2_example() {
+        assert_eq!(safe_area(EXAMPLE, 32).unwrap(), 16);
+    }
+
+    #[test]
+    fn perimeter_visits_each_edge_point_once() {
+        let mut points: Vec<_> = perimeter(Point::new(0, 0), Point::new(3, 2)).collect();
+        points.sort_by_key(|p| (p.y, p.x));
+        points.dedup();
+        assert_eq!(points.len(), 10);
+        assert_eq!(perimeter(Point::new(2, 5), Point::new(2, 5)).count(), 1);
+        assert_eq!(perimeter(Point::new(0, 0), Point::new(0, 4)).count(), 5);
+    }
+
+    // Growing the bounds never changes the areas which the perimeter scan
+    // found to be finite, and every one it found to be infinite keeps
+    // growing, so there's no need to look any further out than the bounds
+    #[test]
+    fn only_areas_touching_the_edge_are_infinite() {
+        let inputs = [EXAMPLE, "0, 0\n10, 0\n5, 5\n0, 10\n10, 10\n4, 6", "3, 3\n3, 4\n9, 2"];
+        for input in IntoIterator::into_iter(inputs) {
+            let (mut coords, grid) = parse_coords(input).unwrap();
+            let (min, max) = (grid.min().unwrap(), grid.max().unwrap());
+            for p in perimeter(min, max) {
+                if let Some(i) = closest(&coords, p) {
+                    coords[i].area = None;
+                }
+            }
+
+            let inside = areas_within(&coords, min, max, 0);
+            let near = areas_within(&coords, min, max, 20);
+            let far = areas_within(&coords, min, max, 40);
+            for (i, coord) in coords.iter().enumerate() {
+                if coord.area.is_some() {
+                    assert_eq!((inside[i], near[i]), (far[i], far[i]), "{} in {:?}", i, input);
+                }
+                else {
+                    assert!(inside[i] < near[i] && near[i] < far[i], "{} in {:?}", i, input);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use common::AocError;
+
+fn main() -> Result<(), AocError> {
+    let input = common::read_input()?;
+
+    println!("The largest finite area is {}", day06::part1(&input)?);
+    println!("The size of the safe area (total distance to all coords < 10000) is {}",
+             day06::part2(&input)?);
+
+    Ok(())
+}
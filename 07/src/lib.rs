@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::cmp::Ordering;
+
+use common::AocError;
+use common::bitset::BitSet;
+use common::parse;
+use common::viz::dot::Dot;
+
+type Steps = Vec<Step>;
+// For each step, the steps which must be finished first. A is 0, B is 1 etc.
+type Requirements = HashMap<Step, BitSet>;
+type PendingSteps<'s, 'r> = Vec<(&'s Step, &'r BitSet)>;
+
+// The number of elves (including you) who can work on steps at once
+const WORKERS: usize = 5;
+// Every step takes this long, plus 1s for A, 2s for B etc.
+const BASE_DURATION: i32 = 60;
+
+#[derive(Debug, Clone, Copy)]
+struct Step {
+    id: char,
+    started: bool,
+    duration: i32,
+}
+
+impl Hash for Step {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state)
+    }
+}
+
+impl PartialEq for Step {
+    fn eq(&self, other: &Step) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Step {}
+
+impl PartialOrd for Step {
+    fn partial_cmp(&self, other: &Step) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Step {
+    fn cmp(&self, other: &Step) -> Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+impl Step {
+    fn new(id: char) -> Step {
+        Step { id, started: false, duration: 0 }
+    }
+
+    // Where the step goes in a BitSet of steps
+    fn index(&self) -> usize {
+        (self.id as usize) - ('A' as usize)
+    }
+
+    fn with_base_duration(&self, base_duration: i32) -> Step {
+        // We want a duration of base + 1 for A, base + 2 for B etc. and 'A'
+        // has 65 value ASCII
+        let duration = base_duration + (self.id as i32) - 64;
+        Step { id: self.id, started: false, duration }
+    }
+}
+
+// Lines look like "Step C must be finished before step A can begin.", which
+// gives the step which must be finished first and then the step waiting for it
+fn parse_requirement(line: &str) -> Result<(char, char), String> {
+    let fields: [&str; 10] = parse::fields(line, " ")?;
+    let step = |field: &str| {
+        let mut chars = field.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_ascii_uppercase() => Ok(c),
+            _ => Err(format!("'{}' isn't a step, which are capital letters", field)),
+        }
+    };
+    Ok((step(fields[1])?, step(fields[7])?))
+}
+
+fn parse_input(input: &str) -> Result<(Steps, Requirements), AocError> {
+    let mut steps = vec![];
+    let mut requirements = HashMap::new();
+    
+    for (i, line) in input.lines().enumerate() {
+        let (requirement_id, step_id) = parse_requirement(line)
+            .map_err(|e| AocError::parse(i + 1, line, e))?;
+
+        // Track the full list of unique steps
+        steps.push(Step::new(requirement_id));
+        steps.push(Step::new(step_id));
+
+        // Insert requirements to the map as apprpriate
+        requirements.entry(Step::new(step_id)).or_insert(BitSet::new())
+            .insert(Step::new(requirement_id).index());
+    }
+    
+    // Remove duplicate steps
+    steps.sort();
+    steps.dedup();
+
+    // Ensure steps without any requirements are in the map 
+    steps.iter().for_each(|&s| { requirements.entry(s).or_insert(BitSet::new()); });
+
+    Ok((steps, requirements))
+}
+
+fn get_sorted_pending_valid_steps(requirements: &Requirements) -> PendingSteps<'_, '_> {
+    let mut steps: Vec<_> = requirements.iter().filter(|(s, r)| r.is_empty() && !s.started).collect();
+    steps.sort_by_key(|(&s, _)| s);
+    steps
+}
+
+fn find_next_step(requirements: &Requirements) -> Step {
+    // Choose the next valid step which is first alphabetically
+    let doable_steps = get_sorted_pending_valid_steps(requirements);
+    let (&next_step, _) = doable_steps[0];
+    next_step
+}
+
+fn do_step(step: &Step, requirements: &Requirements) -> Requirements {
+    let mut new_requirements = requirements.clone();
+    new_requirements.remove(step);
+    new_requirements.values_mut().for_each(|r| { r.remove(step.index()); });
+    new_requirements
+}
+
+fn start_work(available_workers: &mut usize, steps: &Steps, requirements: &Requirements) -> Steps {
+    let pending_steps = get_sorted_pending_valid_steps(requirements);
+    let mut new_steps = steps.clone();
+    for &(ps, _) in pending_steps.iter().take(*available_workers) {
+        new_steps.iter_mut().filter(|s| *s == ps).for_each(
+            |s| if !s.started {
+                s.started = true;
+                *available_workers -= 1;
+            });
+    }
+    new_steps
+}
+
+fn advance_time(steps: &Steps) -> Steps {
+    let mut new_steps = steps.clone();
+    new_steps.iter_mut().filter(|s| s.started).for_each(|s| s.duration -= 1);
+    new_steps
+}
+
+pub fn part1(input: &str) -> Result<String, AocError> {
+    let (steps, requirements) = parse_input(input)?;
+    Ok(steps_in_order(&steps, &requirements))
+}
+
+pub fn part2(input: &str) -> Result<String, AocError> {
+    let (steps, requirements) = parse_input(input)?;
+    Ok(time_taken(&steps, &requirements, WORKERS, BASE_DURATION).to_string())
+}
+
+// The steps with an arrow from each step to the steps waiting for it. Each
+// step is labelled with when it's done in part 1
+pub fn graph(input: &str) -> Result<Dot, AocError> {
+    let (steps, requirements) = parse_input(input)?;
+    let order = steps_in_order(&steps, &requirements);
+
+    let mut dot = Dot::directed().graph_attribute("rankdir", "LR")
+        .node_attribute("shape", "circle");
+    for step in &steps {
+        let position = order.find(step.id).map_or(0, |i| i + 1);
+        let label = format!("{}\n{}", step.id, position);
+        dot.node(&step.id.to_string(), &[("label", &label)]);
+    }
+    for step in &steps {
+        for requirement in requirements[step] {
+            let requirement = (b'A' + requirement as u8) as char;
+            dot.edge(&requirement.to_string(), &step.id.to_string(), &[]);
+        }
+    }
+    Ok(dot)
+}
+
+fn steps_in_order(steps: &Steps, requirements: &Requirements) -> String {
+    let mut steps_taken = vec![];
+    let mut requirements_mut = requirements.clone();
+    for _ in 0..steps.len() {
+        let next_step = find_next_step(&requirements_mut);
+        steps_taken.push(next_step);
+
+        requirements_mut = do_step(&next_step, &requirements_mut);
+    }
+
+    steps_taken.iter().map(|p| p.id).collect()
+}
+
+fn time_taken(steps: &Steps, requirements: &Requirements,
+              workers: usize, base_duration: i32) -> i32 {
+    let mut requirements_mut = requirements.clone();
+    let mut steps_mut: Steps =
+        steps.iter().map(|s| s.with_base_duration(base_duration)).collect();
+    let mut available_workers = workers;
+
+    let mut time = 0;
+    loop {
+        steps_mut = start_work(&mut available_workers, &steps_mut, &requirements_mut);
+        steps_mut = advance_time(&steps_mut);
+
+        for completed_step in steps_mut.iter().filter(|s| s.duration == 0) {
+            requirements_mut = do_step(completed_step, &requirements_mut);
+            available_workers += 1;
+        }
+
+        time += 1;
+
+        if steps_mut.iter().all(|s| s.duration <= 0) {
+            break;
+        }
+    }
+
+    time
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::fixtures::DAY07 as EXAMPLE;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), "CABDFE");
+    }
+
+    // In the example there are two workers and steps don't take 60 seconds
+    // on top of their letter's
+    #[test]
+    fn part2_example() {
+        let (steps, requirements) = parse_input(EXAMPLE).unwrap();
+        assert_eq!(time_taken(&steps, &requirements, 2, 0), 15);
+    }
+
+    #[test]
+    fn bad_requirements() {
+        let error = parse_input("Step C must be finished before step A can begin.\n\
+                                 Step C must be finished before step a can begin.").unwrap_err();
+        assert_eq!(error.to_string(), "Failed to parse line 2, `Step C must be finished before \
+                                       step a ca...`: 'a' isn't a step, which are capital letters");
+        assert!(parse_input("Step C must be finished").is_err());
+    }
+}
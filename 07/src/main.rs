@@ -0,0 +1,10 @@
+use common::AocError;
+
+fn main() -> Result<(), AocError> {
+    let input = common::read_input()?;
+
+    println!("The steps taken, in order, were '{}'", day07::part1(&input)?);
+    println!("The steps took {}s in total with 5 workers!", day07::part2(&input)?);
+
+    Ok(())
+}
@@ -0,0 +1,189 @@
+use common::AocError;
+use common::parse;
+use common::viz::dot::Dot;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Node {
+    // Indexes of the node's children in the tree, in order
+    children: Vec<usize>,
+    metadata: Vec<usize>,
+}
+
+// Every node in the licence, in the order they come in it, so the root is
+// first and every node comes before its children
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Tree {
+    nodes: Vec<Node>,
+}
+
+impl Tree {
+    // A node is how many children it has and how many metadata entries it
+    // has, then its children, then its metadata. Rather than recursing into
+    // each child we keep a stack of the nodes still being read along with how
+    // many of their children are left to read, so deep trees can't overflow
+    // the stack
+    fn from_licence(data: &[usize]) -> Result<Tree, AocError> {
+        let mut data = data.iter().copied();
+        let ends_early = || AocError::parse_input("the licence ends part way through a node");
+        let mut nodes: Vec<Node> = vec![];
+        // Each node being read, with how many children and metadata entries
+        // it has left to read
+        let mut open: Vec<(usize, usize, usize)> = vec![];
+
+        loop {
+            let child = match open.last_mut() {
+                Some((_, children_left, _)) if *children_left > 0 => {
+                    *children_left -= 1;
+                    true
+                },
+                Some(&mut (index, _, metadata_count)) => {
+                    let metadata: Vec<_> = data.by_ref().take(metadata_count).collect();
+                    if metadata.len() < metadata_count {
+                        return Err(ends_early());
+                    }
+                    nodes[index].metadata = metadata;
+                    open.pop();
+                    if open.is_empty() {
+                        break;
+                    }
+                    continue;
+                },
+                None => false,
+            };
+
+            let (child_count, metadata_count) = match (data.next(), data.next()) {
+                (Some(c), Some(m)) => (c, m),
+                _ => return Err(ends_early()),
+            };
+            let index = nodes.len();
+            if child {
+                let (parent, _, _) = open[open.len() - 1];
+                nodes[parent].children.push(index);
+            }
+            nodes.push(Node { children: Vec::with_capacity(child_count), metadata: vec![] });
+            open.push((index, child_count, metadata_count));
+        }
+
+        if data.next().is_some() {
+            return Err(AocError::parse_input("there's more in the licence after the root node"));
+        }
+        Ok(Tree { nodes })
+    }
+
+    // The numbers in the licence the tree was read from
+    #[cfg(test)]
+    fn to_licence(&self) -> Vec<usize> {
+        let mut data = vec![];
+        self.write_node(0, &mut data);
+        data
+    }
+
+    #[cfg(test)]
+    fn write_node(&self, index: usize, data: &mut Vec<usize>) {
+        let node = &self.nodes[index];
+        data.push(node.children.len());
+        data.push(node.metadata.len());
+        for &child in &node.children {
+            self.write_node(child, data);
+        }
+        data.extend(&node.metadata);
+    }
+
+    fn metadata_sum(&self) -> usize {
+        self.nodes.iter().flat_map(|n| &n.metadata).sum()
+    }
+
+    // The value of every node. A node without children is worth the sum of
+    // its metadata. Otherwise its metadata entries pick out its children,
+    // counting from 1, and it's worth the sum of their values, with entries
+    // which don't pick out a child worth nothing. Children come after their
+    // parents so working backwards means they're always valued first
+    fn values(&self) -> Vec<usize> {
+        let mut values = vec![0; self.nodes.len()];
+        for (index, node) in self.nodes.iter().enumerate().rev() {
+            values[index] = if node.children.is_empty() {
+                node.metadata.iter().sum()
+            }
+            else {
+                node.metadata.iter()
+                    .filter_map(|&m| m.checked_sub(1).and_then(|i| node.children.get(i)))
+                    .map(|&child| values[child])
+                    .sum()
+            };
+        }
+        values
+    }
+}
+
+fn read_tree(input: &str) -> Result<Tree, AocError> {
+    Tree::from_licence(&parse::ints_in_line(input))
+}
+
+// The tree of nodes in the licence, with their metadata and values. The nodes
+// are numbered in the order they come in the licence
+pub fn graph(input: &str) -> Result<Dot, AocError> {
+    let tree = read_tree(input)?;
+    let values = tree.values();
+    let mut dot = Dot::directed().node_attribute("shape", "box");
+    for (index, node) in tree.nodes.iter().enumerate() {
+        let metadata: Vec<_> = node.metadata.iter().map(|m| m.to_string()).collect();
+        let label = format!("metadata {}\nvalue {}", metadata.join(" "), values[index]);
+        dot.node(&index.to_string(), &[("label", &label)]);
+        // Edges are labelled with the number the metadata uses for the child
+        for (i, child) in node.children.iter().enumerate() {
+            dot.edge(&index.to_string(), &child.to_string(),
+                     &[("label", &(i + 1).to_string())]);
+        }
+    }
+    Ok(dot)
+}
+
+pub fn part1(input: &str) -> Result<String, AocError> {
+    Ok(read_tree(input)?.metadata_sum().to_string())
+}
+
+pub fn part2(input: &str) -> Result<String, AocError> {
+    Ok(read_tree(input)?.values()[0].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::fixtures::DAY08 as EXAMPLE;
+
+    #[test]
+    fn example() {
+        let tree = read_tree(EXAMPLE).unwrap();
+        let node = |children: Vec<usize>, metadata: Vec<usize>| Node { children, metadata };
+        assert_eq!(tree, Tree { nodes: vec![
+            node(vec![1, 2], vec![1, 1, 2]),
+            node(vec![], vec![10, 11, 12]),
+            node(vec![3], vec![2]),
+            node(vec![], vec![99]),
+        ] });
+        assert_eq!(tree.metadata_sum(), 138);
+        assert_eq!(tree.values(), vec![66, 33, 0, 99]);
+        assert_eq!(part1(EXAMPLE).unwrap(), "138");
+        assert_eq!(part2(EXAMPLE).unwrap(), "66");
+    }
+
+    #[test]
+    fn writes_the_licence_back_out() {
+        let licence = parse::ints_in_line::<usize>(EXAMPLE);
+        assert_eq!(Tree::from_licence(&licence).unwrap().to_licence(), licence);
+        // A chain of nodes each with one child, deeper than recursion could go
+        let depth = 1_000_000;
+        let deep: Vec<_> = std::iter::repeat_n([1, 1], depth - 1).flatten()
+            .chain([0, 1, 5]).chain(std::iter::repeat_n(1, depth - 1)).collect();
+        let tree = Tree::from_licence(&deep).unwrap();
+        assert_eq!((tree.nodes.len(), tree.values()[0]), (depth, 5));
+    }
+
+    #[test]
+    fn bad_licences() {
+        assert!(read_tree("1 1 0 1").is_err());
+        assert!(read_tree("0 1 1 2").is_err());
+        assert!(read_tree("").is_err());
+    }
+}
@@ -0,0 +1,10 @@
+use common::AocError;
+
+fn main() -> Result<(), AocError> {
+    let input = common::read_input()?;
+
+    println!("The sum of all metadata is {}", day08::part1(&input)?);
+    println!("The value of the root node is {}", day08::part2(&input)?);
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use common::AocError;
+use common::parse;
+use common::ring::Ring;
+
+fn play_game(players: usize, max_marble: usize) -> Vec<usize> {
+    let mut marbles = Ring::with_capacity(max_marble);
+    marbles.insert_after_current(0);
+
+    let mut scores = vec![0; players];
+
+    for m in 1..=max_marble {
+
+        if m % 23 == 0 {
+            // Remove the marble 7 marbles counter-clockwise from the current
+            // marble. The player keeps it along with the marble they would
+            // have placed. The marble clockwise of the removed marble becomes
+            // the current one
+            marbles.move_counter_clockwise(7);
+            let removed = marbles.remove_current().expect("Marble circle is empty");
+            scores[m % players] += m + removed;
+        }
+        else {
+            // Insert the next marble between the marbles 1 and 2 places
+            // clockwise from the current marble. The new marble becomes the
+            // current marble
+            marbles.move_clockwise(1);
+            marbles.insert_after_current(m);
+        }
+    }
+    scores
+}
+
+// The input looks like "465 players; last marble is worth 71940 points"
+fn parse_input(input: &str) -> Result<(usize, usize), AocError> {
+    let [players, max_marble] = parse::fixed_ints(input).map_err(AocError::parse_input)?;
+    Ok((players, max_marble))
+}
+
+fn max_score(players: usize, max_marble: usize) -> String {
+    let scores = play_game(players, max_marble);
+    scores.iter().max().unwrap().to_string()
+}
+
+pub fn part1(input: &str) -> Result<String, AocError> {
+    let (players, max_marble) = parse_input(input)?;
+    Ok(max_score(players, max_marble))
+}
+
+pub fn part2(input: &str) -> Result<String, AocError> {
+    let (players, max_marble) = parse_input(input)?;
+    Ok(max_score(players, 100*max_marble))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // There's no example for part 2, which is part 1 with a hundred times as
+    // many marbles
+    #[test]
+    fn part1_examples() {
+        let examples = [(9, 25, "32"), (10, 1618, "8317"), (13, 7999, "146373"),
+                        (17, 1104, "2764"), (21, 6111, "54718"), (30, 5807, "37305")];
+        for &(players, last_marble, score) in &examples {
+            let input = format!("{} players; last marble is worth {} points", players,
+                                last_marble);
+            assert_eq!(part1(&input).unwrap(), score, "{}", input);
+        }
+    }
+}
@@ -0,0 +1,10 @@
+use common::AocError;
+
+fn main() -> Result<(), AocError> {
+    let input = common::read_input()?;
+
+    println!("The max score the game in part 1 is {}", day09::part1(&input)?);
+    println!("The max score the game in part 2 is {}", day09::part2(&input)?);
+
+    Ok(())
+}
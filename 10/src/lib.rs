@@ -0,0 +1,202 @@
+use common::AocError;
+use common::grid::SparseGrid;
+use common::ocr;
+use common::parse;
+use common::point::Point;
+use common::simulation::{Simulation, StepResult};
+use common::viz::svg::{Style, Svg};
+
+#[derive(Debug, Clone)]
+struct Star {
+    pos: Point,
+    vel: Point,
+}
+
+impl Star {
+    // Lines look like "position=< 9,  1> velocity=< 0,  2>"
+    fn new(s: &str) -> Result<Star, String> {
+        let [px, py, vx, vy] = parse::fixed_ints(s)?;
+        Ok(Star { pos: Point::new(px, py), vel: Point::new(vx, vy) })
+    }
+
+    fn advance(&mut self) {
+        self.pos += self.vel
+    }
+
+    fn reverse(&mut self) {
+        self.pos -= self.vel
+    }
+
+    fn advance_by(&mut self, seconds: i32) {
+        self.pos += self.vel * seconds
+    }
+}
+
+fn render_stars(stars: &[Star]) -> String {
+    let sky: SparseGrid<()> = stars.iter().map(|s| (s.pos, ())).collect();
+    let picture = sky.display_with(|star| if star.is_some() { '#' } else { ' ' }).to_string();
+    let rows: Vec<_> = picture.lines().map(|row| row.trim_end()).collect();
+    rows.join("\n")
+}
+
+// The area of the smallest rectangle which covers all the stars, of which
+// there's at least one
+fn bounding_area(stars: &[Star]) -> i64 {
+    let xs = || stars.iter().map(|s| s.pos.x);
+    let ys = || stars.iter().map(|s| s.pos.y);
+    let (x_min, x_max) = (xs().min().unwrap_or(0), xs().max().unwrap_or(0));
+    let (y_min, y_max) = (ys().min().unwrap_or(0), ys().max().unwrap_or(0));
+
+    i64::from(x_max - x_min + 1) * i64::from(y_max - y_min + 1)
+}
+
+// Move the stars until they spell out a message, returning how long it took to
+// appear
+fn align_stars(stars: &mut [Star]) -> i32 {
+    // Assume that the bounding box area decreases monotonically until the
+    // letters appear, and then the box starts to grow
+    let mut last_grid_area = bounding_area(stars);
+
+    let mut t = 0;
+    loop {
+        // Advance the position of all the stars by 1s
+        stars.iter_mut().for_each(|s| s.advance());
+
+        // Update the bounding box
+        let next_grid_area = bounding_area(stars);
+
+        if last_grid_area < next_grid_area {
+            break;
+        }
+
+        last_grid_area = next_grid_area;
+        t += 1;
+    }
+
+    // In our loop we have overshot the minimum bounding box size, so rewind
+    stars.iter_mut().for_each(|s| s.reverse());
+
+    t
+}
+
+fn parse_stars(input: &str) -> Result<Vec<Star>, AocError> {
+    let stars: Vec<_> = input.lines().enumerate()
+        .map(|(i, line)| Star::new(line).map_err(|e| AocError::parse(i + 1, line, e)))
+        .collect::<Result<_, _>>()?;
+    if stars.is_empty() {
+        return Err(AocError::parse_input("there are no stars"));
+    }
+    Ok(stars)
+}
+
+pub fn part1(input: &str) -> Result<String, AocError> {
+    let mut stars = parse_stars(input)?;
+    align_stars(&mut stars);
+    let message = render_stars(&stars);
+    // If there are letters we can't read then give the picture, so that it
+    // can be read by eye instead
+    Ok(ocr::decode(&message).unwrap_or(message))
+}
+
+// The stars at the moment they spell out the message
+pub fn draw(input: &str) -> Result<Svg, AocError> {
+    let mut stars = parse_stars(input)?;
+    align_stars(&mut stars);
+    let mut svg = Svg::new().scale(12.0).background("midnightblue");
+    svg.points(stars.iter().map(|s| s.pos), 0.45, &Style::fill("gold"));
+    Ok(svg)
+}
+
+// The biggest the sky is drawn when watching. When the stars are spread out
+// further than this, each character covers a square of the sky
+const SKY_WIDTH: i32 = 100;
+const SKY_HEIGHT: i32 = 40;
+
+// The stars coming together, for watching. It takes thousands of seconds, so
+// the clock goes faster the longer there is to go, slowing down to a second
+// a step as the message appears. It stops when the message is there
+struct StarField {
+    stars: Vec<Star>,
+    seconds: i32,
+    aligned_at: i32,
+}
+
+impl Simulation for StarField {
+    fn step(&mut self) -> Result<StepResult, AocError> {
+        if self.seconds == self.aligned_at {
+            return Ok(StepResult::Finished);
+        }
+        let seconds = ((self.aligned_at - self.seconds) / 20).max(1);
+        self.stars.iter_mut().for_each(|s| s.advance_by(seconds));
+        self.seconds += seconds;
+        Ok(StepResult::Changed)
+    }
+
+    // Once the message is there, it's read and written above the picture
+    fn render(&self) -> String {
+        let x_min = self.stars.iter().map(|s| s.pos.x).min().unwrap_or(0);
+        let x_max = self.stars.iter().map(|s| s.pos.x).max().unwrap_or(0);
+        let y_min = self.stars.iter().map(|s| s.pos.y).min().unwrap_or(0);
+        let y_max = self.stars.iter().map(|s| s.pos.y).max().unwrap_or(0);
+        let scale = ((x_max - x_min) / SKY_WIDTH + 1).max((y_max - y_min) / SKY_HEIGHT + 1);
+
+        let mut s = if self.seconds < self.aligned_at {
+            format!("Second {}, one character to {}x{}\n", self.seconds, scale, scale)
+        }
+        else {
+            let message = render_stars(&self.stars);
+            match ocr::decode(&message) {
+                Ok(text) => format!("Second {}, the message says {}\n", self.seconds, text),
+                Err(e) => format!("Second {}, the message can't be read: {}\n", self.seconds, e),
+            }
+        };
+        let sky: SparseGrid<()> = self.stars.iter()
+            .map(|s| (Point::new((s.pos.x - x_min) / scale, (s.pos.y - y_min) / scale), ()))
+            .collect();
+        let picture = sky.display_with(|star| if star.is_some() { '#' } else { ' ' }).to_string();
+        let rows: Vec<_> = picture.lines().map(|row| row.trim_end()).collect();
+        s += &rows.join("\n");
+        s
+    }
+}
+
+pub fn simulation(input: &str) -> Result<Box<dyn Simulation>, AocError> {
+    let stars = parse_stars(input)?;
+    let aligned_at = align_stars(&mut stars.clone());
+    Ok(Box::new(StarField { stars, seconds: 0, aligned_at }))
+}
+
+pub fn part2(input: &str) -> Result<String, AocError> {
+    let mut stars = parse_stars(input)?;
+    let t = align_stars(&mut stars);
+    Ok(t.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The example from the puzzle description spells HI after 3 seconds
+    use common::fixtures::DAY10 as EXAMPLE;
+
+    // The example's letters are smaller than the real ones so they can't be
+    // read automatically, and the picture is the answer
+    #[test]
+    fn part1_example() {
+        let message = "\
+#   #  ###
+#   #   #
+#   #   #
+#####   #
+#   #   #
+#   #   #
+#   #   #
+#   #  ###";
+        assert_eq!(part1(EXAMPLE).unwrap(), message);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), "3");
+    }
+}
@@ -0,0 +1,10 @@
+use common::AocError;
+
+fn main() -> Result<(), AocError> {
+    let input = common::read_input()?;
+
+    println!("{}", day10::part1(&input)?);
+    println!("It took {} for this pattern to appear!", day10::part2(&input)?);
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+
+use std::cmp::Reverse;
+
+use rayon::prelude::*;
+
+use common::AocError;
+use common::grid::FixedGrid;
+
+// How many fuel cells there are along each side of the grid
+const SIZE: usize = 300;
+
+// A summed-area table of the fuel cell power levels. Each entry holds the sum
+// of all the power levels above and to the left of it (inclusive), which lets
+// us find the total power of any square with just four lookups. Pad with an
+// extra row and column of zeros at index 0 so that fuel cells, which are
+// indexed from 1 not 0, don't need special casing
+#[derive(Debug)]
+struct SummedAreaTable {
+    sums: FixedGrid<i32, { SIZE + 1 }, { SIZE + 1 }>,
+}
+
+impl SummedAreaTable {
+    fn new(grid_serial_number: i32) -> SummedAreaTable {
+        let mut sums = FixedGrid::new(0);
+        for x in 1..=SIZE {
+            for y in 1..=SIZE {
+                let power = calculate_power_level(x as i32, y as i32,
+                                                  grid_serial_number);
+                sums[(x, y)] = power
+                               + sums[(x - 1, y)]
+                               + sums[(x, y - 1)]
+                               - sums[(x - 1, y - 1)];
+            }
+        }
+        SummedAreaTable { sums }
+    }
+
+    // Total power of the size x size square with top left corner (x, y)
+    fn sum_square_power(&self, size: usize, x: usize, y: usize) -> i32 {
+        let x2 = x + size - 1;
+        let y2 = y + size - 1;
+        self.sums[(x2, y2)] - self.sums[(x - 1, y2)] - self.sums[(x2, y - 1)]
+            + self.sums[(x - 1, y - 1)]
+    }
+
+    // Find the square of the given size with the most power, returning the
+    // power and the coordinates of the top left corner
+    fn find_max_square(&self, size: usize) -> (i32, (usize, usize)) {
+        let mut max_power = i32::MIN;
+        let mut max_power_square = (0, 0);
+        for x in 1..(SIZE - size + 2) {
+            for y in 1..(SIZE - size + 2) {
+                let power = self.sum_square_power(size, x, y);
+                if power > max_power {
+                    max_power = power;
+                    max_power_square = (x, y);
+                }
+            }
+        }
+        (max_power, max_power_square)
+    }
+}
+
+fn get_hundred_digit(i: i32) -> i32 {
+    let s = i.to_string();
+
+    // Return 0 if there's no hundred digit
+    if s.len() <= 3 {
+        return 0;
+    }
+
+    s.chars().rev().nth(2).expect("Didn't find expected 3rd ditgit")
+        .to_digit(10).expect("Faild to parse char as digit") as i32
+}
+
+fn calculate_power_level(x: i32, y: i32, grid_serial_number: i32) -> i32 {
+    let rack_id = x + 10;
+
+    let mut power = rack_id * y;
+
+    power += grid_serial_number;
+
+    power *= rack_id;
+
+    power = get_hundred_digit(power);
+
+    power -= 5;
+
+    power
+}
+
+fn parse_serial_number(input: &str) -> Result<i32, AocError> {
+    input.trim().parse().map_err(|e| AocError::parse(1, input, e))
+}
+
+// The answer is the coordinate of the top left corner of the 3x3 square with
+// the most power, as "x,y"
+pub fn part1(input: &str) -> Result<String, AocError> {
+    let sat = SummedAreaTable::new(parse_serial_number(input)?);
+    let (_, (x, y)) = sat.find_max_square(3);
+    Ok(format!("{},{}", x, y))
+}
+
+// The answer is the top left corner and size of the square of any size with
+// the most power, as "x,y,size". Each size is searched separately, so they're
+// spread across threads, with ties going to the smallest size
+pub fn part2(input: &str) -> Result<String, AocError> {
+    let sat = SummedAreaTable::new(parse_serial_number(input)?);
+
+    let (_, (x, y), size) = (1..=SIZE).into_par_iter()
+        .map(|size| {
+            let (power, square) = sat.find_max_square(size);
+            (power, square, size)
+        })
+        .max_by_key(|&(power, _, size)| (power, Reverse(size)))
+        .expect("There are no square sizes");
+    Ok(format!("{},{},{}", x, y, size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(part1(common::fixtures::DAY11).unwrap(), "33,45");
+        assert_eq!(part1("42").unwrap(), "21,61");
+    }
+
+    #[test]
+    fn part2_examples() {
+        assert_eq!(part2(common::fixtures::DAY11).unwrap(), "90,269,16");
+        assert_eq!(part2("42").unwrap(), "232,251,12");
+    }
+}
@@ -0,0 +1,12 @@
+use common::AocError;
+
+fn main() -> Result<(), AocError> {
+    let input = common::read_input()?;
+
+    println!("The 3x3 square with max power has top left corner {}",
+             day11::part1(&input)?);
+    println!("The square with max power has top left corner and size {}",
+             day11::part2(&input)?);
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+
+use log::debug;
+
+use common::AocError;
+use common::cycle;
+
+// The endless row of pots, as the stretch from the first plant to the last.
+// A pot's next state depends on the two pots either side of it, as well as
+// the pot itself
+#[derive(Debug, Clone)]
+struct Pots {
+    // The number of the first pot in [plants]
+    first: i64,
+    // 1 for a pot with a plant in it, 0 for one without
+    plants: Vec<u8>,
+}
+
+impl Pots {
+    fn new(first: i64, plants: Vec<u8>) -> Pots {
+        let start = plants.iter().position(|&p| p == 1).unwrap_or(plants.len());
+        let end = plants.iter().rposition(|&p| p == 1).map_or(start, |i| i + 1);
+        Pots { first: first + start as i64, plants: plants[start..end].to_vec() }
+    }
+
+    // The next generation. Only the pots up to two away from a plant can have
+    // one next time, and the five pots each of those looks at are kept as a
+    // binary number which each pot is shifted into in turn
+    fn next(&self, rules: &[bool; 32]) -> Pots {
+        let mut plants = Vec::with_capacity(self.plants.len() + 4);
+        let mut pattern = 0;
+        for i in 0..self.plants.len() + 4 {
+            let plant = self.plants.get(i).copied().unwrap_or(0);
+            pattern = (pattern << 1 | plant as usize) & 31;
+            plants.push(rules[pattern] as u8);
+        }
+        Pots::new(self.first - 2, plants)
+    }
+
+    // The sum of the numbers of the pots with plants in them. Pots to the left
+    // of pot 0 have negative numbers so we need a signed sum
+    fn sum(&self) -> i64 {
+        self.plants.iter().enumerate()
+            .filter(|&(_, &p)| p == 1)
+            .map(|(i, _)| self.first + i as i64)
+            .sum()
+    }
+
+    fn count(&self) -> i64 {
+        self.plants.iter().filter(|&&p| p == 1).count() as i64
+    }
+}
+
+// Pots are true if they have a plant in them
+fn parse_pots(s: &str) -> Option<Vec<bool>> {
+    s.chars().map(|c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    }).collect()
+}
+
+// The row of pots, starting from pot 0, and the rules. The five pots a rule
+// looks at are read as a binary number, with a plant being 1 and the leftmost
+// pot the most significant bit, to give the index of the rule
+fn parse_plants(input: &str) -> Result<(Pots, [bool; 32]), AocError> {
+    let first = input.lines().next().unwrap_or("");
+    let pots = first.strip_prefix("initial state: ")
+        .and_then(parse_pots)
+        .ok_or_else(|| AocError::parse(1, first, "expected the initial state of the pots"))?;
+    let pots = Pots::new(0, pots.into_iter().map(u8::from).collect());
+
+    let mut rules = [false; 32];
+    for (i, line) in input.lines().enumerate().skip(2) {
+        let rule = line.split_once(" => ")
+            .and_then(|(pattern, result)| Some((parse_pots(pattern)?, parse_pots(result)?)));
+        match rule {
+            Some((pattern, result)) if pattern.len() == 5 && result.len() == 1 => {
+                let index = pattern.iter().fold(0, |index, &p| index * 2 + p as usize);
+                rules[index] = result[0];
+            },
+            _ => return Err(AocError::parse(i + 1, line, "expected a rule like ..#.# => #")),
+        }
+    }
+
+    // The row of pots is endless, so a plant appearing in the middle of
+    // nowhere would mean infinitely many plants
+    if rules[0] {
+        return Err(AocError::Unsupported("plants grow in empty pots".to_string()));
+    }
+    Ok((pots, rules))
+}
+
+fn sum_after_generations(input: &str, generations: usize) -> Result<i64, AocError> {
+    let (pots, rules) = parse_plants(input)?;
+
+    // The pattern of plants soon repeats, usually drifting along a bit each
+    // generation as a glider, so that we don't have to calculate all the
+    // generations individually. Once a pattern comes round again, every later
+    // generation is one from the cycle moved along by a whole number of
+    // drifts, which moves the sum along by the number of plants each time
+    let mut history = vec![];
+    let patterns = std::iter::successors(Some(pots), |pots| Some(pots.next(&rules)))
+        .take(generations + 1)
+        .map(|pots| {
+            let plants = pots.plants.clone();
+            history.push(pots);
+            plants
+        });
+    match cycle::find_cycle(patterns) {
+        Some((start, period)) => {
+            let drift = history[start + period].first - history[start].first;
+            debug!("The plants repeat from generation {}, with a period of {} and a drift of {}",
+                   start, period, drift);
+            let same = cycle::index_in_cycle(generations, (start, period));
+            let cycles = ((generations - same) / period) as i64;
+            Ok(history[same].sum() + cycles * drift * history[same].count())
+        },
+        None => Ok(history[generations].sum()),
+    }
+}
+
+pub fn part1(input: &str) -> Result<String, AocError> {
+    Ok(sum_after_generations(input, 20)?.to_string())
+}
+
+pub fn part2(input: &str) -> Result<String, AocError> {
+    Ok(sum_after_generations(input, 50000000000)?.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The example from the puzzle description. There's no answer given for
+    // part 2
+    use common::fixtures::DAY12 as EXAMPLE;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), "325");
+    }
+}
@@ -0,0 +1,10 @@
+use common::AocError;
+
+fn main() -> Result<(), AocError> {
+    let input = common::read_input()?;
+
+    println!("The sum after 20 generations is {}", day12::part1(&input)?);
+    println!("The sum after 50000000000 generations is {}", day12::part2(&input)?);
+
+    Ok(())
+}
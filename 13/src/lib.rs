@@ -0,0 +1,319 @@
+use common::AocError;
+use common::direction::Direction;
+use common::grid::ReadingOrder;
+use common::simulation::{run_until, run_until_stable, Simulation, StepResult};
+
+// What a cart does at an intersection
+#[derive(Debug, Clone, Copy)]
+enum Turn {
+    Straight,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone)]
+struct Cart {
+    x: usize,
+    y: usize,
+    direction: Direction,
+    next_turn: Turn,
+    has_moved: bool,
+}
+
+impl Cart {
+    fn new(x: usize, y: usize, direction: Direction) -> Cart {
+        // Carts always turn left first
+        Cart { x, y, direction, next_turn: Turn::Left, has_moved: false }
+    }
+
+    fn get_next_turn(&mut self) -> Turn {
+        let next_turn = self.next_turn;
+        match next_turn {
+            Turn::Left => self.next_turn = Turn::Straight,
+            Turn::Straight => self.next_turn = Turn::Right,
+            Turn::Right => self.next_turn = Turn::Left,
+        }
+        next_turn
+    }
+
+    fn move_to_next_pos(&mut self) -> (usize, usize) {
+        // Calculate next position
+        let offset = self.direction.offset();
+        let x = (self.x as i32 + offset.x) as usize;
+        let y = (self.y as i32 + offset.y) as usize;
+
+        // Update internal position
+        self.x = x;
+        self.y = y;
+        self.has_moved = true;
+
+        // Return new position
+        (x, y)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Map {
+    map_vec: Vec<char>,
+    width: usize,
+    height: usize,
+}
+
+impl Map {
+    fn get(&self, x: usize, y :usize) -> char {
+        self.map_vec[x + (self.width * y)]
+    }
+
+    fn set(&mut self, x: usize, y :usize, value: char) {
+        self.map_vec[x + (self.width * y)] = value;
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Tracks {
+    map: Map,
+    carts: Vec<Cart>,
+    // Where carts have crashed, in the order they crashed
+    crashes: Vec<(usize, usize)>,
+    // How many crashes there had been before this tick
+    crashes_before_tick: usize,
+    ticks: usize,
+    // When the first crash happens, if it's known, for slowing down around it
+    first_crash: Option<usize>,
+}
+
+// How many ticks either side of the first crash are eventful
+const AROUND_FIRST_CRASH: usize = 5;
+
+impl Tracks {
+    // Sort the carts vector so that it's in the order the carts move in
+    fn sort_carts(&mut self) {
+        self.carts.sort_by_key(|c| ReadingOrder(c.x, c.y));
+    }
+
+    // Cleanup required at the end of each tick
+    fn finish_tick(&mut self) {
+        self.carts.iter_mut().for_each(|c| c.has_moved = false);
+    }
+
+    // Move every cart which hasn't moved yet this tick, stopping at the first
+    // collision. Gives the location of the collision, if there was one
+    fn move_carts(&mut self) -> Result<Option<(usize, usize)>, AocError> {
+        self.sort_carts();
+        let mut carts_mut = self.carts.clone();
+        let mut collision_coord = None;
+
+        for (i, cart) in self.carts.iter().enumerate() {
+            // Skip any carts that have already moved this ticket
+            if cart.has_moved {
+                continue;
+            }
+
+            let mut c = cart.clone();
+
+            let (x, y) = c.move_to_next_pos();
+            let next_track = self.map.get(x, y);
+
+            match next_track {
+                '-' => {},
+                '|' => {},
+                '/' => {
+                    // Make a turn. Carts going across turn left and carts
+                    // going up or down turn right
+                    c.direction = match c.direction {
+                        Direction::Left | Direction::Right => c.direction.turn_left(),
+                        Direction::Up | Direction::Down => c.direction.turn_right(),
+                    };
+                },
+                '\\' => {
+                    // Make a turn, the opposite way to a /
+                    c.direction = match c.direction {
+                        Direction::Left | Direction::Right => c.direction.turn_right(),
+                        Direction::Up | Direction::Down => c.direction.turn_left(),
+                    };
+                },
+                '+' => {
+                    // Make an appropriate turn at the intersection
+                    match c.get_next_turn() {
+                        Turn::Left => c.direction = c.direction.turn_left(),
+                        Turn::Straight => {},
+                        Turn::Right => c.direction = c.direction.turn_right(),
+                    }
+                }
+                _ => {
+                    return Err(AocError::parse_input(format!("a cart goes off the track at \
+                                                             {},{}", x, y)));
+                },
+            }
+
+            // Check for collisions
+            for other_c in &carts_mut {
+                if c.x == other_c.x && c.y == other_c.y {
+                    collision_coord = Some((x, y));
+                    break;
+                }
+            }
+
+            // Update carts_mut
+            carts_mut[i] = c;
+
+            // Break if there's been a collision
+            if collision_coord.is_some() {
+                break;
+            }
+        }
+
+        self.carts = carts_mut;
+        Ok(collision_coord)
+    }
+}
+
+impl std::fmt::Display for Tracks {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        // Crashes are Xs, apart from ones which have just happened, which are
+        // *s so that they stand out
+        let mut printing_map = self.map.clone();
+        for (i, &(x, y)) in self.crashes.iter().enumerate() {
+            printing_map.set(x, y, if i < self.crashes_before_tick { 'X' } else { '*' });
+        }
+        for c in &self.carts {
+            printing_map.set(c.x, c.y, c.direction.arrow());
+        }
+        let mut map_string = String::new();
+        for row_index in 0..self.map.height {
+            let mut row: String = printing_map.map_vec.iter()
+                                    .skip(row_index * self.map.width)
+                                    .take(self.map.width).collect();
+            row.push('\n');
+            map_string.push_str(&row);
+        }
+        write!(f, "{}", map_string)
+    }
+}
+
+// Each step is a tick, in which every cart moves once. Carts which crash are
+// taken off the track, and once there's at most one cart left there's nothing
+// more to happen
+impl Simulation for Tracks {
+    fn step(&mut self) -> Result<StepResult, AocError> {
+        if self.carts.len() <= 1 {
+            return Ok(StepResult::Finished);
+        }
+
+        self.crashes_before_tick = self.crashes.len();
+        self.ticks += 1;
+        // We may need to call move_carts multiple times since we have to break
+        // out when there's a collision to remove the carts in question
+        while let Some((x, y)) = self.move_carts()? {
+            self.crashes.push((x, y));
+            let len_before = self.carts.len();
+            self.carts.retain(|c| c.x != x || c.y != y);
+            assert!(self.carts.len() + 2 == len_before);
+        }
+        self.finish_tick();
+
+        Ok(if self.carts.len() <= 1 { StepResult::Finished } else { StepResult::Changed })
+    }
+
+    fn render(&self) -> String {
+        self.to_string()
+    }
+
+    fn is_eventful(&self) -> bool {
+        self.first_crash.is_some_and(|tick| {
+            self.ticks + AROUND_FIRST_CRASH >= tick && self.ticks <= tick + AROUND_FIRST_CRASH
+        })
+    }
+}
+
+fn parse_tracks(input: &str) -> Tracks {
+    // Lines can be shorter than the widest one if they end in spaces which
+    // have been trimmed, so they're padded out with empty space
+    let height = input.lines().count();
+    let width = input.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+
+    let mut map_vec = vec![];
+    let mut carts = vec![];
+
+    for (y, line) in input.lines().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            let mut map_c = c;
+            // Replace carts with their underlying track to get a complete map
+            // of the track
+            if let Some(direction) = Direction::from_arrow(c) {
+                map_c = match direction {
+                    Direction::Left | Direction::Right => '-',
+                    Direction::Up | Direction::Down => '|',
+                };
+                carts.push(Cart::new(x, y, direction));
+            }
+            map_vec.push(map_c);
+        }
+        map_vec.resize((y + 1) * width, ' ');
+    }
+
+    let map = Map { map_vec, width, height };
+    Tracks { map, carts, crashes: vec![], crashes_before_tick: 0, ticks: 0, first_crash: None }
+}
+
+// The carts going round the tracks, for watching
+pub fn simulation(input: &str) -> Result<Box<dyn Simulation>, AocError> {
+    let mut tracks = parse_tracks(input);
+    // Run ahead to find the first crash, so that it can be watched slowly
+    let mut ahead = tracks.clone();
+    if run_until(&mut ahead, |t| !t.crashes.is_empty())? {
+        tracks.first_crash = Some(ahead.ticks);
+    }
+    Ok(Box::new(tracks))
+}
+
+// Part 1 where we stop at the first collision. The answer is its location as
+// "x,y"
+pub fn part1(input: &str) -> Result<String, AocError> {
+    let mut tracks = parse_tracks(input);
+    if !run_until(&mut tracks, |t| !t.crashes.is_empty())? {
+        return Err(AocError::NoSolution("no carts crash".to_string()));
+    }
+    let (x, y) = tracks.crashes[0];
+    Ok(format!("{},{}", x, y))
+}
+
+// Part 2 where we keep going until there's one cart left. The answer is its
+// location as "x,y"
+pub fn part2(input: &str) -> Result<String, AocError> {
+    let mut tracks = parse_tracks(input);
+    run_until_stable(&mut tracks)?;
+    // With an even number of carts there may be none left at the end
+    let last_cart = tracks.carts.first().ok_or_else(|| {
+        AocError::NoSolution("every cart crashed".to_string())
+    })?;
+    Ok(format!("{},{}", last_cart.x, last_cart.y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        let tracks = r"/->-\
+|   |  /----\
+| /-+--+-\  |
+| | |  | v  |
+\-+-/  \-+--/
+  \------/";
+        assert_eq!(part1(tracks).unwrap(), "7,3");
+    }
+
+    #[test]
+    fn part2_example() {
+        let tracks = r"/>-<\
+|   |
+| /<+-\
+| | | v
+\>+</ |
+  |   ^
+  \<->/";
+        assert_eq!(part2(tracks).unwrap(), "6,4");
+    }
+}
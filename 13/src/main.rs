@@ -0,0 +1,10 @@
+use common::AocError;
+
+fn main() -> Result<(), AocError> {
+    let input = common::read_input()?;
+
+    println!("First collision at {}!", day13::part1(&input)?);
+    println!("The last remaining cart is located at {}!", day13::part2(&input)?);
+
+    Ok(())
+}
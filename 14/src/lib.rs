@@ -0,0 +1,190 @@
+use common::AocError;
+
+#[derive(Debug)]
+struct Elf {
+    pos: usize,
+}
+
+#[derive(Debug)]
+struct RecipeBoard {
+    // The score of each recipe, which is always a single digit
+    recipes: Vec<u8>,
+    elves: [Elf; 2],
+}
+
+impl std::fmt::Display for RecipeBoard {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let elf1_pos = self.elves[0].pos;
+        let elf2_pos = self.elves[1].pos;
+        assert!(elf1_pos != elf2_pos);
+
+        let mut recipe_string = String::new();
+
+        for (i, r) in self.recipes.iter().enumerate() {
+            match i {
+                _ if i == elf1_pos => recipe_string.push('('),
+                _ if i == elf2_pos => recipe_string.push('['),
+                _ => recipe_string.push(' '),
+            }
+            recipe_string.push_str(&r.to_string());
+            match i {
+                _ if i == elf1_pos => recipe_string.push(')'),
+                _ if i == elf2_pos => recipe_string.push(']'),
+                _ => recipe_string.push(' '),
+            }
+        }
+        write!(f, "{}", recipe_string)
+    }
+}
+
+impl RecipeBoard {
+    fn new() -> RecipeBoard {
+        RecipeBoard {
+            recipes: vec![3, 7],
+            elves: [
+                Elf { pos: 0 },
+                Elf { pos: 1 },
+            ],
+        }
+    }
+
+    fn create_new_recipes(&mut self) {
+        let sum: u8 = self.elves.iter().map(|e| self.recipes[e.pos]).sum();
+
+        // Single digit sum
+        if sum < 10 {
+            self.recipes.push(sum);
+        }
+        // Double digit sum
+        else {
+            assert!(sum < 19);
+            self.recipes.push(1);
+            self.recipes.push(sum - 10);
+        }
+
+        // move the elves
+        for e in &mut self.elves {
+            let steps_forward = self.recipes[e.pos] as usize + 1;
+            // Cheaper than % in the hot loop. Each elf moves at most 10
+            // recipes, so it only goes round more than once while the board
+            // is tiny
+            e.pos += steps_forward;
+            while e.pos >= self.recipes.len() {
+                e.pos -= self.recipes.len();
+            }
+        }
+
+    }
+}
+
+// Looks for a sequence of digits in digits given one at a time, remembering
+// how much of the sequence the latest digits match so that each digit is only
+// looked at once (the Knuth-Morris-Pratt algorithm)
+struct SequenceMatcher {
+    sequence: Vec<u8>,
+    // For each length of match, how long the longest match is which is also
+    // a proper suffix of it, to fall back to when the next digit doesn't fit
+    fallback: Vec<usize>,
+    matched: usize,
+}
+
+impl SequenceMatcher {
+    fn new(sequence: Vec<u8>) -> SequenceMatcher {
+        let mut fallback = vec![0; sequence.len() + 1];
+        let mut k = 0;
+        for i in 1..sequence.len() {
+            while k > 0 && sequence[i] != sequence[k] {
+                k = fallback[k];
+            }
+            if sequence[i] == sequence[k] {
+                k += 1;
+            }
+            fallback[i + 1] = k;
+        }
+        SequenceMatcher { sequence, fallback, matched: 0 }
+    }
+
+    // Take the next digit. Returns true if the sequence ends with it
+    fn push(&mut self, digit: u8) -> bool {
+        if self.matched == self.sequence.len() {
+            self.matched = self.fallback[self.matched];
+        }
+        while self.matched > 0 && self.sequence[self.matched] != digit {
+            self.matched = self.fallback[self.matched];
+        }
+        if self.sequence[self.matched] == digit {
+            self.matched += 1;
+        }
+        self.matched == self.sequence.len()
+    }
+}
+
+pub fn part1(input: &str) -> Result<String, AocError> {
+    let input: usize = input.trim().parse().map_err(|e| AocError::parse(1, input, e))?;
+    let mut recipe_board = RecipeBoard::new();
+    //println!("{}", recipe_board);
+
+    loop {
+        recipe_board.create_new_recipes();
+        //println!("{}", recipe_board);
+
+        if recipe_board.recipes.len() > input + 10 {
+            break;
+        }
+    }
+    let next_10_scores = &recipe_board.recipes[input..input+10];
+    Ok(next_10_scores.iter().map(|&s| char::from(b'0' + s)).collect())
+}
+
+pub fn part2(input: &str) -> Result<String, AocError> {
+    let mut seq = vec![];
+    for c in input.trim().chars() {
+        let digit = c.to_digit(10)
+            .ok_or_else(|| AocError::parse(1, input, format!("'{}' isn't a digit", c)))?;
+        seq.push(digit as u8);
+    }
+    if seq.is_empty() {
+        return Err(AocError::parse_input("there's no sequence of scores to look for"));
+    }
+    let seq_len = seq.len();
+    let mut matcher = SequenceMatcher::new(seq);
+
+    let mut recipe_board = RecipeBoard::new();
+    //println!("{}", recipe_board);
+
+    // How many recipes have been given to the matcher. The board grows by up
+    // to 2 recipes each iteration, and each of them could end the sequence
+    let mut checked = 0;
+    loop {
+        while checked < recipe_board.recipes.len() {
+            checked += 1;
+            if matcher.push(recipe_board.recipes[checked - 1]) {
+                return Ok((checked - seq_len).to_string());
+            }
+        }
+
+        recipe_board.create_new_recipes();
+        //println!("{}", recipe_board);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(part1("9").unwrap(), "5158916779");
+        assert_eq!(part1("5").unwrap(), "0124515891");
+        assert_eq!(part1("18").unwrap(), "9251071085");
+        assert_eq!(part1("2018").unwrap(), "5941429882");
+    }
+
+    #[test]
+    fn part2_examples() {
+        assert_eq!(part2("51589").unwrap(), "9");
+        assert_eq!(part2("01245").unwrap(), "5");
+        assert_eq!(part2("92510").unwrap(), "18");
+        assert_eq!(part2("59414").unwrap(), "2018");
+    }
+}
@@ -0,0 +1,12 @@
+use common::AocError;
+
+fn main() -> Result<(), AocError> {
+    let input = common::read_input()?;
+
+    println!("The next 10 scores after {} recipes are: {}",
+             input.trim(), day14::part1(&input)?);
+    println!("There are {} recipes to the left of sequence {}",
+             day14::part2(&input)?, input.trim());
+
+    Ok(())
+}
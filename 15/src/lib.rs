@@ -0,0 +1,785 @@
+use std::cmp::Ordering;
+use std::collections::{HashSet, VecDeque};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicI32, Ordering as AtomicOrdering};
+
+use log::debug;
+
+use common::AocError;
+use common::grid::{Grid, ReadingOrder};
+use common::simulation::{run_until, run_until_stable, Simulation, StepResult};
+use common::viz::animation::{text_width, Animation, Picture, Rgb, TEXT_HEIGHT};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct UnitData {
+    hp: i32,
+    attack: i32,
+    had_turn: bool,
+}
+
+impl UnitData {
+    fn new() -> UnitData {
+        UnitData { hp: 200, attack: 3, had_turn: false }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum SquareType {
+    Open,
+    Wall,
+    Elf,
+    Goblin
+}
+use self::SquareType::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Square {
+    x: usize,
+    y: usize,
+    t: SquareType,
+    data: Option<UnitData>,
+}
+
+impl Square {
+    fn new(x: usize, y: usize, c: char) -> Result<Square, String> {
+        match c {
+            '.' => {
+                Ok(Square { x, y, t: Open, data: None })
+            },
+            '#' => {
+                Ok(Square { x, y, t: Wall, data: None })
+            },
+            'E' => {
+                Ok(Square { x, y, t: Elf, data: Some(UnitData::new()) })
+            },
+            'G' => {
+                Ok(Square { x, y, t: Goblin, data: Some(UnitData::new()) })
+            },
+            _ => Err(format!("'{}' isn't open floor, a wall, an elf or a goblin", c)),
+        }
+    }
+}
+
+impl PartialOrd for Square {
+    // Order squares by reading order
+    fn partial_cmp(&self, other: &Square) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Square {
+    // Order squares by reading order
+    fn cmp(&self, other: &Square) -> Ordering {
+        ReadingOrder(self.x, self.y).cmp(&ReadingOrder(other.x, other.y))
+    }
+}
+
+// What a unit does with the moving part of its turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Move {
+    // There are no enemies left, so the battle's over
+    NoEnemies,
+    // It's already next to an enemy, or it can't get next to any
+    Stay,
+    // The square it steps into
+    Step(usize, usize),
+}
+
+#[derive(Debug, Clone)]
+struct Map {
+    map_vec: Vec<Square>,
+    width: usize,
+    height: usize,
+    // The number of complete rounds of the battle so far
+    rounds: usize,
+}
+
+impl Map {
+    fn get(&self, x: usize, y :usize) -> Square {
+        self.map_vec[x + (self.width * y)].clone()
+    }
+
+    fn get_mut_ref(&mut self, x: usize, y: usize) -> &mut Square {
+        &mut self.map_vec[x + (self.width * y)]
+    }
+
+    fn get_neighbours(&self, square: &Square) -> Vec<Square> {
+        let x = square.x;
+        let y = square.y;
+        vec![
+            // Order the neighbours by reading order
+            self.get(x, y - 1),
+            self.get(x - 1, y),
+            self.get(x + 1, y),
+            self.get(x, y + 1),
+        ]
+    }
+
+    fn get_possible_moves(&self, square: &Square) -> Vec<(Square, usize)> {
+        let mut neighbours = self.get_neighbours(square);
+        // Can only move into open squares
+        neighbours.retain(|s| s.t == Open);
+        // Map into a vector of possible moves and their distance costs
+        neighbours.iter().map(|s| (s.clone(), 1)).collect()
+    }
+
+    fn get_targets_in_range(&self, square: &Square) -> Vec<Square> {
+        let enemy = match square.t {
+            Elf => Goblin,
+            Goblin => Elf,
+            _ => unreachable!(),
+        };
+        let mut neighbours = self.get_neighbours(square);
+        neighbours.retain(|s| s.t == enemy);
+        neighbours
+    }
+
+    // The returned units vector is in reading order
+    fn get_units_mut(&mut self) -> Vec<&mut Square> {
+        let mut units_mut = vec![];
+        for s in self.map_vec.iter_mut() {
+            match s.t {
+                Open | Wall => {},
+                _ => units_mut.push(s),
+            }
+        }
+        units_mut
+    }
+
+    // The returned units vector is in reading order
+    fn get_units(&self) -> Vec<Square> {
+        let mut units = vec![];
+        for s in &self.map_vec {
+            match s.t {
+                Open | Wall => {},
+                _ => units.push(s.clone()),
+            }
+        }
+        units
+    }
+
+    // The returned elves vector is in reading order
+    fn get_elves(&self) -> Vec<Square> {
+        let mut elves = self.get_units();
+        elves.retain(|s| s.t == Elf);
+        elves
+    }
+
+    // The returned elves vector is in reading order
+    fn get_elves_mut(&mut self) -> Vec<&mut Square> {
+        let mut elves_mut = self.get_units_mut();
+        elves_mut.retain(|s| s.t == Elf);
+        elves_mut
+    }
+
+    // The returned goblins vector is in reading order
+    fn get_goblins(&self) -> Vec<Square> {
+        let mut goblins = self.get_units();
+        goblins.retain(|s| s.t == Goblin);
+        goblins
+    }
+
+    // Returns true if the battle finished during the turn, otherwise false
+    fn take_turn(&mut self) -> bool {
+        for u in self.get_units_mut() {
+            u.data.as_mut().expect("Unit doesn't have data").had_turn = false;
+        }
+
+
+        // MOVEMENT
+        loop {
+            let mut next_square = None;
+            let mut x = None;
+            let mut y = None;
+
+            // Iterate to find a unit that hasn't moved yet
+            for unit in self.get_units() {
+                if unit.data.clone().expect("Unit doesn't have data").had_turn {
+                    continue;
+                }
+
+                // This is needed for attacking etc. later
+                x = Some(unit.x);
+                y = Some(unit.y);
+
+                match self.choose_move(&unit) {
+                    Move::NoEnemies => return true,
+                    Move::Stay => {},
+                    Move::Step(next_x, next_y) => next_square = Some(self.get(next_x, next_y)),
+                }
+                break;
+            }
+
+            // All units have taken turns so return
+            if x.is_none() {
+                return false;
+            }
+
+            let unit = self.get_mut_ref(x.unwrap(), y.unwrap());
+            unit.data.as_mut().unwrap().had_turn = true;
+
+            // Actually move the unit on the map if appropriate
+            if let Some(ns) = next_square {
+                let unit_type = unit.t.clone();
+                let unit_data = unit.data.clone();
+
+                unit.t = Open;
+                unit.data = None;
+
+                let moved_unit = self.get_mut_ref(ns.x, ns.y);
+                moved_unit.t = unit_type;
+                moved_unit.data = unit_data;
+
+                // Update x and y, they might be needed during attacking
+                x = Some(ns.x);
+                y = Some(ns.y);
+            }
+
+
+            // ATTACK
+            let u = self.get(x.unwrap(), y.unwrap());
+            let targets_in_range = self.get_targets_in_range(&u);
+
+            if targets_in_range.is_empty() {
+                continue;
+            }
+
+            let mut min_hp = 201;
+            let mut best_target = None;
+            for mut t in targets_in_range {
+                let hp = t.data.as_mut().expect("Unit has no data").hp;
+                if hp < min_hp {
+                    min_hp = hp;
+                    best_target = Some(t.clone());
+                }
+                else if hp == min_hp {
+                    // Should order by reading order because of Ord impl
+                    if t < best_target.clone().unwrap() {
+                        best_target = Some(t.clone());
+                    }
+                }
+            }
+
+            let target = best_target.expect("Error finding best target");
+            let target_mut = self.get_mut_ref(target.x, target.y);
+
+            // Do the attack
+            target_mut.data.as_mut().unwrap().hp
+                -= u.data.as_ref().unwrap().attack;
+
+            // Remove the victim if it has died
+            if target_mut.data.as_mut().unwrap().hp <= 0 {
+                target_mut.data = None;
+                target_mut.t = Open;
+            }
+        }
+    }
+}
+
+impl Map {
+    // Where [unit] moves on its turn
+    fn choose_move(&self, unit: &Square) -> Move {
+        // Don't move if already in range of a target
+        if !self.get_targets_in_range(unit).is_empty() {
+            return Move::Stay;
+        }
+
+        // Get a list of potential targets
+        let potential_targets = match unit.t {
+            Elf => self.get_goblins(),
+            Goblin => self.get_elves(),
+            _ => unreachable!(),
+        };
+
+        // The battle ends if there's no potential targets left
+        if potential_targets.is_empty() {
+            return Move::NoEnemies;
+        }
+
+        // Work out which squares are in range of a potential target
+        let in_range: HashSet<_>
+            = potential_targets.iter()
+                               .flat_map(|s| self.get_possible_moves(s))
+                               .map(|(s, _)| (s.x, s.y))
+                               .collect();
+
+        // Work out the target square we're trying to move towards. It's
+        // the closest one we can reach, with ties broken by reading
+        // order. One flood out from the unit gives the distance to
+        // every square in range at once
+        let start = (unit.x, unit.y);
+        let from_unit = self.flood(start);
+        let nearest = in_range.iter()
+            .filter_map(|&(x, y)| from_unit[(x, y)].map(|d| (d, ReadingOrder(x, y))))
+            .min();
+
+        // Stay put if there's no reachable targets
+        let (min_dist, ReadingOrder(target_x, target_y)) = match nearest {
+            Some(nearest) => nearest,
+            None => return Move::Stay,
+        };
+
+        // Now we've got a target square we consider each of the (up to)
+        // 4 possible next steps from our current position. We eliminate
+        // all squares except those satisfying the minimum distance to
+        // our target which we found earlier, using a flood back from the
+        // target. The steps are in reading order so we can then just take
+        // the first one as our next square to move to.
+        let from_target = self.flood((target_x, target_y));
+        let (next_x, next_y) = self.open_neighbours(start)
+            .find(|&p| from_target[p] == Some(min_dist - 1))
+            .expect("There's no first step towards the target");
+        Move::Step(next_x, next_y)
+    }
+
+    // The open squares next to [x], [y], which are the only ones units can
+    // move into, in reading order
+    fn open_neighbours(&self, (x, y): (usize, usize))
+            -> impl Iterator<Item = (usize, usize)> + '_ {
+        IntoIterator::into_iter([(x, y - 1), (x - 1, y), (x + 1, y), (x, y + 1)])
+            .filter(move |&(x, y)| self.map_vec[x + self.width * y].t == Open)
+    }
+
+    // How many steps it takes to get from [start] to every square which can be
+    // got to through open squares, and None for the rest
+    fn flood(&self, start: (usize, usize)) -> Grid<Option<usize>> {
+        let mut distances = Grid::new(self.width, self.height, None);
+        distances[start] = Some(0);
+        let mut queue = VecDeque::from(vec![(start, 0)]);
+        while let Some((p, distance)) = queue.pop_front() {
+            for next in self.open_neighbours(p) {
+                if distances[next].is_none() {
+                    distances[next] = Some(distance + 1);
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+        distances
+    }
+}
+
+impl std::fmt::Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let printing_map_vec = self.map_vec.iter().map(|s| match s.t {
+                                                            Open => '.',
+                                                            Wall => '#',
+                                                            Elf => 'E',
+                                                            Goblin => 'G',
+                                                        }).collect::<Vec<_>>();
+        let mut map_string = String::new();
+        for row_index in 0..self.height {
+            let mut row: String = printing_map_vec.iter()
+                                    .skip(row_index * self.width)
+                                    .take(self.width).collect();
+            row.push('\n');
+            map_string.push_str(&row);
+        }
+        write!(f, "{}", map_string)
+    }
+}
+
+// Each step is a round of the battle, and the battle finishing part way through
+// a round is the end
+impl Simulation for Map {
+    fn step(&mut self) -> Result<StepResult, AocError> {
+        if self.take_turn() {
+            return Ok(StepResult::Finished);
+        }
+        self.rounds += 1;
+        debug!("After {} rounds:\n{}", self.rounds, self);
+        Ok(StepResult::Changed)
+    }
+
+    fn render(&self) -> String {
+        self.to_string()
+    }
+}
+
+// The map has to be a rectangle with walls all the way round, since units
+// look at the squares next to them without checking they're on the map
+fn parse_map(input: &str) -> Result<Map, AocError> {
+    let height = input.lines().count();
+    let width = input.lines().next().map_or(0, |line| line.chars().count());
+    if width == 0 {
+        return Err(AocError::parse_input("there's no map"));
+    }
+
+    let mut map_vec = vec![];
+    for (y, line) in input.lines().enumerate() {
+        if line.chars().count() != width {
+            return Err(AocError::parse(y + 1, line, format!("the map isn't {} squares wide like \
+                                                       the first line", width)));
+        }
+        for (x, c) in line.chars().enumerate() {
+            let square = Square::new(x, y, c).map_err(|e| AocError::parse(y + 1, line, e))?;
+            let edge = x == 0 || y == 0 || x == width - 1 || y == height - 1;
+            if edge && square.t != Wall {
+                return Err(AocError::parse(y + 1, line, "the map isn't surrounded by walls"));
+            }
+            map_vec.push(square);
+        }
+    }
+    Ok(Map { map_vec, width, height, rounds: 0 })
+}
+
+// Just read the map, for fuzzing the parser
+pub fn parse(input: &str) -> Result<(), AocError> {
+    parse_map(input).map(|_| ())
+}
+
+// The battle without any boost, for watching
+pub fn simulation(input: &str) -> Result<Box<dyn Simulation>, AocError> {
+    Ok(Box::new(parse_map(input)?))
+}
+
+// How many pixels across each square is in the animation, and how big the
+// writing above the map is
+const SQUARE: usize = 8;
+const TEXT_SCALE: usize = 2;
+const CAPTION_HEIGHT: usize = (TEXT_HEIGHT + 2) * TEXT_SCALE;
+
+const FLOOR: Rgb = [24, 24, 28];
+const WALL: Rgb = [80, 80, 92];
+const WRITING: Rgb = [230, 230, 230];
+const ELF_COLOUR: Rgb = [80, 220, 100];
+const GOBLIN_COLOUR: Rgb = [235, 70, 60];
+
+// How long the first picture, each round and the outcome are shown for, in
+// hundredths of a second
+const FIRST_DELAY: u16 = 100;
+const ROUND_DELAY: u16 = 12;
+const LAST_DELAY: u16 = 400;
+
+// Units get darker as they're hurt, from their full colour at 200 hit points
+// down to a third of it. Hit points are rounded up to tenths, so that there
+// aren't too many colours for a GIF
+fn shade(colour: Rgb, hp: i32) -> Rgb {
+    let tenths = ((hp.clamp(0, 200) * 10 + 199) / 200) as u32;
+    colour.map(|c| (u32::from(c) * (10 + 3 * tenths) / 40) as u8)
+}
+
+// A unit is drawn as a square with its corners cut off, a pixel in from the
+// edges of its square
+fn draw_unit(picture: &mut Picture, x: usize, y: usize, colour: Rgb) {
+    picture.rect(x + 2, y + 1, SQUARE - 4, SQUARE - 2, colour);
+    picture.rect(x + 1, y + 2, SQUARE - 2, SQUARE - 4, colour);
+}
+
+// What's written above the map: the round, and how many of each side are
+// left or who's won
+fn caption(map: &Map, finished: bool) -> Vec<(String, Rgb)> {
+    let (elves, goblins) = (map.get_elves().len(), map.get_goblins().len());
+    let mut caption = vec![(format!("Round {}", map.rounds), WRITING)];
+    match (finished, elves) {
+        (true, 0) => caption.push(("Goblins win".to_string(), GOBLIN_COLOUR)),
+        (true, _) => caption.push(("Elves win".to_string(), ELF_COLOUR)),
+        (false, _) => {
+            caption.push((format!("Elves {}", elves), ELF_COLOUR));
+            caption.push((format!("Goblins {}", goblins), GOBLIN_COLOUR));
+        },
+    }
+    caption
+}
+
+// The map with the caption above it, [width] pixels across
+fn battle_picture(map: &Map, finished: bool, width: usize) -> Picture {
+    let mut picture = Picture::new(width, CAPTION_HEIGHT + map.height * SQUARE, FLOOR);
+    picture.rect(0, 0, width, CAPTION_HEIGHT, [0, 0, 0]);
+    let mut x = TEXT_SCALE;
+    for (text, colour) in caption(map, finished) {
+        picture.text(x, TEXT_SCALE, &text, TEXT_SCALE, colour);
+        x += text_width(&format!("{}  ", text), TEXT_SCALE) + TEXT_SCALE;
+    }
+
+    for square in &map.map_vec {
+        let (x, y) = (square.x * SQUARE, CAPTION_HEIGHT + square.y * SQUARE);
+        match (&square.t, &square.data) {
+            (Wall, _) => picture.rect(x, y, SQUARE, SQUARE, WALL),
+            (Elf, Some(unit)) => draw_unit(&mut picture, x, y, shade(ELF_COLOUR, unit.hp)),
+            (Goblin, Some(unit)) => draw_unit(&mut picture, x, y, shade(GOBLIN_COLOUR, unit.hp)),
+            _ => {},
+        }
+    }
+    picture
+}
+
+// The battle without any boost as an animation, with a picture after each
+// round and one of the outcome. Elves are green and goblins red, and units
+// get darker as they're hurt
+pub fn animation(input: &str) -> Result<Animation, AocError> {
+    let mut map = parse_map(input)?;
+    // Wide enough for the map and the longest caption there'll be
+    let longest = format!("Round 000  Elves {0}  Goblins {0}", map.get_units().len());
+    let width = (map.width * SQUARE).max(text_width(&longest, TEXT_SCALE) + 2 * TEXT_SCALE);
+
+    let mut animation = Animation::new();
+    animation.push(battle_picture(&map, false, width), FIRST_DELAY);
+    while map.step()? == StepResult::Changed {
+        animation.push(battle_picture(&map, false, width), ROUND_DELAY);
+    }
+    animation.push(battle_picture(&map, true, width), LAST_DELAY);
+    Ok(animation)
+}
+
+pub fn part1(input: &str) -> Result<String, AocError> {
+    let starting_map = parse_map(input)?;
+    let (outcome, _) = resolve_battle(&starting_map)?;
+    Ok(outcome.to_string())
+}
+
+// The highest attack boost worth trying. At 197 every elf kills a goblin with
+// each hit, so no higher boost can go any differently
+const MAX_BOOST: i32 = 197;
+
+// How a battle with boosted elves went, as far as part 2 cares
+enum Trial {
+    // Every elf survived, with this outcome
+    Flawless(i32),
+    ElfDied,
+    // The battle was abandoned before either
+    Cancelled,
+}
+
+// Fight the battle with the elves' attack boosted by [boost], giving up as
+// soon as an elf dies or [cancelled] becomes true between rounds
+fn try_boost(starting_map: &Map, boost: i32, cancelled: impl Fn() -> bool)
+        -> Result<Trial, AocError> {
+    let mut map = starting_map.clone();
+    map.get_elves_mut().iter_mut().for_each(|e| {
+        e.data.as_mut().unwrap().attack += boost;
+    });
+    let elves = map.get_elves().len();
+
+    let stopped = run_until(&mut map, |m| m.get_elves().len() < elves || cancelled())?;
+    if map.get_elves().len() < elves {
+        return Ok(Trial::ElfDied);
+    }
+    if stopped {
+        return Ok(Trial::Cancelled);
+    }
+    let total_hp: i32 = map.get_units_mut().iter_mut()
+        .map(|u| u.data.as_mut().unwrap().hp)
+        .sum();
+    Ok(Trial::Flawless(total_hp * map.rounds as i32))
+}
+
+// Find the smallest attack boost which lets the elves win without losing
+// anyone. The answer is the outcome of that battle. Whether elves die doesn't
+// always go down as the boost goes up, so every boost has to be tried in turn
+// until one works. They're shared out in order between as many workers as the
+// thread pool has. Once a boost works, or its battle fails, the battles with
+// higher boosts are abandoned and no more are started. Every lower boost was
+// handed out earlier, so they're all fought to the end, and the lowest boost
+// which ended the search is the answer once every worker has finished
+pub fn part2(input: &str) -> Result<String, AocError> {
+    let starting_map = parse_map(input)?;
+    let next_boost = AtomicI32::new(1);
+    // The lowest boost found so far which ends the search
+    let lowest_done = AtomicI32::new(i32::MAX);
+    let found: Mutex<Option<(i32, Result<i32, AocError>)>> = Mutex::new(None);
+
+    rayon::scope(|s| {
+        for _ in 0..rayon::current_num_threads() {
+            s.spawn(|_| loop {
+                let boost = next_boost.fetch_add(1, AtomicOrdering::SeqCst);
+                if boost > MAX_BOOST || boost > lowest_done.load(AtomicOrdering::SeqCst) {
+                    break;
+                }
+                debug!("Trying an attack boost of {}", boost);
+                let cancelled = || lowest_done.load(AtomicOrdering::Relaxed) < boost;
+                let result = match try_boost(&starting_map, boost, cancelled) {
+                    Ok(Trial::Flawless(outcome)) => Ok(outcome),
+                    Ok(Trial::ElfDied) | Ok(Trial::Cancelled) => continue,
+                    Err(e) => Err(e),
+                };
+                lowest_done.fetch_min(boost, AtomicOrdering::SeqCst);
+                let mut found = found.lock().unwrap();
+                if found.as_ref().is_none_or(|&(lowest, _)| boost < lowest) {
+                    *found = Some((boost, result));
+                }
+            });
+        }
+    });
+
+    match found.into_inner().unwrap() {
+        Some((boost, result)) => {
+            debug!("The elves win without losses with an attack boost of {}", boost);
+            Ok(result?.to_string())
+        },
+        None => Err(AocError::NoSolution("the elves can't win without losses with \
+                                          any attack boost".to_string())),
+    }
+}
+
+// Resolve a battle. Returning the outcome and the number of remaining elves
+fn resolve_battle(starting_map: &Map) -> Result<(i32, usize), AocError> {
+    let mut map = starting_map.clone();
+    let complete_rounds = run_until_stable(&mut map)? as i32;
+
+    let mut units = map.get_units_mut();
+    let total_hp: i32 = units.iter_mut()
+                             .map(|u| u.data.as_mut().unwrap().hp)
+                             .sum();
+
+    Ok((total_hp * complete_rounds, map.get_elves().len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::fixtures;
+
+    // The battles from the puzzle description, with the answers for each part.
+    // The second one has no answer for part 2
+    const EXAMPLES: [(&str, &str, Option<&str>); 6] = [
+        (fixtures::DAY15, "27730", Some("4988")),
+        ("\
+#######
+#G..#E#
+#E#E.E#
+#G.##.#
+#...#E#
+#...E.#
+#######", "36334", None),
+        ("\
+#######
+#E..EG#
+#.#G.E#
+#E.##E#
+#G..#.#
+#..E#.#
+#######", "39514", Some("31284")),
+        ("\
+#######
+#E.G#.#
+#.#G..#
+#G.#.G#
+#G..#.#
+#...E.#
+#######", "27755", Some("3478")),
+        ("\
+#######
+#.E...#
+#.#..G#
+#.###.#
+#E#G#G#
+#...#G#
+#######", "28944", Some("6474")),
+        ("\
+#########
+#G......#
+#.E.#...#
+#..##..G#
+#...##..#
+#...#...#
+#.G...G.#
+#.....G.#
+#########", "18740", Some("1140")),
+    ];
+
+    // The first example's battle at the start, after a round and at the end
+    #[test]
+    fn battle_snapshots() {
+        let mut map = parse_map(EXAMPLES[0].0).unwrap();
+        let mut pictures = vec![map.to_string()];
+        map.step().unwrap();
+        pictures.push(map.to_string());
+        while map.step().unwrap() != StepResult::Finished {}
+        pictures.push(map.to_string());
+        common::assert_snapshot!("example_battle", pictures.join("\n"));
+    }
+
+    // Where the unit at [x], [y] on [map] moves on its turn
+    fn move_of(map: &str, x: usize, y: usize) -> Move {
+        let map = parse_map(map).unwrap();
+        map.choose_move(&map.get(x, y))
+    }
+
+    // The squares next to the goblin are both one step away, and the one
+    // above it comes first in reading order, so the elf goes left rather
+    // than down
+    #[test]
+    fn nearest_squares_tie_broken_by_reading_order() {
+        let map = "\
+#####
+#...#
+#..E#
+#.G.#
+#####";
+        assert_eq!(move_of(map, 3, 2), Move::Step(2, 2));
+    }
+
+    // The only square next to the goblin is three steps away whether the
+    // elf starts by going right or down, and right comes first in reading
+    // order
+    #[test]
+    fn first_steps_tie_broken_by_reading_order() {
+        let map = "\
+#####
+#E..#
+#...#
+#.#G#
+#####";
+        assert_eq!(move_of(map, 1, 1), Move::Step(2, 1));
+    }
+
+    #[test]
+    fn blocked_units_stay() {
+        // Walled in on one side and by another elf on the other
+        let map = "\
+#####
+#E#.#
+#E..#
+#..G#
+#####";
+        assert_eq!(move_of(map, 1, 1), Move::Stay);
+        // With free squares next to the goblin but no way through to them
+        let map = "\
+#####
+#E..#
+#####
+#G..#
+#####";
+        assert_eq!(move_of(map, 1, 1), Move::Stay);
+    }
+
+    // The elf is next to a goblin so it stays to fight it, even though the
+    // other goblin has a free square next to it
+    #[test]
+    fn units_in_range_stay() {
+        let map = "\
+#####
+#E..#
+#G..#
+#..G#
+#####";
+        assert_eq!(move_of(map, 1, 1), Move::Stay);
+        assert_eq!(move_of("#####\n#E..#\n#...#\n#...#\n#####", 1, 1), Move::NoEnemies);
+    }
+
+    #[test]
+    fn bad_maps() {
+        assert!(parse_map("").is_err());
+        assert!(parse_map("###\n#E#\n##").is_err());
+        assert!(parse_map("###\n#X#\n###").is_err());
+        assert!(parse_map("###\n.E#\n###").is_err());
+        assert!(parse_map("#####\n#E.G#\n#####").is_ok());
+    }
+
+    #[test]
+    fn part1_examples() {
+        for &(battle, outcome, _) in &EXAMPLES {
+            assert_eq!(part1(battle).unwrap(), outcome, "\n{}", battle);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "slow-tests"), ignore = "slow, run with --features slow-tests")]
+    fn part2_examples() {
+        for &(battle, _, outcome) in &EXAMPLES {
+            if let Some(outcome) = outcome {
+                assert_eq!(part2(battle).unwrap(), outcome, "\n{}", battle);
+            }
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+
+use common::AocError;
+use common::bitset::BitSet;
+use common::combin;
+use common::elfcode::Op;
+use common::parse;
+
+// The device's registers. It has the same operations as the elfcode machine
+// which days 19 and 21 run programs on, but only four registers
+type Registers = [i64; 4];
+
+#[derive(Debug)]
+struct TestCase {
+    before: Registers,
+    opcode: i64,
+    a: i64,
+    b: i64,
+    c: i64,
+    after: Registers,
+}
+
+// The instructions' operands are only ever 0 to 3, whether they're registers
+// or values, so anything else is turned away when it's read and running an
+// operation can't go outside the registers
+fn check_operands(operands: &[i64]) -> Result<(), String> {
+    match operands.iter().find(|&&r| !(0..4).contains(&r)) {
+        Some(r) => Err(format!("register {} is out of range", r)),
+        None => Ok(()),
+    }
+}
+
+// Run [op] on [registers], writing the result to [c]
+fn execute(registers: &mut Registers, op: Op, a: i64, b: i64, c: i64) {
+    registers[c as usize] = op.apply(registers, a, b);
+}
+
+fn try_instruction(t: &TestCase, op: Op) -> bool {
+    let mut registers = t.before;
+    execute(&mut registers, op, t.a, t.b, t.c);
+    registers == t.after
+}
+
+// The IDs of the operations which behave like the test case, where an
+// operation's ID is where it is in Op::ALL
+fn run_test_case(t: &TestCase) -> BitSet {
+    let mut matching = BitSet::new();
+    for (id, &op) in Op::ALL.iter().enumerate() {
+        if try_instruction(t, op) {
+            matching.insert(id);
+        }
+    }
+    matching
+}
+
+// A test case is three lines, like
+//     Before: [3, 2, 1, 1]
+//     9 2 1 2
+//     After:  [3, 2, 2, 1]
+fn parse_test_case(line0: &str, line1: &str, line2: &str) -> Result<TestCase, String> {
+    let before = parse::fixed_ints(line0)?;
+    let [opcode, a, b, c] = parse::fixed_ints(line1)?;
+    let after = parse::fixed_ints(line2)?;
+    Ok(TestCase { before, opcode, a, b, c, after })
+}
+
+#[derive(Debug)]
+struct Command {
+    // Which line of the input the command is on, counting from 1
+    line: usize,
+    opcode: i64,
+    a: i64,
+    b: i64,
+    c: i64,
+}
+
+fn parse_input(input: &str) -> Result<(Vec<TestCase>, Vec<Command>), AocError> {
+    let lines: Vec<_> = input.lines().collect();
+
+    let mut test_cases = vec![];
+    let mut commands = vec![];
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+
+        if line.contains("Before") {
+            if i + 2 >= lines.len() {
+                return Err(AocError::parse(i + 1, line, "the test case is cut short"));
+            }
+            let tc = parse_test_case(lines[i], lines[i + 1], lines[i + 2])
+                .map_err(|e| AocError::parse(i + 1, line, e))?;
+            check_operands(&[tc.a, tc.b, tc.c])
+                .map_err(|e| AocError::parse(i + 2, lines[i + 1], e))?;
+            test_cases.push(tc);
+
+            i += 3;
+            continue;
+        }
+
+        if !line.is_empty() {
+            let [opcode, a, b, c] = parse::fixed_ints(line)
+                .map_err(|e| AocError::parse(i + 1, line, e))?;
+            check_operands(&[a, b, c]).map_err(|e| AocError::parse(i + 1, line, e))?;
+            commands.push(Command { line: i + 1, opcode, a, b, c });
+        }
+
+        i += 1;
+    }
+
+    Ok((test_cases, commands))
+}
+
+pub fn part1(input: &str) -> Result<String, AocError> {
+    let (test_cases, _) = parse_input(input)?;
+
+    let gt3_count = test_cases.iter()
+        .filter(|t| run_test_case(t).len() >= 3)
+        .count();
+    Ok(gt3_count.to_string())
+}
+
+// When there are no more opcodes which can only be one instruction, try every
+// way of giving the remaining opcodes the remaining instructions. The samples
+// only show which opcode is which if exactly one of the ways fits them. There
+// are n! ways for n opcodes, so only a few opcodes can be left
+fn search_mappings(unresolved: &[(i64, BitSet)]) -> Option<Vec<(i64, usize)>> {
+    let ids: Vec<_> = unresolved.iter().fold(BitSet::new(), |all, &(_, ids)| all | ids)
+        .iter().collect();
+    if unresolved.len() > 8 || ids.len() != unresolved.len() {
+        return None;
+    }
+
+    let mut fits = combin::permutations(&ids).filter(|ordering| {
+        unresolved.iter().zip(ordering).all(|(&(_, ids), &id)| ids.contains(id))
+    });
+    let mapping = fits.next()?;
+    if fits.next().is_some() {
+        return None;
+    }
+    Some(unresolved.iter().zip(mapping).map(|(&(opcode, _), id)| (opcode, id)).collect())
+}
+
+pub fn part2(input: &str) -> Result<String, AocError> {
+    let (test_cases, commands) = parse_input(input)?;
+
+    let mut opcode_matches: HashMap<i64, BitSet> = HashMap::new();
+    for t in &test_cases {
+        *opcode_matches.entry(t.opcode).or_default() |= run_test_case(t);
+    }
+
+    // Work out the mapping between the opcodes in the input and our internal
+    // IDs for the different instructions
+    let mut opcode_to_ids = HashMap::new();
+    while opcode_to_ids.len() != 16 {
+
+        let known_mappings: Vec<_> = opcode_matches.iter()
+            .filter(|(_, v)| v.len() == 1)
+            .map(|(&opcode, &ids)| (opcode, ids))
+            .collect();
+        if known_mappings.is_empty() {
+            let unresolved: Vec<_> = opcode_matches.iter()
+                .filter(|&(opcode, _)| !opcode_to_ids.contains_key(opcode))
+                .map(|(&opcode, &ids)| (opcode, ids))
+                .collect();
+            let mappings = search_mappings(&unresolved).ok_or_else(|| {
+                AocError::NoSolution("the samples don't show which opcode is which".to_string())
+            })?;
+            opcode_to_ids.extend(mappings);
+            break;
+        }
+
+        for (opcode, ids) in known_mappings {
+            assert_eq!(ids.len(), 1);
+            let known_id = ids.first().unwrap();
+
+            opcode_to_ids.insert(opcode, known_id);
+            opcode_matches.values_mut().for_each(|v| { v.remove(known_id); });
+        }
+    }
+
+    let mut registers = [0; 4];
+    for command in commands {
+        let id = opcode_to_ids.get(&command.opcode).ok_or_else(|| {
+            let line = input.lines().nth(command.line - 1).unwrap_or("");
+            AocError::parse(command.line, line, format!("opcode {} isn't in any of the samples",
+                                                        command.opcode))
+        })?;
+        execute(&mut registers, Op::ALL[*id], command.a, command.b, command.c);
+    }
+
+    // The answer is the value left in register 0
+    Ok(registers[0].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The sample from the puzzle description, which behaves like mulr, addi
+    // and seti. There's no example for part 2
+    #[test]
+    fn part1_example() {
+        let sample = "Before: [3, 2, 1, 1]\n9 2 1 2\nAfter:  [3, 2, 2, 1]\n";
+        let (test_cases, _) = parse_input(sample).unwrap();
+        assert_eq!(run_test_case(&test_cases[0]).len(), 3);
+        assert_eq!(part1(sample).unwrap(), "1");
+    }
+
+    #[test]
+    fn registers_out_of_range() {
+        let error = |input| parse_input(input).unwrap_err().to_string();
+        assert_eq!(error("Before: [0, 0, 0, 0]\n0 7 0 0\nAfter:  [0, 0, 0, 0]"),
+                   "Failed to parse line 2, `0 7 0 0`: register 7 is out of range");
+        assert_eq!(error("Before: [0, 0, 0, 0]\n0 1 0 0\nAfter:  [0, 0, 0, 0]\n\n\n1 0 0 4"),
+                   "Failed to parse line 6, `1 0 0 4`: register 4 is out of range");
+        assert!(parse_input("0 -1 0 0").is_err());
+    }
+
+    // The operations which behave like the single sample in [sample]
+    fn behaves_like(sample: &str) -> Vec<Op> {
+        let (test_cases, _) = parse_input(sample).unwrap();
+        run_test_case(&test_cases[0]).iter().map(|id| Op::ALL[id]).collect()
+    }
+
+    // Values too big for an i32 are fine, and adding and multiplying wrap
+    // around rather than overflowing
+    #[test]
+    fn big_values() {
+        let sample = "Before: [2000000000, 2000000000, 0, 0]\n0 0 1 2\n\
+                      After:  [2000000000, 2000000000, 4000000000, 0]";
+        assert_eq!(behaves_like(sample), vec![Op::Addr]);
+        let sample = format!("Before: [{0}, {0}, 0, 0]\n0 0 1 2\nAfter:  [{0}, {0}, 1, 0]",
+                             i64::MAX);
+        assert!(behaves_like(&sample).contains(&Op::Mulr));
+    }
+}
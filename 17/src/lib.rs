@@ -0,0 +1,335 @@
+use std::ops::Range;
+use std::collections::{BTreeMap, BTreeSet};
+
+use common::AocError;
+use common::grid::SparseGrid;
+use common::parse;
+use common::point::Point;
+use common::ranges::RangeSet;
+use common::simulation::{run_until_stable, Simulation, StepResult};
+
+type PossibleRange = (i32, Option<i32>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockType {
+    Clay,
+    Sand,
+    WettedSand,
+    Water,
+    Spring,
+}
+use self::BlockType::*;
+
+#[derive(Debug, Default)]
+struct GroundScan {
+    // Anything which isn't in the grid is sand
+    grid: SparseGrid<BlockType>,
+    // The range of y values with clay in, which is the part of the scan that
+    // counts
+    y_min: i32,
+    y_max: i32,
+    // The points water is falling from which haven't been followed yet
+    falling: BTreeSet<(i32, i32)>,
+    // Where water got to last, which is where to look when watching
+    latest: Option<Point>,
+}
+
+impl GroundScan {
+    fn add_clay(&mut self, x_range: Range<i64>, y: i32) {
+        for x in x_range {
+            self.set(x as i32, y, Clay);
+        }
+    }
+
+    fn get(&self, x: i32, y: i32) -> BlockType {
+        self.grid.get(Point::new(x, y)).copied().unwrap_or(Sand)
+    }
+
+    fn set(&mut self, x: i32, y: i32, value: BlockType) {
+        if value == WettedSand || value == Water {
+            self.latest = Some(Point::new(x, y));
+        }
+        self.grid.insert(Point::new(x, y), value);
+    }
+}
+
+impl std::fmt::Display for GroundScan {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.grid.display_with(|b| match b.unwrap_or(&Sand) {
+                                                Clay => '#',
+                                                Sand => '.',
+                                                WettedSand => '|',
+                                                Water => '~',
+                                                Spring => '+',
+                                            }))
+    }
+}
+
+// Each step follows the water from one of the points it falls from, until it
+// either spreads out over something or all the water is falling off the
+// bottom of the scan
+impl Simulation for GroundScan {
+    fn step(&mut self) -> Result<StepResult, AocError> {
+        // Copy so we don't keep an immutable reference to self.falling
+        let key = match self.falling.iter().next() {
+            Some(&key) => key,
+            None => return Ok(StepResult::Finished),
+        };
+        let (x_source, y_source) = key;
+
+        // Find a block below and to start spreading out from
+        let mut j = 1;
+        loop {
+            if y_source + j > self.y_max {
+                // We've hit the bottom of the map so stop resolving this source
+                self.falling.remove(&key);
+                return Ok(StepResult::Changed);
+            }
+
+            let block_below = self.get(x_source, y_source + j);
+            match block_below {
+                Sand => self.set(x_source, y_source + j, WettedSand),
+                Clay | Water => {
+                    // Go back up a level since we've hit something tha water
+                    // will sit on
+                    j -= 1;
+                    break;
+                },
+                // Water has already flowed here, so stop resolving this source
+                WettedSand => {
+                    self.falling.remove(&key);
+                    return Ok(StepResult::Changed);
+                },
+                _ => unreachable!(),
+            }
+            j += 1;
+        }
+
+        // Start spreading out now that we're done falling down
+        loop {
+            self.set(x_source, y_source + j, Water);
+
+            // Consider spreading sideways
+            // First spread right
+            let mut new_falling_point = false;
+            let mut right_edge_contained = false;
+            let mut i = 1;
+            loop {
+                let block_right = self.get(x_source + i, y_source + j);
+                match block_right {
+                    Sand | WettedSand => {
+                        let block_below = self.get(x_source + i, y_source + j + 1);
+                        match block_below {
+                            Sand => {
+                                self.set(x_source + i, y_source + j, WettedSand);
+                                self.falling.insert((x_source + i, y_source + j));
+                                new_falling_point = true;
+                                break;
+                            },
+                            Clay | Water => self.set(x_source + i, y_source + j, Water),
+                            WettedSand => break,
+                            _ => unreachable!(),
+                        }
+                    },
+                    Clay => {
+                        right_edge_contained = true;
+                        break;
+                    },
+                    Water => break,
+                    _ => unreachable!(),
+                }
+                i += 1;
+            }
+            let i_max = i;
+
+            // Then spread left
+            let mut left_edge_contained = false;
+            i = -1;
+            loop {
+                let block_left = self.get(x_source + i, y_source + j);
+                match block_left {
+                    Sand | WettedSand => {
+                        let block_below = self.get(x_source + i, y_source + j + 1);
+                        match block_below {
+                            Sand => {
+                                self.set(x_source + i, y_source + j, WettedSand);
+                                self.falling.insert((x_source + i, y_source + j));
+                                new_falling_point = true;
+                                break;
+                            },
+                            Clay | Water => self.set(x_source + i, y_source + j, Water),
+                            WettedSand => break,
+                            _ => unreachable!(),
+                        }
+                    },
+                    Clay => {
+                        left_edge_contained = true;
+                        break;
+                    },
+                    Water => break,
+                    _ => unreachable!(),
+                }
+                i -= 1;
+            }
+            let i_min = i;
+
+            // If we've found a new falling point then blocks on this level are
+            // where water flowed, not sat. It also means we've finished
+            // resolving this falling point so exit the loop
+            if new_falling_point {
+                // Account for fact we have searched one block either side to
+                // find clay walls or falling points
+                for k in (i_min + 1)..i_max {
+                    self.set(x_source + k, y_source + j, WettedSand);
+                }
+
+                self.falling.remove(&key);
+                return Ok(StepResult::Changed);
+            }
+
+            // If both edges are contained by clay walls then start filling up
+            // by spreading left and right again at a lower y value
+            if right_edge_contained && left_edge_contained {
+                j -= 1;
+            }
+            else {
+                break;
+            }
+        }
+
+        Ok(StepResult::Changed)
+    }
+
+    fn render(&self) -> String {
+        self.to_string()
+    }
+
+    // The scan is far taller than a screen, so the viewer follows the water
+    fn focus(&self) -> Option<(usize, usize)> {
+        let (latest, min) = (self.latest?, self.grid.min()?);
+        Some(((latest.x - min.x) as usize, (latest.y - min.y) as usize))
+    }
+}
+
+// Either a single value like "x=495" or a range like "y=2..7"
+fn parse_possible_range(s: &str) -> Result<PossibleRange, String> {
+    match s.contains("..") {
+        true => {
+            let [start, end] = parse::fixed_ints(s)?;
+            Ok((start, Some(end)))
+        },
+        false => {
+            let [value] = parse::fixed_ints(s)?;
+            Ok((value, None))
+        }
+    }
+}
+
+fn parse_x_and_y(line: &str) -> Result<(PossibleRange, PossibleRange), String> {
+    let [first, second] = parse::fields(line, ", ")?;
+
+    // Determine if x or y is given first
+    let (x, y) = match first.starts_with('x') {
+        true => (first, second),
+        false => (second, first),
+    };
+
+    Ok((parse_possible_range(x)?, parse_possible_range(y)?))
+}
+
+// The scan before any water has flowed, with just the clay and the spring
+fn parse_scan(input: &str) -> Result<GroundScan, AocError> {
+    let mut clay = vec![];
+    for (i, line) in input.lines().enumerate() {
+        clay.push(parse_x_and_y(line).map_err(|e| AocError::parse(i + 1, line, e))?);
+    }
+
+    // The clay in each row, with any veins which cross or touch merged
+    let mut rows: BTreeMap<i32, RangeSet> = BTreeMap::new();
+    for ((x1, x2), (y1, y2)) in clay {
+        let x_range = i64::from(x1)..i64::from(x2.unwrap_or(x1)) + 1;
+        for y in y1..=y2.unwrap_or(y1) {
+            rows.entry(y).or_default().insert(x_range.clone());
+        }
+    }
+
+    let mut scan = GroundScan::default();
+    for (y, row) in rows {
+        for x_range in row.ranges() {
+            scan.add_clay(x_range, y);
+        }
+    }
+
+    // So far the grid only covers the clay
+    match (scan.grid.min(), scan.grid.max()) {
+        (Some(min), Some(max)) => {
+            scan.y_min = min.y;
+            scan.y_max = max.y;
+        },
+        _ => return Err(AocError::parse_input("there's no clay in the scan")),
+    }
+
+    // Add the spring, where the water starts falling from
+    scan.set(500, 0, Spring);
+    scan.falling.insert((500, 0));
+
+    Ok(scan)
+}
+
+// The water flowing down from the spring, for watching
+pub fn simulation(input: &str) -> Result<Box<dyn Simulation>, AocError> {
+    Ok(Box::new(parse_scan(input)?))
+}
+
+// Let the water flow until the scan stops changing
+fn simulate_water(input: &str) -> Result<GroundScan, AocError> {
+    let mut scan = parse_scan(input)?;
+    run_until_stable(&mut scan)?;
+    Ok(scan)
+}
+
+// Count the tiles which satisfy [is_counted]. Anything above the highest clay,
+// like the spring and the water falling from it, doesn't count
+fn count_tiles(scan: &GroundScan, is_counted: impl Fn(&BlockType) -> bool) -> usize {
+    scan.grid.iter().filter(|&(p, b)| p.y >= scan.y_min && is_counted(b)).count()
+}
+
+pub fn part1(input: &str) -> Result<String, AocError> {
+    let scan = simulate_water(input)?;
+    let count = count_tiles(&scan, |&b| b == Water || b == WettedSand);
+    Ok(count.to_string())
+}
+
+pub fn part2(input: &str) -> Result<String, AocError> {
+    let scan = simulate_water(input)?;
+    let count = count_tiles(&scan, |&b| b == Water);
+    Ok(count.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::fixtures::DAY17 as EXAMPLE;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), "57");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), "29");
+    }
+
+    // Veins which cross, or cover the same clay twice, give the same scan as
+    // veins which don't
+    #[test]
+    fn overlapping_veins() {
+        let overlapping = "x=495, y=2..7\ny=7, x=495..501\nx=501, y=3..7\ny=7, x=498..499";
+        let separate = "x=495, y=2..6\ny=7, x=495..501\nx=501, y=3..6";
+        assert_eq!(parse_scan(overlapping).unwrap().to_string(),
+                   parse_scan(separate).unwrap().to_string());
+        assert_eq!(part1(overlapping).unwrap(), part1(separate).unwrap());
+    }
+}
@@ -0,0 +1,289 @@
+
+use log::debug;
+
+use common::AocError;
+use common::automaton::{self, Automaton};
+use common::cycle;
+use common::grid::{FixedGrid, Grid};
+use common::hash::HashMap;
+use common::point::Point;
+use common::simulation::{Simulation, StepResult};
+use common::viz::sparkline::sparkline;
+use common::viz::svg::{Style, Svg};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum TileType {
+    Open,
+    Wooded,
+    LumberYard,
+}
+use self::TileType::*;
+
+impl TileType {
+    fn from_char(c: char) -> Option<TileType> {
+        match c {
+            '.' => Some(Open),
+            '|' => Some(Wooded),
+            '#' => Some(LumberYard),
+            _ => None,
+        }
+    }
+}
+
+// How the area is drawn when it's rendered as a simulation
+impl From<TileType> for char {
+    fn from(tile: TileType) -> char {
+        match tile {
+            Open => '.',
+            Wooded => '|',
+            LumberYard => '#',
+        }
+    }
+}
+
+type UpdateRule = fn(&TileType, &[&TileType]) -> TileType;
+type Area = Automaton<TileType, UpdateRule>;
+
+// What [tile] becomes in a minute, given how many of the tiles around it are
+// wooded and how many are lumberyards
+fn next_tile(tile: TileType, wooded: usize, lumber_yards: usize) -> TileType {
+    match tile {
+        Open if wooded >= 3 => Wooded,
+        Wooded if lumber_yards >= 3 => LumberYard,
+        LumberYard if lumber_yards == 0 || wooded == 0 => Open,
+        _ => tile,
+    }
+}
+
+fn update_tile(tile: &TileType, adjacent: &[&TileType]) -> TileType {
+    let count = |tile_type| adjacent.iter().filter(|&&&t| t == tile_type).count();
+    next_tile(*tile, count(Wooded), count(LumberYard))
+}
+
+fn get_resource_value<'a>(tiles: impl IntoIterator<Item = &'a TileType>) -> usize {
+    let (mut wooded, mut lumber_yards) = (0, 0);
+    for tile in tiles {
+        match tile {
+            Wooded => wooded += 1,
+            LumberYard => lumber_yards += 1,
+            Open => {},
+        }
+    }
+    wooded * lumber_yards
+}
+
+// Beyond the edges is treated as open ground, which doesn't affect any of the
+// tiles next to it
+fn parse_area(input: &str) -> Result<Area, AocError> {
+    let grid = Grid::parse(input, TileType::from_char)?;
+    Ok(Automaton::new(grid, automaton::MOORE, Open, update_tile))
+}
+
+// Every real input is this size, so the answers are worked out on a grid of
+// exactly that size, which is several times quicker than the automaton.
+// Anything else, like the example, uses the automaton
+const SIZE: usize = 50;
+type FixedArea = FixedGrid<TileType, SIZE, SIZE>;
+
+// The tiles are counted over the square of up to nine tiles around each one,
+// which the compiler can check is in the grid up front, and then the tile
+// itself is taken back out
+fn step_fixed(area: &FixedArea) -> FixedArea {
+    FixedGrid::from_fn(|x, y| {
+        let (mut wooded, mut lumber_yards) = (0, 0);
+        for ny in y.saturating_sub(1)..(y + 2).min(SIZE) {
+            for nx in x.saturating_sub(1)..(x + 2).min(SIZE) {
+                match area[(nx, ny)] {
+                    Wooded => wooded += 1,
+                    LumberYard => lumber_yards += 1,
+                    Open => {},
+                }
+            }
+        }
+        let tile = area[(x, y)];
+        match tile {
+            Wooded => wooded -= 1,
+            LumberYard => lumber_yards -= 1,
+            Open => {},
+        }
+        next_tile(tile, wooded, lumber_yards)
+    })
+}
+
+// The same as running the automaton to [goal], but on a fixed size grid
+fn resource_value_after_fixed(mut area: FixedArea, goal: usize)
+        -> (usize, Option<(usize, usize)>) {
+    let mut values = vec![];
+    let states = std::iter::from_fn(|| {
+        let next = step_fixed(&area);
+        Some(std::mem::replace(&mut area, next))
+    });
+    let cycle = cycle::find_cycle(states.take(goal + 1).inspect(|area| {
+        values.push(get_resource_value(area.iter()))
+    }));
+    match cycle {
+        Some(cycle) => (values[cycle::index_in_cycle(goal, cycle)], Some(cycle)),
+        None => (values[goal], None),
+    }
+}
+
+// The area changing minute by minute, for watching, with the resource value
+// over the last few minutes drawn as a sparkline above it. It soon settles
+// into a cycle, which shows up as the sparkline repeating, and is pointed out
+// once a minute looks the same as an earlier one
+struct TimeLapse {
+    area: Area,
+    // The resource value after each minute so far
+    values: Vec<usize>,
+    // The minute each state of the area was first seen, until the cycle's
+    // been found
+    seen: HashMap<Grid<TileType>, usize>,
+    // The first minute of the cycle and how long it is
+    cycle: Option<(usize, usize)>,
+}
+
+// How many minutes the sparkline covers
+const SPARKLINE_MINUTES: usize = 50;
+
+impl Simulation for TimeLapse {
+    fn step(&mut self) -> Result<StepResult, AocError> {
+        let result = Simulation::step(&mut self.area)?;
+        let minute = self.values.len();
+        self.values.push(get_resource_value(self.area.cells().iter()));
+        if self.cycle.is_none() {
+            if let Some(start) = self.seen.insert(self.area.cells().clone(), minute) {
+                self.cycle = Some((start, minute - start));
+                self.seen.clear();
+            }
+        }
+        Ok(result)
+    }
+
+    fn render(&self) -> String {
+        let minute = self.values.len() - 1;
+        let mut s = format!("Minute {}, resource value {}\n", minute, self.values[minute]);
+        if let Some((start, length)) = self.cycle {
+            s += &format!("Repeating every {} minutes from minute {}\n", length, start);
+        }
+        s += &sparkline(&self.values[self.values.len().saturating_sub(SPARKLINE_MINUTES)..]);
+        s.push('\n');
+        s += &self.area.render();
+        s
+    }
+}
+
+pub fn simulation(input: &str) -> Result<Box<dyn Simulation>, AocError> {
+    let area = parse_area(input)?;
+    let values = vec![get_resource_value(area.cells().iter())];
+    Ok(Box::new(TimeLapse { area, values, seen: HashMap::default(), cycle: None }))
+}
+
+// How tall the plot is, in the same units as the minutes across it
+const PLOT_HEIGHT: i32 = 200;
+
+// A plot of the resource value each minute, until the cycle the area settles
+// into has gone round twice, with the first time round shaded
+pub fn draw(input: &str) -> Result<Svg, AocError> {
+    let mut area = parse_area(input)?;
+    let mut values = vec![];
+    let states = std::iter::from_fn(|| {
+        let cells = area.cells().clone();
+        area.step();
+        Some(cells)
+    });
+    let (start, length) = cycle::find_cycle(states.inspect(|cells| {
+        values.push(get_resource_value(cells.iter()))
+    })).ok_or_else(|| AocError::NoSolution("the area never repeats".to_string()))?;
+    for _ in 0..length {
+        area.step();
+        values.push(get_resource_value(area.cells().iter()));
+    }
+
+    let max = values.iter().copied().max().unwrap_or(0).max(1);
+    let height = |value: usize| PLOT_HEIGHT - (value * PLOT_HEIGHT as usize / max) as i32;
+    let mut svg = Svg::new().scale(2.0);
+    svg.rect(start as f64 + 0.5, 0.0, length as f64, f64::from(PLOT_HEIGHT) + 1.0,
+             &Style::fill("gold").opacity(0.3));
+    svg.path(vec![Point::new(0, PLOT_HEIGHT), Point::new(values.len() as i32 - 1, PLOT_HEIGHT)],
+             &Style::stroke("grey", 1.0));
+    svg.path(values.iter().enumerate().map(|(minute, &v)| Point::new(minute as i32, height(v))),
+             &Style::stroke("forestgreen", 1.0));
+    Ok(svg)
+}
+
+// The same as resource_value_after_fixed but for any size of area
+fn resource_value_after_automaton(grid: Grid<TileType>, goal: usize)
+        -> (usize, Option<(usize, usize)>) {
+    let mut area = Automaton::new(grid, automaton::MOORE, Open, update_tile);
+    let cycle = area.run_to(goal);
+    (get_resource_value(area.cells().iter()), cycle)
+}
+
+// The resource value after [goal_iterations] minutes, and the cycle the area
+// got stuck in on the way, if it did, as the minute it starts and its length
+pub fn resource_value_after(input: &str, goal_iterations: usize)
+        -> Result<(usize, Option<(usize, usize)>), AocError> {
+    // The area soon gets stuck in a cycle, which lets the automaton skip ahead
+    // to the goal
+    let grid = Grid::parse(input, TileType::from_char)?;
+    let (value, cycle) = match FixedArea::from_grid(&grid) {
+        Some(area) => resource_value_after_fixed(area, goal_iterations),
+        None => resource_value_after_automaton(grid, goal_iterations),
+    };
+    if let Some((start, length)) = cycle {
+        debug!("The area repeats every {} minutes from minute {}, so minute {} looks like \
+                minute {}", length, start, goal_iterations,
+               cycle::index_in_cycle(goal_iterations, (start, length)));
+    }
+    Ok((value, cycle))
+}
+
+pub fn part1(input: &str) -> Result<String, AocError> {
+    Ok(resource_value_after(input, 10)?.0.to_string())
+}
+
+pub fn part2(input: &str) -> Result<String, AocError> {
+    Ok(resource_value_after(input, 1000000000)?.0.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The example from the puzzle description. There's no answer given for
+    // part 2
+    use common::fixtures::DAY18 as EXAMPLE;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), "1147");
+    }
+
+    // An area the size of the real inputs, with each tile picked at random
+    // but the same every run
+    fn random_area() -> String {
+        let mut state: u64 = 18;
+        let mut area = String::new();
+        for _ in 0..SIZE {
+            for _ in 0..SIZE {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                area.push(['.', '.', '|', '#'][(state >> 33) as usize % 4]);
+            }
+            area.push('\n');
+        }
+        area
+    }
+
+    // Areas the size of the real inputs are worked out on a fixed size grid,
+    // which has to follow the same rules as the automaton
+    #[test]
+    fn fixed_size_areas_match_the_automaton() {
+        let grid = Grid::parse(&random_area(), TileType::from_char).unwrap();
+        let area = FixedArea::from_grid(&grid).unwrap();
+        for &minutes in &[0, 1, 2, 10, 100, 1000, 1_000_000_000] {
+            let (fixed, _) = resource_value_after_fixed(area.clone(), minutes);
+            let (automaton, _) = resource_value_after_automaton(grid.clone(), minutes);
+            assert_eq!(fixed, automaton, "after {} minutes", minutes);
+        }
+    }
+}
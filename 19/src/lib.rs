@@ -0,0 +1,298 @@
+use std::fmt;
+
+use log::{debug, log_enabled, trace, Level};
+
+use common::AocError;
+use common::elfcode;
+use common::viz::svg::Svg;
+
+// The answer is the value left in [0] when the program halts
+pub fn part1(input: &str) -> Result<String, AocError> {
+    let program = elfcode::parse_program(input)?;
+    let mut registers = [0; 6];
+    // Tracing is only worth slowing every step down for when it'll be seen
+    if log_enabled!(Level::Trace) {
+        while let Some(step) = program.step_traced(&mut registers) {
+            trace!("{}", step);
+        }
+    }
+    else {
+        program.run(&mut registers);
+    }
+    Ok(registers[0].to_string())
+}
+
+// How much of the part 1 run to keep in order, which is enough to see the
+// program set up and then go round its loops a few times
+const TRACE_LENGTH: usize = 400;
+
+// How many times each line of the program runs for part 1, with the start of
+// the run alongside. The two loops inside each other which part 2 works
+// through below are the lines which run the most
+pub fn draw(input: &str) -> Result<Svg, AocError> {
+    let program = elfcode::parse_program(input)?;
+    let profile = program.profile(&mut [0; 6], u64::MAX, TRACE_LENGTH);
+    Ok(elfcode::draw_profile(&program, &profile))
+}
+
+// How many steps the largest register has to go without changing before the
+// program is taken to have finished setting up, and the most steps setting up
+// can take. That only picks out the number being set up when it's big. The
+// inner loop counts register 5 up past the number, to the number + 1, and
+// with a small number that happens again well within STABLE_STEPS, so the
+// largest register never settles. The numbers the puzzle inputs set up, about
+// a thousand for part 1 and ten million for part 2, are plenty big enough
+const STABLE_STEPS: u64 = 1000;
+const MAX_SETUP_STEPS: u64 = 1_000_000;
+
+// What the program works out, as found by analyse
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Derivation {
+    // The number the program sets up before going into its loops
+    pub target: i64,
+    pub divisors: Vec<i64>,
+}
+
+impl Derivation {
+    // What the program leaves in [0]
+    pub fn sum(&self) -> i64 {
+        self.divisors.iter().sum()
+    }
+}
+
+impl fmt::Display for Derivation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let divisors: Vec<_> = self.divisors.iter().map(|d| d.to_string()).collect();
+        write!(f, "The program sums the divisors of {}, which are {}, giving {}",
+               self.target, divisors.join(" + "), self.sum())
+    }
+}
+
+// Work out what the program leaves in [0] when starting with [0] set to
+// [register0] without running it to the end. As worked through below, it
+// spends a few steps setting up a big number and then the rest of its time
+// summing that number's divisors in two loops, one inside the other. So it's
+// run until the largest register stops changing, which is the big number,
+// and the divisors are found here instead
+pub fn analyse(input: &str, register0: i64) -> Result<Derivation, AocError> {
+    let program = elfcode::parse_program(input)?;
+    let mut registers = [register0, 0, 0, 0, 0, 0];
+    let mut target = 0;
+    let mut stable = 0;
+    for _ in 0..MAX_SETUP_STEPS {
+        if program.step(&mut registers).is_none() {
+            break;
+        }
+        let largest = registers.iter().copied().max().unwrap_or(0);
+        if largest == target {
+            stable += 1;
+            if stable == STABLE_STEPS {
+                return Ok(Derivation { target, divisors: divisors(target) });
+            }
+        }
+        else {
+            target = largest;
+            stable = 0;
+        }
+    }
+    Err(AocError::NoSolution("the program never settles on a number to sum the divisors of"
+                             .to_string()))
+}
+
+// The divisors of [n], smallest first
+fn divisors(n: i64) -> Vec<i64> {
+    let mut small = vec![];
+    let mut large = vec![];
+    let mut d = 1;
+    while d * d <= n {
+        if n % d == 0 {
+            small.push(d);
+            if d * d != n {
+                large.push(n / d);
+            }
+        }
+        d += 1;
+    }
+    small.extend(large.into_iter().rev());
+    small
+}
+
+pub fn part2(input: &str) -> Result<String, AocError> {
+    let derivation = analyse(input, 1)?;
+    debug!("{}", derivation);
+    Ok(derivation.sum().to_string())
+}
+
+// Working out by hand what the program does, which analyse relies on:
+//
+// The program loops, seemingly endlessly for Part 2. Try working through
+// the commands of the progam to see if we can work out when it will stop:
+
+// Running through the program from the start:
+//
+// IP is [1]
+// Add 16 to [1]
+// IP is now 17 so add 2 to [2]
+// IP is now 18 so multiply [2] by 2
+// IP is now 19 so multiply [2] by [1]
+// IP is now 20 so multiply [2] by 11
+// IP is now 21 so add 7 to [4]
+// IP is now 22 so multiply [4] by [1]
+// IP is now 23 so add 13 to [4]
+// IP is now 24 so add [4] to [2]
+//
+// Assuming [1, 0, 0, 0, 0, 0] start we now have [1, 25, 1003, 0, 167 , 0]
+//
+// IP is now 25 so add [0] to [1]
+// IP is now 27 so set [1] to [4]
+// IP is now 28 so multiply [4] by [1]
+// IP is now 29 so add [1] to [4]
+// IP is now 30 so multiply [4] by [1]
+// IP is now 31 so multiply [4] by 14
+// IP is now 32 so multiply [4] by [1]
+// IP is now 33 so add [4] to [2]
+// IP is now 34 so set [0] to 0
+// IP is now 35 so set [1] to 0
+//
+// We now have [0, 1, 10551403, 0, 10550400, 0]
+//
+// IP is now 1 so set [3] to 1
+// IP is now 2 so set [5] to 1
+//
+// >> LOOP <<
+// IP is now 3 so set [4] to [3]*[5]
+// IP is now 4 so set [4] to [4]==[2]
+//
+// We now have [0, 5, 10551403, 1, 0, 1]
+//
+// IP is now 5 so add [4] to [1]
+// IP is now 6 so add 1 to [1]
+// IP is now 8 so add 1 to [5]
+// IP is now 9 so set [4] to [5]>[2]
+// IP is now 10 so add [4] to [1]
+//
+// We now have [0, 11, 10551403, 1, 0, 2]
+//
+// IP is now 11 so set [1] to 2
+// >> LOOP <<
+// IP is now 3...
+
+// Loop found, consider its effects:
+    // set [4] to ([3]*[5])==[2] (not true for the moment)
+    // add [4] to [1] (it's zero since the test was false so no jumps)
+    // add 1 to [5]
+    // set [4] to [5]>[2] (not true for the moment)
+    // add [4] to [1] (it's zero since the test was false so no jumps)
+    // loop back to the top
+
+// The loop will only end if either of the testing operations succeeds.
+// The only change every iteration is [5]++.
+// At the moment [3] is 1 so ([3]*[5])==[2] will be satisfied first.
+
+// Lets go through that iteration of the loop:
+// We initially have [0, 3, 10551403, 1, 0, 10551403]
+//
+// IP is now 3 so set [4] to [3]*[5]
+// IP is now 4 so set [4] to [4]==[2] (true so [4] is 1)
+// IP is now 5 so add [4] to [1]
+// IP is now 7 so add [3] to [0]
+// IP is now 8 so add 1 to [5]
+// IP is now 9 so set [4] to [5]>[2] (true so [4] is 1)
+// IP is now 10 so add [4] to [1]
+// IP is now 12 so add 1 to [3]
+// IP is now 13 so set [4] to [3]>[2] (false so [4] is 0)
+// IP is now 14 so add [4] to [1]
+// IP is now 15 so set [1] to 1
+// IP is now 2 so set [5] to [1]
+// IP is now 3...
+//
+// The net result is [3]++, [0]=1 and [5]=1 so we're back to the original
+// loop but with [3]++ and [0]=1
+
+// The first test had the effect of adding [3] to [0] when true, but didn't
+// do anything else.
+//
+// The second test had the effect of [3]++, opening up a new test and
+// setting [5]=1. The new test is the only option to escape the loop (it's
+// also the final testing command in the program so better end the loop...)
+
+// This final test will be true when [3] is 10551404. While [3] is slowly
+// incrementing via the second test command the value of [0] (what we
+// actually care about) will be modified whenever the first test is true.
+
+// The first test will only be satisifed on 4 occasions, one of which we've
+// already dealt with above, since the only factors of 10551403 are 19 and
+// 555337. We will end up with the sum of all the factors of 10551403 in
+// [0]. This is 1 + 10551403 + 19 + 555337 = 11106760
+
+// At this point we enter the final iteration of the loop:
+// We initially have [11106760, 3, 10551403, 10551403, 0, 10551403]
+//
+// IP is now 3 so set [4] to [3]*[5]
+// IP is now 4 so set [4] to [4]==[2] (false so [4] is 0)
+// IP is now 5 so add [4] to [1]
+// IP is now 6 so add 1 to [1]
+// IP is now 8 so add 1 to [5]
+// IP is now 9 so set [4] to [5]>[2] (true so [4] is 1)
+// IP is now 10 so add [4] to [1]
+// IP is now 12 so add 1 to [3]
+// IP is now 13 so set [4] to [3]>[2] (true so [4] is 0)
+// IP is now 14 so add [4] to [1]
+// IP is now 16 so set multiply [1] by [1]
+// IP is now 257 -> out of range so program finishes!
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The example from the puzzle description. Part 2 relies on working out
+    // what the real program does, so only part 1 can run it
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(common::fixtures::DAY19).unwrap(), "7");
+    }
+
+    // A program shaped like the real one, as worked through above, which
+    // sums the divisors of 936 when [0] starts as 0 and of 2310 when it
+    // starts as 1. They're small enough to run to the end, to check that
+    // analyse finds the right number and gets the same answer as running it
+    const DIVISOR_SUM: &str = "\
+#ip 1
+addi 1 16 1
+seti 1 0 3
+seti 1 0 5
+mulr 3 5 4
+eqrr 4 2 4
+addr 4 1 1
+addi 1 1 1
+addr 3 0 0
+addi 5 1 5
+gtrr 5 2 4
+addr 1 4 1
+seti 2 0 1
+addi 3 1 3
+gtrr 3 2 4
+addr 4 1 1
+seti 1 0 1
+mulr 1 1 1
+seti 936 0 2
+addr 1 0 1
+seti 0 0 1
+seti 2310 0 2
+seti 0 0 0
+seti 0 0 1";
+
+    #[test]
+    #[cfg_attr(not(feature = "slow-tests"), ignore = "slow, run with --features slow-tests")]
+    fn analysis_matches_running_the_program() {
+        let program = elfcode::parse_program(DIVISOR_SUM).unwrap();
+        for &(register0, target) in &[(0, 936), (1, 2310)] {
+            let derivation = analyse(DIVISOR_SUM, register0).unwrap();
+            assert_eq!(derivation.target, target);
+            let mut registers = [register0, 0, 0, 0, 0, 0];
+            program.run(&mut registers);
+            assert_eq!(derivation.sum(), registers[0], "{}", derivation);
+        }
+        assert_eq!(part2(DIVISOR_SUM).unwrap(), "6912");
+    }
+}
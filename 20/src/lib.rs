@@ -0,0 +1,235 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use log::debug;
+
+use common::AocError;
+use common::direction::Direction;
+use common::grid::{Grid, SparseGrid};
+use common::hash::HashMap;
+use common::point::Point;
+use common::search::{self, Graph};
+use common::viz::dot::Dot;
+use common::viz::svg::{Style, Svg};
+
+type Pos = Point;
+
+// A set of branches in the regex which is being walked
+#[derive(Debug)]
+struct Branches {
+    // The rooms the branches start from
+    starts: HashSet<Pos>,
+    // The rooms the branches finished so far end up in
+    ends: HashSet<Pos>,
+}
+
+#[derive(Debug, Default)]
+struct RoomMap {
+    // For each room, the neighbouring rooms we know there's a door to
+    doors: SparseGrid<HashSet<Pos>>,
+}
+
+impl RoomMap {
+    // Walk every route described by the regex, recording each door we pass
+    // through. Rather than following each route separately, which takes
+    // exponentially long with nested branches, we keep the set of rooms the
+    // routes so far could have reached, and a stack of where each open set
+    // of branches started and where its finished branches ended up. Rooms
+    // reached more than one way are only kept once, so every character is
+    // only processed once for each room it could start from
+    fn from_regex(regex: &str) -> Result<RoomMap, AocError> {
+        let mut map = RoomMap::default();
+        let mut stack: Vec<Branches> = vec![];
+        let mut rooms: HashSet<Pos> = IntoIterator::into_iter([Point::ORIGIN]).collect();
+        map.doors.insert(Point::ORIGIN, HashSet::new());
+
+        let error = |i: usize, message: &str| {
+            AocError::parse_input(format!("{} at character {} of the regex", message, i + 1))
+        };
+        for (i, c) in regex.chars().enumerate() {
+            if let Some(direction) = Direction::from_char(c) {
+                rooms = rooms.into_iter().map(|pos| {
+                    let next_pos = pos + direction.offset();
+                    map.add_door(pos, next_pos);
+                    next_pos
+                }).collect();
+                continue;
+            }
+            match c {
+                // Start a new set of branches from the current rooms
+                '(' => stack.push(Branches { starts: rooms.clone(), ends: HashSet::new() }),
+                // Start the next branch from where this set of branches began
+                '|' => {
+                    let branches = stack.last_mut().ok_or_else(|| error(i, "unmatched |"))?;
+                    branches.ends.extend(rooms);
+                    rooms = branches.starts.clone();
+                },
+                // This set of branches is finished. Carry on from wherever
+                // any of them ended up
+                ')' => {
+                    let branches = stack.pop().ok_or_else(|| error(i, "unmatched )"))?;
+                    rooms.extend(branches.ends);
+                },
+                '^' | '$' => {},
+                _ => return Err(error(i, &format!("'{}' isn't a direction or bracket", c))),
+            }
+        }
+        if !stack.is_empty() {
+            return Err(AocError::parse_input("there's an unmatched ( in the regex"));
+        }
+
+        Ok(map)
+    }
+
+    fn add_door(&mut self, a: Pos, b: Pos) {
+        self.doors.get_or_insert_with(a, HashSet::new).insert(b);
+        self.doors.get_or_insert_with(b, HashSet::new).insert(a);
+    }
+
+    // Breadth first search from the starting room, giving the fewest doors
+    // that must be passed through to reach every room
+    fn distances(&self) -> HashMap<Pos, usize> {
+        search::bfs_distances(self, &Point::ORIGIN)
+    }
+
+    // The map drawn the way the puzzle draws it, with a # for each wall, a .
+    // for each room, a | or - for each door and an X for the room where you
+    // start. Rooms are two characters apart, with the wall or door between
+    fn picture(&self) -> Grid<char> {
+        let (min, max) = match (self.doors.min(), self.doors.max()) {
+            (Some(min), Some(max)) => (min, max),
+            _ => return Grid::from_fn(1, 1, |_, _| '#'),
+        };
+        let at = |p: Pos| ((2 * (p.x - min.x) + 1) as usize, (2 * (p.y - min.y) + 1) as usize);
+        let mut picture = Grid::from_fn((2 * (max.x - min.x) + 3) as usize,
+                                        (2 * (max.y - min.y) + 3) as usize, |_, _| '#');
+        for (pos, neighbours) in self.doors.iter() {
+            let (x, y) = at(pos);
+            picture[(x, y)] = if pos == Point::ORIGIN { 'X' } else { '.' };
+            for next_pos in neighbours {
+                let (nx, ny) = at(*next_pos);
+                picture[((x + nx) / 2, (y + ny) / 2)] = if ny == y { '|' } else { '-' };
+            }
+        }
+        picture
+    }
+}
+
+impl fmt::Display for RoomMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.picture())
+    }
+}
+
+fn parse_map(input: &str) -> Result<RoomMap, AocError> {
+    let map = RoomMap::from_regex(input.trim())?;
+    debug!("The map:\n{}", map);
+    Ok(map)
+}
+
+// Each door leads to a neighbouring room
+impl Graph for RoomMap {
+    type Node = Pos;
+
+    fn edges(&self, pos: &Pos) -> Vec<(Pos, usize)> {
+        self.doors[*pos].iter().map(|&next_pos| (next_pos, 1)).collect()
+    }
+}
+
+// The rooms with a line for each door, laid out as they are in the facility.
+// The layout only works with Graphviz's neato, which it asks for. The room
+// where you start is red
+pub fn graph(input: &str) -> Result<Dot, AocError> {
+    let map = RoomMap::from_regex(input.trim())?;
+    let mut dot = Dot::undirected().graph_attribute("layout", "neato")
+        .graph_attribute("inputscale", "4")
+        .node_attribute("shape", "point");
+
+    // In reading order, so the same input always gives the same graph
+    let mut rooms: Vec<_> = map.doors.iter().collect();
+    rooms.sort_by_key(|(pos, _)| (pos.y, pos.x));
+
+    let id = |p: Pos| format!("{},{}", p.x, p.y);
+    for &(pos, _) in &rooms {
+        // Graphviz's y axis points up
+        let position = format!("{},{}!", pos.x, -pos.y);
+        let colour = if pos == Point::ORIGIN { "red" } else { "black" };
+        dot.node(&id(pos), &[("pos", &position), ("color", colour)]);
+    }
+    for &(pos, neighbours) in &rooms {
+        // Each door is only drawn once, from the room to the left of it or
+        // above it
+        let mut doors: Vec<_> = neighbours.iter().filter(|n| (n.y, n.x) > (pos.y, pos.x)).collect();
+        doors.sort_by_key(|n| (n.y, n.x));
+        for &next_pos in doors {
+            dot.edge(&id(pos), &id(next_pos), &[]);
+        }
+    }
+    Ok(dot)
+}
+
+// The map, with each room coloured by how many doors away it is, from green
+// for the nearest to red for the furthest, and walls in black
+pub fn draw(input: &str) -> Result<Svg, AocError> {
+    let map = RoomMap::from_regex(input.trim())?;
+    let distances = map.distances();
+    let furthest = distances.values().copied().max().unwrap_or(0).max(1);
+    let min = map.doors.min().unwrap_or(Point::ORIGIN);
+    let colour = |pos: &Pos| {
+        let hue = 120 - 120 * distances.get(pos).copied().unwrap_or(0) / furthest;
+        Style::fill(&format!("hsl({}, 80%, 50%)", hue))
+    };
+
+    let mut svg = Svg::new().scale(4.0).background("black");
+    let at = |p: Pos| Point::new(2 * (p.x - min.x) + 1, 2 * (p.y - min.y) + 1);
+    for (pos, neighbours) in map.doors.iter() {
+        svg.cell(at(pos), &colour(&pos));
+        // Each door is the colour of the room nearer the start
+        for next_pos in neighbours {
+            let door = Point::new((at(pos).x + at(*next_pos).x) / 2,
+                                  (at(pos).y + at(*next_pos).y) / 2);
+            let nearer = if distances.get(next_pos) < distances.get(&pos) { next_pos } else { &pos };
+            svg.cell(door, &colour(nearer));
+        }
+    }
+    // Cover the whole map, walls and all
+    let max = map.doors.max().unwrap_or(Point::ORIGIN);
+    svg.rect(0.0, 0.0, f64::from(2 * (max.x - min.x) + 3), f64::from(2 * (max.y - min.y) + 3),
+             &Style::stroke("white", 0.2));
+    svg.label(at(Point::ORIGIN), "X", &Style::fill("white"));
+    Ok(svg)
+}
+
+pub fn part1(input: &str) -> Result<String, AocError> {
+    let distances = parse_map(input)?.distances();
+    let longest = distances.values().max()
+        .ok_or_else(|| AocError::NoSolution("there are no rooms".to_string()))?;
+    Ok(longest.to_string())
+}
+
+pub fn part2(input: &str) -> Result<String, AocError> {
+    let distances = parse_map(input)?.distances();
+    let limit = 1000;
+    let far_rooms = distances.values().filter(|&&l| l >= limit).count();
+    Ok(far_rooms.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // There's no example for part 2
+    #[test]
+    fn part1_examples() {
+        let examples = [
+            ("^WNE$", "3"),
+            ("^ENWWW(NEEE|SSE(EE|N))$", "10"),
+            ("^ENNWSWW(NEWS|)SSSEEN(WNSE|)EE(SWEN|)NNN$", "18"),
+            ("^ESSWWN(E|NNENN(EESS(WNSE|)SSS|WWWSSSSE(SW|NNNE)))$", "23"),
+            ("^WSSEESWWWNW(S|NENNEEEENN(ESSSSW(NWSW|SSEN)|WSWWN(E|WWS(E|SS))))$", "31"),
+        ];
+        for &(regex, doors) in &examples {
+            assert_eq!(part1(regex).unwrap(), doors, "{}", regex);
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use common::AocError;
+use common::cycle;
+use common::elfcode;
+use common::viz::svg::Svg;
+
+// Work out the values of [0] which make the program halt after the fewest and
+// the most instructions. The program is hand-decompiled below so the puzzle
+// input isn't needed
+fn halting_values() -> (i64, i64) {
+    // *Part 1* logic (calculate solution later on with part 2)
+    //
+    // Only command 28 makes use of the [0], since we can only effect
+    // [0] the program up until the point where command 28 runs is out of our
+    // control so don't bother trying to understand it.
+    // State when we first run command 28: [x, 28, 1, 13270004, 1, 1]
+    //
+    // Carrying on:
+    // IP is now 28 so set [2] to [3]==[0] (false with x = 0)
+    // IP is now 29 so add [2] to [1]
+    // IP is now 30 so set [1] to 5
+    // IP is now 6...
+    //
+    // Now if [3]==[0] was true then the program would have had IP 31 after
+    // command 29 and finished. This is surely the quickest way to halt the
+    // program by changing only the inital value of [0].
+    //
+    // So the answer to part 1 us 13270004!
+
+
+
+    // *Part 2*
+    //
+    // We need to understand the program now, so running through it:
+    //
+    // IP is [1]
+    // IP is now 0 so set [3] to 123
+    // IP is now 1 so set [3] to [3]&456 (binary and)
+    // IP is now 2 so set [3] to [3]==72 (true for this code)
+    // IP is now 3 so add [3] to [1]
+    // IP is now 5 so set [3] to 0
+    //
+    // Everything so far is the check that inputs are being interpreted as
+    // numbers and not strings. Once complete, there's no resulting changes.
+    //
+    // IP is now 6 so set [5] to [3]|65536
+    // IP is now 7 so set [3] to 15028787
+    // IP is now 8 so set [2] to [5]&255
+    // IP is now 9 so add [2] to [3]
+    // IP is now 10 so set [3] to [3]&16777215
+    // IP is now 11 so multiply [3] by 65899
+    // IP is now 12 so set [3] to [3]&16777215
+    // IP is now 13 so set [2] to 256>[5] (false)
+    // IP is now 14 so add [2] to [1]
+    // IP is now 15 so add 1 to [1]
+    // IP is now 17 so set [2] to 0
+    // >> LOOP <<
+    // IP is now 18 so set [4] to [2]+1
+    // IP is now 19 so multiply [4] by 256
+    // IP is now 20 so set [4] to [4]>[5] (false for now)
+    // IP is now 21 so add [4] to [1]
+    // IP is now 22 so add 1 to [1]
+    // IP is now 24 so add 1 to [2]
+    // IP is now 25 so set [1] to 17
+    // >> LOOP <<
+    // IP is now 18 so...
+
+    // The above loop doesn't involve [0] (the one thing we can control) so it's
+    // not helpful towards the solution but we still need to get past it.
+    // The only test is that [4]>[5] where [4]=256*([2]+1) and every loop [2]
+    // increases by 1. The condition will be true when [2] = 256 at the start
+    // of the loop. So we start with [x, 18, 256, 6196817, 0, 65536] and do:
+    //
+    // IP is now 18 so set [4] to [2]+1
+    // IP is now 19 so multiply [4] by 256
+    // IP is now 20 so set [4] to [4]>[5] (true)
+    // IP is now 21 so add [4] to [1]
+    // IP is now 23 so set [1] to 25
+    // IP is now 26 so set [5] to [2]
+    // IP is now 27 so set [1] to 7
+    //
+    // IP is now 8 so set [2] to [5]&255
+    // IP is now 9 so add [2] to [3]
+    // IP is now 10 so set [3] to [3]&16777215
+    // IP is now 11 so multiply [3] by 65899
+    // IP is now 12 so set [3] to [3]&16777215
+    // IP is now 13 so set [2] to 256>[5] (false)
+    // IP is now 14 so add [2] to [1]
+    // IP is now 15 so add 1 to [1]
+    // IP is now 17 so set [2] to 0
+    //
+    // IP is now 18 so set [4] to [2]+1
+    // IP is now 19 so multiply [4] by 256
+    // IP is now 20 so set [4] to [4]>[5] (false for now)
+    // IP is now 21 so add [4] to [1]
+    // IP is now 22 so add 1 to [1]
+    // IP is now 24 so add 1 to [2]
+    // IP is now 25 so set [1] to 17
+    //
+    // IP is now 18 so set [4] to [2]+1
+    // IP is now 19 so multiply [4] by 256
+    // IP is now 20 so set [4] to [4]>[5] (now true)
+    // IP is now 21 so add [4] to [1]
+    // IP is now 23 so set [1] to 25
+    // IP is now 26 so set [5] to [2]
+    // IP is now 27 so set [1] to 7
+    //
+    // IP is now 8 so set [2] to [5]&255
+    // IP is now 9 so add [2] to [3]
+    // IP is now 10 so set [3] to [3]&16777215
+    // IP is now 11 so multiply [3] by 65899
+    // IP is now 12 so set [3] to [3]&16777215
+    // IP is now 13 so set [2] to 256>[5] (now true)
+    // IP is now 14 so add [2] to [1]
+    // IP is now 16 so set [1] to 27
+    //
+    // Now IP 28 where we have to consider [0], the state is:
+    // [x, 28, 1, 13270004, 1, 1]
+
+    // Loop 1 (18-25):
+    //
+    // loop {
+        // if 256*([2]+1) > [5] {
+            // break to command 26
+        // }
+        // [2]++
+    // }
+    //
+    // Command 26 sets [5] to [2] and goes to the start of loop 2 (see below)
+
+    // Loop 2 (8-17):
+    //
+    // loop {
+        // [3] += [5]&255
+        // [3] = [3]&16777215
+        // [3] *= 65899
+        // [3] = [3]&16777215
+        // if 256>[5] {
+            // break to command 28
+        // }
+        // [2] = 0
+        // Do loop 1
+    // }
+    //
+    // Command 28 offers a chance to exit if [3] == [0],
+    // otherwise [5] = [3]|65536 and [3] = 15028787, then start loop 2
+
+    let mut values = vec![];
+
+    // Everything after the check depends only on [3], so once a value of [3]
+    // is repeated the values checked against [0] go round in a cycle
+    let (start, length) = cycle::find_cycle(checked_values().inspect(|&value| values.push(value)))
+        .expect("The values checked against [0] never repeat");
+
+    // Part 1. Setting [0] equal to the first value of [3] will allow us
+    // to exit the program the quickest.
+    //
+    // Part 2. Setting [0] to the last value before repetition will run
+    // the program for the longest whilst still halting.
+    (values[0], values[start + length - 1])
+}
+
+// The values of [3] each time the program gets to command 28 and checks it
+// against [0], worked out as halting_values describes
+fn checked_values() -> impl Iterator<Item = i64> {
+    // Optimised reproduction of the program from when it initially passes the
+    // check that inputs are numbers and not strings:
+    //let mut two = 0;
+    let mut three: i64 = 15028787;
+    // [3] is still 0 here so [3]|65536 is just 65536
+    let mut five = 65536;
+    std::iter::from_fn(move || {
+        loop {
+            three += five&255;
+            three &= 16777215;
+            three *= 65899;
+            three &= 16777215;
+            if 256 > five {
+                break;
+            }
+            //two = 0;
+
+            // We can remove this loop (and variable 2 entirely), see below
+            /*
+            loop {
+                if 256*(two+1) > five {
+                    five = two;
+                    break;
+                }
+                two += 1;
+            }
+            */
+            five = ((five as f64) / 256.0).floor() as i64;
+        }
+
+        // This is the point in the loop where we can exit if [0] == [3]
+        let value = three;
+
+        five = three|65536;
+        three = 15028787;
+        Some(value)
+    })
+}
+
+pub fn part1(_input: &str) -> Result<String, AocError> {
+    let (shortest, _) = halting_values();
+    Ok(shortest.to_string())
+}
+
+pub fn part2(_input: &str) -> Result<String, AocError> {
+    let (_, longest) = halting_values();
+    Ok(longest.to_string())
+}
+
+// How much of the run to keep in order. The check that numbers aren't strings
+// and the first time round the two loops are all in it
+const TRACE_LENGTH: usize = 600;
+
+// How many times each line of the program runs when [0] is the part 1 answer,
+// so that it halts as soon as it can, with the run alongside. The loop which
+// counts up to [5] / 256 one at a time is the bit which runs the most
+pub fn draw(input: &str) -> Result<Svg, AocError> {
+    let program = elfcode::parse_program(input)?;
+    let (shortest, _) = halting_values();
+    let profile = program.profile(&mut [shortest, 0, 0, 0, 0, 0], u64::MAX, TRACE_LENGTH);
+    Ok(elfcode::draw_profile(&program, &profile))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::hash::HashSet;
+
+    const INPUT: &str = include_str!("../input.txt");
+
+    // The command which halts the program if [3] == [0]
+    const CHECK: i64 = 28;
+
+    // Run the program from [register0], giving the values of [3] each time it
+    // gets to the check, until it's been there [checks] times or halts
+    fn run_to_checks(register0: i64, checks: usize) -> Vec<i64> {
+        let program = elfcode::parse_program(INPUT).unwrap();
+        let mut registers = [register0, 0, 0, 0, 0, 0];
+        let mut values = vec![];
+        while values.len() < checks {
+            if registers[program.ip_register] == CHECK {
+                values.push(registers[3]);
+            }
+            if program.step(&mut registers).is_none() {
+                break;
+            }
+        }
+        values
+    }
+
+    #[test]
+    fn matches_the_program() {
+        // [0] is never negative so the program never halts
+        let expected: Vec<_> = checked_values().take(5).collect();
+        assert_eq!(run_to_checks(-1, 5), expected);
+    }
+
+    // The program halts the first time it gets to the check, so only sees one
+    // value of [3]
+    #[test]
+    fn part1_halts_at_the_first_check() {
+        let answer = part1(INPUT).unwrap().parse().unwrap();
+        assert_eq!(run_to_checks(answer, 2), vec![answer]);
+    }
+
+    #[test]
+    fn part2_is_the_last_new_value() {
+        let mut seen = HashSet::default();
+        let last = checked_values().take_while(|&value| seen.insert(value)).last();
+        assert_eq!(part2(INPUT).unwrap(), last.unwrap().to_string());
+    }
+}
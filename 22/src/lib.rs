@@ -0,0 +1,592 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use common::AocError;
+use common::grid::Grid;
+use common::parse;
+use common::point::Point;
+use common::search::{self, Graph};
+use common::simulation::{Simulation, StepResult};
+use common::viz::heatmap::Heatmap;
+use common::viz::svg::{Style, Svg};
+
+#[macro_use]
+extern crate lazy_static;
+
+type Moves = Vec<(CaveSystemState, usize)>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum RegionType {
+    Rocky,
+    Narrow,
+    Wet,
+    Unknown,
+}
+use self::RegionType::*;
+
+impl RegionType {
+    fn symbol(&self) -> char {
+        match self {
+            Rocky => '.',
+            Narrow => '|',
+            Wet => '=',
+            Unknown => '?',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Region {
+    t: RegionType,
+    geologic_index: Option<i32>,
+    erosion_level: Option<i32>,
+}
+
+impl Region {
+    fn new(p: Point, depth: i32, target: Point) -> Region {
+        let mut region = Region {
+            t: Unknown,
+            geologic_index: None,
+            erosion_level: None,
+        };
+
+        // The mouth and the target come first, so a target on one of the
+        // edges still has a geologic index of 0. The rest of the regions
+        // depend on their neighbours so are filled in by the cave
+        if p == Point::ORIGIN || p == target {
+            region.set_geologic_index(0, depth);
+        }
+        else if p.y == 0 {
+            region.set_geologic_index(p.x * 16807, depth);
+        }
+        else if p.x == 0 {
+            region.set_geologic_index(p.y * 48271, depth);
+        }
+
+        region
+    }
+
+    fn set_geologic_index(&mut self, gi: i32, depth: i32) {
+        self.geologic_index = Some(gi);
+        let el = (gi + depth) % 20183;
+        self.erosion_level = Some(el);
+        self.t = match el % 3 {
+            0 => Rocky,
+            1 => Wet,
+            2 => Narrow,
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CaveSystem {
+    regions: Grid<Region>,
+}
+
+impl std::fmt::Display for CaveSystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.regions.display_with(|r| r.t.symbol()))
+    }
+}
+
+impl CaveSystem {
+    fn new(width: i32, height: i32, depth: i32, target: Point) -> CaveSystem {
+
+        let mut regions = Grid::from_fn(width as usize, height as usize, |x, y| {
+            Region::new(Point::new(x as i32, y as i32), depth, target)
+        });
+
+        let err_str = "Unexpectedly unknown erosion level!";
+        for x in 1..width {
+            for y in 1..height {
+                let p = Point::new(x, y);
+                // Don't overrite the RegionType for the target
+                if p == target {
+                    continue;
+                }
+
+                let x_minus = regions[p - Point::new(1, 0)].erosion_level.expect(err_str);
+                let y_minus = regions[p - Point::new(0, 1)].erosion_level.expect(err_str);
+                regions[p].set_geologic_index(x_minus * y_minus, depth);
+            }
+        }
+
+        CaveSystem { regions }
+    }
+
+    fn get_possible_moves(&self, css: &CaveSystemState) -> Moves {
+        let current_tool = &css.tool;
+        let current_region_type = &css.region_type;
+
+
+        //### Consider moves into neighbouring regions ###//
+        let mut next_moves = vec![];
+        for n in css.pos.neighbours() {
+            let n_region_type = match self.regions.at(n) {
+                Some(region) => &region.t,
+                None => continue,
+            };
+
+            // If the current tool is valid for the neighbouring square then
+            // we can just move in at a cost of one minute
+            if VALID_GEAR.get(n_region_type).unwrap().contains(current_tool) {
+                let next_state = CaveSystemState {
+                    pos: n,
+                    tool: current_tool.clone(),
+                    region_type: *n_region_type,
+                };
+                next_moves.push((next_state, 1));
+            }
+        }
+
+
+        //### Consider swapping the current tool at a cost of 7 minutes ###//
+        let new_tool: Vec<_> =
+            TOOLS.iter().filter(
+                |t| *t != current_tool &&
+                VALID_GEAR.get(current_region_type).unwrap().contains(t)
+            ).collect();
+        assert!(new_tool.len() == 1);
+
+        let new_tool_state = CaveSystemState {
+            pos: css.pos,
+            tool: new_tool[0].clone(),
+            region_type: *current_region_type,
+        };
+        next_moves.push((new_tool_state, 7));
+
+
+        next_moves
+    }
+}
+
+impl Graph for CaveSystem {
+    type Node = CaveSystemState;
+
+    fn edges(&self, css: &CaveSystemState) -> Moves {
+        self.get_possible_moves(css)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Tool {
+    Torch,
+    ClimbingGear,
+    Neither,
+}
+use self::Tool::*;
+
+impl Tool {
+    // Where the tool is in TOOLS
+    fn index(&self) -> usize {
+        match self {
+            Torch => 0,
+            ClimbingGear => 1,
+            Neither => 2,
+        }
+    }
+}
+
+lazy_static! {
+    static ref TOOLS: Vec<Tool> = vec![Torch, ClimbingGear, Neither];
+}
+
+
+lazy_static! {
+    static ref VALID_GEAR: HashMap<RegionType, HashSet<Tool>> = {
+        let mut map = HashMap::new();
+        map.insert(Rocky, [ClimbingGear, Torch].iter().cloned().collect());
+        map.insert(Wet, [ClimbingGear, Neither].iter().cloned().collect());
+        map.insert(Narrow, [Torch, Neither].iter().cloned().collect());
+        map
+    };
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CaveSystemState {
+    pos: Point,
+    tool: Tool,
+    region_type: RegionType,
+}
+
+impl CaveSystemState {
+    fn distance(&self, other: &CaveSystemState) -> usize {
+        self.pos.distance(other.pos) as usize
+    }
+}
+
+// The furthest the target can be from the mouth of the cave in each direction,
+// and the deepest the cave can be, so that mapping the cave doesn't run out
+// of memory or overflow working out the erosion levels
+const MAX_TARGET: i32 = 10_000;
+const MAX_DEPTH: i32 = 1_000_000;
+
+fn parse_input(input: &str) -> Result<(Point, i32), AocError> {
+    let mut depth = None;
+    let mut target = None;
+
+    // The input looks like "depth: 510\ntarget: 10,10"
+    for (i, line) in input.lines().enumerate() {
+        if line.contains("depth") {
+            let [d] = parse::fixed_ints(line).map_err(|e| AocError::parse(i + 1, line, e))?;
+            depth = Some(d);
+        }
+        if line.contains("target") {
+            let [x, y] = parse::fixed_ints(line).map_err(|e| AocError::parse(i + 1, line, e))?;
+            target = Some(Point::new(x, y));
+        }
+    }
+
+    let target = target.ok_or_else(|| AocError::parse_input("there's no target"))?;
+    let depth = depth.ok_or_else(|| AocError::parse_input("there's no depth"))?;
+    if !(0..=MAX_TARGET).contains(&target.x) || !(0..=MAX_TARGET).contains(&target.y) {
+        return Err(AocError::parse_input(format!(
+            "the target's coordinates must be between 0 and {}", MAX_TARGET)));
+    }
+    if !(0..=MAX_DEPTH).contains(&depth) {
+        return Err(AocError::parse_input(format!("the depth must be between 0 and {}",
+                                                 MAX_DEPTH)));
+    }
+
+    Ok((target, depth))
+}
+
+// Just read the depth and target, for fuzzing the parser
+pub fn parse(input: &str) -> Result<(), AocError> {
+    parse_input(input).map(|_| ())
+}
+
+pub fn part1(input: &str) -> Result<String, AocError> {
+    let (target, depth) = parse_input(input)?;
+    let cs = CaveSystem::new(target.x + 1, target.y + 1, depth, target);
+
+    let danger_index: i32 = cs.regions.iter().map(|r| match r.t {
+                                                    Rocky => 0,
+                                                    Wet => 1,
+                                                    Narrow => 2,
+                                                    Unknown => unreachable!(),
+                                                }).sum();
+    Ok(danger_index.to_string())
+}
+
+// The rescuer following the quickest route to the target, one move or change
+// of tool at a time
+struct Rescue {
+    cave: CaveSystem,
+    target: Point,
+    route: Vec<CaveSystemState>,
+    // How far along the route the rescuer has got, and how long it's taken
+    at: usize,
+    minutes: usize,
+}
+
+impl Simulation for Rescue {
+    fn step(&mut self) -> Result<StepResult, AocError> {
+        let (here, next) = match (self.route.get(self.at), self.route.get(self.at + 1)) {
+            (Some(here), Some(next)) => (here, next),
+            _ => return Ok(StepResult::Finished),
+        };
+        self.minutes += if here.pos == next.pos { 7 } else { 1 };
+        self.at += 1;
+        Ok(StepResult::Changed)
+    }
+
+    // The cave is far taller than it is wide, so only the part around the
+    // rescuer is drawn. The rescuer is an @ and where they've been is a *
+    fn render(&self) -> String {
+        let here = &self.route[self.at];
+        let tool = match here.tool {
+            Torch => "the torch",
+            ClimbingGear => "the climbing gear",
+            Neither => "nothing",
+        };
+        let mut s = format!("Minute {}, holding {}\n", self.minutes, tool);
+
+        let been: HashSet<Point> = self.route[..self.at].iter().map(|s| s.pos).collect();
+        let top = (here.pos.y - 20).max(0);
+        let bottom = (here.pos.y + 20).min(self.cave.regions.height() as i32 - 1);
+        for y in top..=bottom {
+            for x in 0..self.cave.regions.width() as i32 {
+                let p = Point::new(x, y);
+                s.push(match p {
+                    _ if p == here.pos => '@',
+                    _ if p == self.target => 'T',
+                    _ if p == Point::ORIGIN => 'M',
+                    _ if been.contains(&p) => '*',
+                    _ => self.cave.regions[p].t.symbol(),
+                });
+            }
+            s.push('\n');
+        }
+        s
+    }
+}
+
+// The cave, big enough to find the quickest route through, and the target
+fn map_cave(input: &str) -> Result<(CaveSystem, Point), AocError> {
+    let (target, depth) = parse_input(input)?;
+    // Allow 20 squares extra beyond the target in x and y since the fastest
+    // route may involve some squares beyond the target x and y values.
+    Ok((CaveSystem::new(target.x + 21, target.y + 21, depth, target), target))
+}
+
+// Find the quickest route to the target, and how many minutes it takes
+fn plan_rescue(input: &str) -> Result<(Rescue, usize), AocError> {
+    let (cs, target) = map_cave(input)?;
+
+    let start = CaveSystemState {
+        pos: Point::ORIGIN,
+        tool: Torch,
+        region_type: Rocky,
+    };
+
+    let dest = CaveSystemState {
+        pos: target,
+        tool: Torch,
+        region_type: Rocky,
+    };
+
+    let quickest_path = search::astar(&cs, &start, |s| s.distance(&dest), |s| s == &dest);
+    let (route, minutes) = quickest_path
+        .ok_or_else(|| AocError::NoSolution("there's no way to the target".to_string()))?;
+    Ok((Rescue { cave: cs, target, route, at: 0, minutes: 0 }, minutes))
+}
+
+// The rescuer making their way to the target, for watching
+pub fn simulation(input: &str) -> Result<Box<dyn Simulation>, AocError> {
+    let (rescue, _) = plan_rescue(input)?;
+    Ok(Box::new(rescue))
+}
+
+// The cave with the quickest route to the target drawn over it. The dots are
+// where the rescuer changes tools
+pub fn draw(input: &str) -> Result<Svg, AocError> {
+    let (rescue, _) = plan_rescue(input)?;
+    let mut svg = Svg::new();
+    svg.grid(&rescue.cave.regions, Point::ORIGIN, |r| match r.t {
+        Rocky => Some("darkgrey"),
+        Wet => Some("steelblue"),
+        Narrow => Some("saddlebrown"),
+        Unknown => None,
+    });
+    svg.path(rescue.route.iter().map(|s| s.pos), &Style::stroke("red", 0.3));
+    let tool_changes = rescue.route.windows(2).filter(|w| w[0].pos == w[1].pos);
+    svg.points(tool_changes.map(|w| w[0].pos), 0.3, &Style::fill("yellow"));
+    svg.label(Point::ORIGIN, "M", &Style::fill("white"));
+    svg.label(rescue.target, "T", &Style::fill("white"));
+    Ok(svg)
+}
+
+// The raw erosion levels across the cave, before they're taken mod 3 to give
+// the region types, with the quickest route to the target highlighted. Each
+// level is worked out from the ones above and to the left of it, which shows
+// up as bands and streaks running across the cave
+pub fn heatmap(input: &str) -> Result<Heatmap, AocError> {
+    let (rescue, _) = plan_rescue(input)?;
+    let levels = &rescue.cave.regions;
+    let levels = Grid::from_fn(levels.width(), levels.height(), |x, y| {
+        levels[(x, y)].erosion_level.unwrap_or(0) as u64
+    });
+    let route = rescue.route.iter().map(|s| (s.pos.x as usize, s.pos.y as usize));
+    Ok(Heatmap::new(levels).highlight(route))
+}
+
+// How part 2 finds the quickest time to the target, so that the two ways can
+// be benchmarked against each other. Only the benchmarks and tests choose;
+// part2, and so the runner, always uses Dense
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    // The shared A* search, which keeps track of the states it's seen in a
+    // HashMap and can give the route
+    Generic,
+    // A* over (x, y, tool) with the best time to each state kept in a table
+    // with a slot per region and tool, which is several times faster
+    Dense,
+}
+
+// Whether [tool] can be used in a region of type [t]
+fn can_use(tool: &Tool, t: RegionType) -> bool {
+    matches!((t, tool), (Rocky, ClimbingGear | Torch) | (Wet, ClimbingGear | Neither)
+                        | (Narrow, Torch | Neither))
+}
+
+// A state waiting to be searched from: the estimated time to the target, the
+// time so far, the position and the tool
+type Queued = (u32, u32, (i32, i32), usize);
+
+// The search behind Algorithm::Dense
+struct DenseSearch {
+    width: usize,
+    target: Point,
+    // The quickest time found to each region holding each tool, by row
+    best: Vec<[u32; 3]>,
+    // Quickest first
+    queue: BinaryHeap<Reverse<Queued>>,
+}
+
+impl DenseSearch {
+    // Queue getting to [p] holding TOOLS[tool] after [minutes], unless there's
+    // a quicker way there
+    fn reach(&mut self, p: Point, tool: usize, minutes: u32) {
+        let best = &mut self.best[p.y as usize * self.width + p.x as usize][tool];
+        if minutes < *best {
+            *best = minutes;
+            // Changing tool at the end is the only extra cost which is known
+            let change = if tool == Torch.index() { 0 } else { 7 };
+            let estimate = minutes + p.distance(self.target) as u32 + change;
+            self.queue.push(Reverse((estimate, minutes, (p.x, p.y), tool)));
+        }
+    }
+}
+
+// The quickest time to the target, using Algorithm::Dense
+fn quickest_time(cave: &CaveSystem, target: Point) -> Option<u32> {
+    let width = cave.regions.width();
+    let mut search = DenseSearch {
+        width,
+        target,
+        best: vec![[u32::MAX; 3]; width * cave.regions.height()],
+        queue: BinaryHeap::new(),
+    };
+    search.reach(Point::ORIGIN, Torch.index(), 0);
+
+    while let Some(Reverse((_, minutes, (x, y), tool))) = search.queue.pop() {
+        let p = Point::new(x, y);
+        if p == target && tool == Torch.index() {
+            return Some(minutes);
+        }
+        // There's been a quicker way here since this was queued
+        if minutes > search.best[y as usize * width + x as usize][tool] {
+            continue;
+        }
+
+        for n in p.neighbours() {
+            match cave.regions.at(n) {
+                Some(region) if can_use(&TOOLS[tool], region.t) => {
+                    search.reach(n, tool, minutes + 1)
+                },
+                _ => (),
+            }
+        }
+        let here = cave.regions[p].t;
+        if let Some(other) = (0..TOOLS.len()).find(|&t| t != tool && can_use(&TOOLS[t], here)) {
+            search.reach(p, other, minutes + 7);
+        }
+    }
+    None
+}
+
+// Part 2, finding the quickest time with [algorithm]
+pub fn part2_using(input: &str, algorithm: Algorithm) -> Result<String, AocError> {
+    let minutes = match algorithm {
+        Algorithm::Generic => plan_rescue(input)?.1,
+        Algorithm::Dense => {
+            let (cave, target) = map_cave(input)?;
+            let minutes = quickest_time(&cave, target)
+                .ok_or_else(|| AocError::NoSolution("there's no way to the target".to_string()))?;
+            minutes as usize
+        },
+    };
+    Ok(minutes.to_string())
+}
+
+pub fn part2(input: &str) -> Result<String, AocError> {
+    part2_using(input, Algorithm::Dense)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::fixtures::DAY22 as EXAMPLE;
+
+    // The cave as far as the target, drawn like the puzzle does
+    #[test]
+    fn cave_snapshot() {
+        let (target, depth) = parse_input(EXAMPLE).unwrap();
+        let cave = CaveSystem::new(target.x + 1, target.y + 1, depth, target);
+        common::assert_snapshot!("example_cave", cave);
+    }
+
+    // The erosion levels of the cave as far as [size], worked out straight
+    // from the rules in the puzzle
+    fn reference_erosion_levels(size: Point, depth: i32, target: Point) -> Vec<Vec<i32>> {
+        let mut levels = vec![vec![0; size.x as usize]; size.y as usize];
+        for y in 0..size.y as usize {
+            for x in 0..size.x as usize {
+                let p = Point::new(x as i32, y as i32);
+                let geologic_index = if p == Point::ORIGIN || p == target {
+                    0
+                }
+                else if y == 0 {
+                    p.x * 16807
+                }
+                else if x == 0 {
+                    p.y * 48271
+                }
+                else {
+                    levels[y][x - 1] * levels[y - 1][x]
+                };
+                levels[y][x] = (geologic_index + depth) % 20183;
+            }
+        }
+        levels
+    }
+
+    // Targets on the edges of the cave, or right next to the mouth, are where
+    // the target's rule and the edges' rules meet
+    #[test]
+    fn targets_on_the_edges() {
+        for depth in [510, 11_739] {
+            for x in 0..5 {
+                for y in 0..5 {
+                    let target = Point::new(x, y);
+                    let size = target + Point::new(3, 3);
+                    let cave = CaveSystem::new(size.x, size.y, depth, target);
+                    let levels: Vec<Vec<_>> = (0..size.y)
+                        .map(|y| (0..size.x)
+                            .map(|x| cave.regions[Point::new(x, y)].erosion_level.unwrap())
+                            .collect())
+                        .collect();
+                    assert_eq!(levels, reference_erosion_levels(size, depth, target),
+                               "target {} with depth {}", target, depth);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn targets_next_to_the_mouth() {
+        // With a depth of 510 the mouth and target are both rocky, so the
+        // torch can be carried straight there
+        for target in ["0,0", "1,0", "0,1"] {
+            let input = format!("depth: 510\ntarget: {}", target);
+            assert_eq!(part1(&input).unwrap(), "0", "{}", target);
+            let minutes = if target == "0,0" { "0" } else { "1" };
+            assert_eq!(part2_using(&input, Algorithm::Dense).unwrap(), minutes, "{}", target);
+            assert_eq!(part2_using(&input, Algorithm::Generic).unwrap(), minutes, "{}", target);
+        }
+    }
+
+    #[test]
+    fn bad_headers() {
+        assert!(parse_input("depth: 510").is_err());
+        assert!(parse_input("target: 10,10").is_err());
+        assert!(parse_input("depth: 510\ntarget: 10").is_err());
+        assert!(parse_input("depth: -510\ntarget: 10,10").is_err());
+        assert!(parse_input("depth: 510\ntarget: -1,10").is_err());
+        assert!(parse_input("depth: 510\ntarget: 10,99999999999").is_err());
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), "114");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2_using(EXAMPLE, Algorithm::Dense).unwrap(), "45");
+        assert_eq!(part2_using(EXAMPLE, Algorithm::Generic).unwrap(), "45");
+    }
+}
@@ -0,0 +1,248 @@
+use std::cmp::{max, Ordering};
+use std::collections::BinaryHeap;
+
+use common::AocError;
+use common::parse;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+struct Position {
+    x: i64,
+    y: i64,
+    z: i64,
+}
+
+impl Position {
+    fn new(x: i64, y: i64, z: i64) -> Position {
+        Position { x, y, z }
+    }
+
+    fn distance(&self, other: &Position) -> i64 {
+        (self.x - other.x).abs()
+            + (self.y - other.y).abs()
+            + (self.z - other.z).abs()
+    }
+}
+
+#[derive(Debug)]
+struct Nanobot {
+    pos: Position,
+    signal_radius: i64,
+}
+
+impl Nanobot {
+    fn new(x: i64, y: i64, z: i64, r: i64) -> Nanobot {
+        Nanobot {
+            pos: Position { x, y, z },
+            signal_radius: r,
+        }
+    }
+
+    fn is_bot_in_range(&self, other: &Nanobot) -> bool {
+        self.pos.distance(&other.pos) <= self.signal_radius
+    }
+
+    fn is_cube_in_range(&self, cube: &Cube) -> bool {
+        cube.distance(&self.pos) <= self.signal_radius
+    }
+}
+
+// An axis-aligned cube of positions, from [min] to [min] + [size] - 1
+// inclusive along each axis
+#[derive(Debug, PartialEq, Eq)]
+struct Cube {
+    min: Position,
+    size: i64,
+}
+
+impl Cube {
+    // The distance from a position to the nearest point inside the cube
+    fn distance(&self, pos: &Position) -> i64 {
+        let axis_distance = |p: i64, min: i64| {
+            let max = min + self.size - 1;
+            if p < min { min - p } else if p > max { p - max } else { 0 }
+        };
+        axis_distance(pos.x, self.min.x)
+            + axis_distance(pos.y, self.min.y)
+            + axis_distance(pos.z, self.min.z)
+    }
+
+    fn split(&self) -> Vec<Cube> {
+        let half = self.size / 2;
+        let mut cubes = vec![];
+        for &dx in &[0, half] {
+            for &dy in &[0, half] {
+                for &dz in &[0, half] {
+                    let min = Position::new(self.min.x + dx,
+                                            self.min.y + dy,
+                                            self.min.z + dz);
+                    cubes.push(Cube { min, size: half });
+                }
+            }
+        }
+        cubes
+    }
+}
+
+// A cube waiting to be searched, along with how many nanobots have a point in
+// the cube in range. No position in the cube can be in range of more bots
+// than that, which is what makes the search below sound.
+#[derive(Debug, PartialEq, Eq)]
+struct SearchCube {
+    cube: Cube,
+    bots_in_range: usize,
+    origin_distance: i64,
+}
+
+impl SearchCube {
+    fn new(cube: Cube, nanobots: &[Nanobot]) -> SearchCube {
+        let bots_in_range =
+            nanobots.iter().filter(|n| n.is_cube_in_range(&cube)).count();
+        let origin_distance = cube.distance(&Position::new(0, 0, 0));
+        SearchCube { cube, bots_in_range, origin_distance }
+    }
+}
+
+impl Ord for SearchCube {
+    // BinaryHeap is a max-heap so the 'greatest' cube is searched first. That
+    // should be the cube with most bots in range, then the one closest to the
+    // origin, then the smallest one
+    fn cmp(&self, other: &SearchCube) -> Ordering {
+        self.bots_in_range.cmp(&other.bots_in_range)
+            .then(other.origin_distance.cmp(&self.origin_distance))
+            .then(other.cube.size.cmp(&self.cube.size))
+    }
+}
+
+impl PartialOrd for SearchCube {
+    fn partial_cmp(&self, other: &SearchCube) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Best-first search over ever smaller cubes. The first single position cube
+// to come off the heap is in range of the most bots, since every cube still
+// on the heap has an upper bound on its bots in range no bigger than it, and
+// is the closest such position to the origin because of the tie breaking.
+fn find_best_position(nanobots: &[Nanobot]) -> Position {
+    let mut min = Position::new(0, 0, 0);
+    let mut max_pos = Position::new(0, 0, 0);
+    for n in nanobots {
+        min.x = min.x.min(n.pos.x);
+        min.y = min.y.min(n.pos.y);
+        min.z = min.z.min(n.pos.z);
+        max_pos.x = max(max_pos.x, n.pos.x);
+        max_pos.y = max(max_pos.y, n.pos.y);
+        max_pos.z = max(max_pos.z, n.pos.z);
+    }
+
+    // Start with a cube containing all the bots. Use a power of two side
+    // length so that it splits evenly all the way down to single positions
+    let longest_side = max(max_pos.x - min.x,
+                           max(max_pos.y - min.y, max_pos.z - min.z)) + 1;
+    let mut size = 1;
+    while size < longest_side {
+        size *= 2;
+    }
+
+    let mut heap = BinaryHeap::new();
+    heap.push(SearchCube::new(Cube { min, size }, nanobots));
+
+    while let Some(search_cube) = heap.pop() {
+        if search_cube.cube.size == 1 {
+            return search_cube.cube.min;
+        }
+        for cube in search_cube.cube.split() {
+            heap.push(SearchCube::new(cube, nanobots));
+        }
+    }
+    unreachable!();
+}
+
+// Lines look like "pos=<0,0,0>, r=4"
+fn parse_input(input: &str) -> Result<Vec<Nanobot>, AocError> {
+    let mut nanobots = vec![];
+    for (i, line) in input.lines().enumerate() {
+        let [x, y, z, r] = parse::fixed_ints(line).map_err(|e| AocError::parse(i + 1, line, e))?;
+        nanobots.push(Nanobot::new(x, y, z, r));
+    }
+    Ok(nanobots)
+}
+
+pub fn part1(input: &str) -> Result<String, AocError> {
+    let nanobots = parse_input(input)?;
+    let strongest = nanobots.iter().max_by_key(|n| n.signal_radius)
+        .ok_or_else(|| AocError::NoSolution("there are no nanobots".to_string()))?;
+
+    let in_range_count =
+        nanobots.iter().filter(|n| strongest.is_bot_in_range(n)).count();
+    Ok(in_range_count.to_string())
+}
+
+pub fn part2(input: &str) -> Result<String, AocError> {
+    let nanobots = parse_input(input)?;
+    let best = find_best_position(&nanobots);
+    Ok(best.distance(&Position::new(0, 0, 0)).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The answer to part 2 found by trying every position bots can reach
+    fn brute_force(nanobots: &[Nanobot]) -> i64 {
+        let reach = nanobots.iter()
+            .map(|n| n.pos.x.abs().max(n.pos.y.abs()).max(n.pos.z.abs()) + n.signal_radius)
+            .max().unwrap_or(0);
+        let mut best = (0, 0);
+        for x in -reach..=reach {
+            for y in -reach..=reach {
+                for z in -reach..=reach {
+                    let pos = Position::new(x, y, z);
+                    let in_range = nanobots.iter()
+                        .filter(|n| n.pos.distance(&pos) <= n.signal_radius)
+                        .count();
+                    let distance = pos.distance(&Position::new(0, 0, 0));
+                    if (in_range, -distance) > (best.0, -best.1) {
+                        best = (in_range, distance);
+                    }
+                }
+            }
+        }
+        best.1
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "slow-tests"), ignore = "slow, run with --features slow-tests")]
+    fn matches_brute_force() {
+        // The same pseudo-random bots every run, close enough together that
+        // their ranges overlap in lots of different ways
+        let mut state: u64 = 2018;
+        let mut random = |below: u64| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((state >> 33) % below) as i64
+        };
+        for _ in 0..50 {
+            let count = 1 + random(6);
+            let nanobots: Vec<_> = (0..count)
+                .map(|_| Nanobot::new(random(17) - 8, random(17) - 8, random(17) - 8, random(7)))
+                .collect();
+            let best = find_best_position(&nanobots).distance(&Position::new(0, 0, 0));
+            assert_eq!(best, brute_force(&nanobots), "{:?}", nanobots);
+        }
+    }
+
+    #[test]
+    fn part1_example() {
+        let input = "pos=<0,0,0>, r=4\npos=<1,0,0>, r=1\npos=<4,0,0>, r=3\npos=<0,2,0>, r=1\n\
+                     pos=<0,5,0>, r=3\npos=<0,0,3>, r=1\npos=<1,1,1>, r=1\npos=<1,1,2>, r=1\n\
+                     pos=<1,3,1>, r=1";
+        assert_eq!(part1(input).unwrap(), "7");
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = "pos=<10,12,12>, r=2\npos=<12,14,12>, r=2\npos=<16,12,12>, r=4\n\
+                     pos=<14,14,14>, r=6\npos=<50,50,50>, r=200\npos=<10,10,10>, r=5";
+        assert_eq!(part2(input).unwrap(), "36");
+    }
+}
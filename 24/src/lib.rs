@@ -0,0 +1,380 @@
+use std::collections::{HashMap, HashSet};
+
+use common::AocError;
+use common::bitset::BitSet;
+
+extern crate regex;
+use regex::{Regex, Match};
+
+type Army = HashMap<i32, Group>;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+enum DamageType {
+    Bludgeoning,
+    Slashing,
+    Fire,
+    Cold,
+    Radiation,
+}
+use self::DamageType::*;
+
+impl std::str::FromStr for DamageType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<DamageType, Self::Err> {
+        match s {
+            "bludgeoning" => Ok(Bludgeoning),
+            "slashing" => Ok(Slashing),
+            "fire" => Ok(Fire),
+            "cold" => Ok(Cold),
+            "radiation" => Ok(Radiation),
+            _ => Err(format!("unknown damage type '{}'", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Group {
+    units: i32,
+    hp: i32,
+    immune: HashSet<DamageType>,
+    weak: HashSet<DamageType>,
+    damage: i32,
+    dt: DamageType,
+    initiative: i32,
+    id: i32,
+    target: Option<Option<i32>>,
+}
+
+impl Group {
+    fn effective_power(&self) -> i32 {
+        self.units * self.damage
+    }
+
+    fn calculate_damage(&self, other: &Group) -> i32 {
+        if other.immune.contains(&self.dt) {
+            0
+        }
+        else if other.weak.contains(&self.dt) {
+            self.effective_power() * 2
+        }
+        else {
+            self.effective_power()
+        }
+    }
+
+    // Returns the number of units killed
+    fn resolve_damage(&mut self, damage: i32) -> i32 {
+        let excess_damage = damage % self.hp;
+        let actual_damage = damage - excess_damage;
+        let losses = std::cmp::min(self.units, actual_damage / self.hp);
+        self.units -= losses;
+        losses
+    }
+
+    fn reset_target(&mut self) {
+        self.target = None;
+    }
+
+    fn find_target(&mut self, enemy_army: &Army, taken_targets: BitSet) {
+        let targets: Vec<_> = enemy_army
+                                .values()
+                                .filter(|g| !taken_targets.contains(g.id as usize))
+                                .collect();
+
+        let mut target = None;
+        let mut max_damage = -1;
+        for t in &targets {
+            let potential_damage = self.calculate_damage(t);
+
+            if potential_damage > max_damage {
+                max_damage = potential_damage;
+                target = Some(t);
+            }
+            else if potential_damage == max_damage {
+                // Use effective power as a tie breaker
+                let current_power = target.unwrap().effective_power();
+                if t.effective_power() > current_power {
+                    target = Some(t);
+                }
+                else if t.effective_power() == current_power {
+                    // Use initiative as a further tie breaker
+                    if t.initiative > target.unwrap().initiative {
+                        target = Some(t);
+                    }
+                }
+            }
+        }
+
+        if max_damage > 0 {
+            self.target = Some(Some(target.unwrap().id));
+        }
+        else {
+            // Outer option shows if we've chosen a target, however the target
+            // can be None hence the inner option.
+            self.target = Some(None);
+        }
+    }
+}
+
+fn select_all_targets(army: &mut Army, enemy_army: &Army) {
+    for g in army.values_mut() {
+        g.reset_target();
+    }
+
+    let mut target_taken = BitSet::new();
+
+    loop {
+        let mut next_group_to_choose = None;
+        let mut max_power = 0;
+        let mut max_power_initiative = 0;
+
+        for (id, g) in army.clone() {
+            // Already chosen a target
+            if g.target.is_some() {
+                continue;
+            }
+
+            let power = g.effective_power();
+            if power > max_power {
+                max_power = power;
+                max_power_initiative = g.initiative;
+                next_group_to_choose = Some(id);
+            }
+            else if power == max_power {
+                // Use initiative to break ties
+                if g.initiative > max_power_initiative {
+                    max_power_initiative = g.initiative;
+                    next_group_to_choose = Some(id);
+                }
+            }
+        }
+
+        // All groups have chosen a target
+        if next_group_to_choose.is_none() {
+            break;
+        }
+
+        let g = army.get_mut(&next_group_to_choose.unwrap()).unwrap();
+        assert!(g.target.is_none());
+        g.find_target(enemy_army, target_taken);
+        assert!(g.target.is_some());
+
+        match g.target.unwrap() {
+            None => {},
+            Some(target) => {
+                target_taken.insert(target as usize);
+            },
+        }
+    }
+}
+
+// Returns the total number of units killed during the turn
+fn do_turn(army1: &mut Army, army2: &mut Army) -> i32 {
+    select_all_targets(army1, army2);
+    select_all_targets(army2, army1);
+
+    let mut all_groups = vec![];
+    army1.values().for_each(|g| all_groups.push(g.clone()));
+    army2.values().for_each(|g| all_groups.push(g.clone()));
+
+    // Order by decreasing initiative
+    all_groups.sort_unstable_by_key(|g| 999999 - g.initiative);
+
+    // Resolve attacks in order of decreasing initiative
+    let mut units_killed = 0;
+    for ref id in all_groups.iter().map(|g| g.id) {
+        // Get a ref to the attacking group
+        let group = if army1.contains_key(id) {
+            army1.get(id).expect("Unknown group id").clone()
+        }
+        else {
+            army2.get(id).expect("Unknown group id").clone()
+        };
+
+        // Skip this group it's got no target or no units
+        let target_id = match group.target.unwrap() {
+            Some(target_id) if group.units > 0 => target_id,
+            _ => continue,
+        };
+
+        // Get a mut ref to the target group
+        let target = if army1.contains_key(&target_id) {
+            army1.get_mut(&target_id).expect("Unknown group id")
+        }
+        else {
+            army2.get_mut(&target_id).expect("Unknown group id")
+        };
+
+        // Resolve the attck itself
+        let damage = group.calculate_damage(target);
+        units_killed += target.resolve_damage(damage);
+    }
+
+    // Remove any defeated groups
+    army1.retain(|_, g| g.units > 0);
+    army2.retain(|_, g| g.units > 0);
+
+    units_killed
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    ImmuneSystemWins(i32),
+    InfectionWins(i32),
+    // Neither army can kill any more of the other's units, so the fight
+    // would go on forever
+    Stalemate,
+}
+
+fn fight(immune_army: &mut Army, infection_army: &mut Army) -> Outcome {
+    loop {
+        if infection_army.is_empty() {
+            return Outcome::ImmuneSystemWins(
+                immune_army.values().map(|g| g.units).sum());
+        }
+        if immune_army.is_empty() {
+            return Outcome::InfectionWins(
+                infection_army.values().map(|g| g.units).sum());
+        }
+        if do_turn(immune_army, infection_army) == 0 {
+            return Outcome::Stalemate;
+        }
+    }
+}
+
+fn parse_damange_types(m: Option<Match>) -> Result<HashSet<DamageType>, String> {
+    match m {
+        None => Ok(HashSet::new()),
+        Some(m) => {
+            let s = m.as_str();
+            let mut set = HashSet::new();
+            for dt in s.split(", ") {
+                set.insert(dt.parse()?);
+            }
+            Ok(set)
+        },
+    }
+}
+
+// Parse a group, giving the reason if it can't be parsed
+fn parse_army(re: &Regex, s: &str, id: i32) -> Result<Group, String> {
+    let c = re.captures(s).ok_or_else(|| format!("'{}' doesn't describe a group", s))?;
+    let number = |name| c.name(name).unwrap().as_str().parse::<i32>()
+        .map_err(|e| e.to_string());
+
+    let units = number("units")?;
+    let hp = number("hp")?;
+    // There are two different possible immune captues in the regex since
+    // immunities and weakness can be specified in any order
+    let mut immune = parse_damange_types(c.name("immune1"))?;
+    if immune.is_empty() {
+        immune = parse_damange_types(c.name("immune2"))?;
+    }
+    let weak = parse_damange_types(c.name("weak"))?;
+    let damage = number("damage")?;
+    let dt: DamageType = c.name("damage_type").unwrap().as_str().parse()?;
+    let initiative = number("initiative")?;
+
+    Ok(Group {
+        units, hp, immune, weak, damage, dt, initiative, id, target: None
+    })
+}
+
+fn parse_input(input: &str) -> Result<(Army, Army), AocError> {
+    let input_regex = Regex::new(r"^(?P<units>\d+) units each with (?P<hp>\d+) hit points (?:\((?:immune to (?P<immune1>[^;]+))?(?:; )?(?:weak to (?P<weak>[^;]+))?(?:; )?(?:immune to (?P<immune2>[^;]+))?\) )?with an attack that does (?P<damage>\d+) (?P<damage_type>\w+) damage at initiative (?P<initiative>\d+)$").unwrap();
+
+    let mut immune_army = HashMap::new();
+    let mut infection_army = HashMap::new();
+
+    let mut id = 0;
+    // Assume the immune system armies are listed first
+    let mut infection = false;
+    for (i, line) in input.lines().enumerate() {
+        match line {
+            "Immune System:" | "" => {},
+            "Infection:" => infection = true,
+            _ => {
+                // The groups which have been targeted are tracked in a BitSet
+                if id as usize >= BitSet::CAPACITY {
+                    return Err(AocError::Unsupported(format!(
+                        "there are more than {} groups", BitSet::CAPACITY)));
+                }
+                let a = parse_army(&input_regex, line, id)
+                    .map_err(|reason| AocError::parse(i + 1, line, reason))?;
+                if infection {
+                    infection_army.insert(id, a);
+                }
+                else {
+                    immune_army.insert(id, a);
+                }
+                id += 1;
+            },
+        }
+    }
+
+    Ok((immune_army, infection_army))
+}
+
+// The answer is the number of units the winning army has left
+pub fn part1(input: &str) -> Result<String, AocError> {
+    let (mut immune_army, mut infection_army) = parse_input(input)?;
+
+    match fight(&mut immune_army, &mut infection_army) {
+        Outcome::InfectionWins(sum) => Ok(sum.to_string()),
+        Outcome::ImmuneSystemWins(sum) => Ok(sum.to_string()),
+        Outcome::Stalemate => {
+            Err(AocError::NoSolution("the fight ends in a stalemate".to_string()))
+        },
+    }
+}
+
+// The answer is the number of units the immune system has left after winning
+// with the smallest possible boost
+pub fn part2(input: &str) -> Result<String, AocError> {
+    let (initial_immune_army, initial_infection_army) = parse_input(input)?;
+
+    let remaining = |boost: i32| {
+        let mut immune_army = initial_immune_army.clone();
+        let mut infection_army = initial_infection_army.clone();
+
+        // Apply the boost
+        immune_army.values_mut().for_each(|g| g.damage += boost);
+
+        // In some cases the fight will go on forever because there will be two
+        // groups left who cannot deal damage to one another because of
+        // immunities. That's no good to the reindeer so keep boosting
+        match fight(&mut immune_army, &mut infection_army) {
+            Outcome::ImmuneSystemWins(sum) => Some(sum),
+            _ => None,
+        }
+    };
+
+    // Try every boost in turn. A bigger boost can still end in a stalemate
+    // after a smaller one wins, so there's no skipping ahead. The search gives
+    // up at a boost where every attacking unit kills at least one unit of
+    // whatever group it hits, which is plenty
+    let max_hp = initial_infection_army.values().map(|g| g.hp).max().unwrap_or(0);
+    let sum = (1..=max_hp).find_map(remaining)
+        .ok_or_else(|| AocError::NoSolution("the immune system can't win with any \
+                                             boost".to_string()))?;
+    Ok(sum.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::fixtures::DAY24 as EXAMPLE;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), "5216");
+    }
+
+    // The smallest boost the immune system wins with is 1570
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), "51");
+    }
+}
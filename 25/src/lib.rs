@@ -0,0 +1,69 @@
+use common::AocError;
+use common::combin;
+use common::dsu::DisjointSet;
+use common::parse;
+
+#[derive(Debug, Clone, Copy)]
+struct Point {
+    x: i32,
+    y: i32,
+    z: i32,
+    t: i32,
+}
+
+impl Point {
+    fn distance(&self, other: &Point) -> i32 {
+        (self.x - other.x).abs()
+            + (self.y - other.y).abs()
+            + (self.z - other.z).abs()
+            + (self.t - other.t).abs()
+    }
+}
+
+// Parse a point like "-1,2,2,0", giving the reason if it can't be parsed
+fn parse_point(s: &str) -> Result<Point, String> {
+    let [x, y, z, t] = parse::fixed_ints(s)?;
+    Ok(Point { x, y, z, t })
+}
+
+// There's no part 2 on the last day, so this is the only solver
+pub fn part1(input: &str) -> Result<String, AocError> {
+    let mut points = vec![];
+    for (i, line) in input.lines().enumerate() {
+        points.push(parse_point(line).map_err(|reason| AocError::parse(i + 1, line, reason))?);
+    }
+
+    // Points close enough to each other are in the same constellation, so
+    // each set is a constellation
+    let mut constellations = DisjointSet::new(points.len());
+    for ((i, p), (j, other_p)) in combin::pairs(points.iter().enumerate()) {
+        if p.distance(other_p) <= 3 {
+            constellations.union(i, j);
+        }
+    }
+
+    Ok(constellations.count().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The examples from the puzzle description, with how many constellations
+    // each has
+    #[test]
+    fn part1_examples() {
+        let examples = [
+            ("0,0,0,0\n3,0,0,0\n0,3,0,0\n0,0,3,0\n0,0,0,3\n0,0,0,6\n9,0,0,0\n12,0,0,0", "2"),
+            ("-1,2,2,0\n0,0,2,-2\n0,0,0,-2\n-1,2,0,0\n-2,-2,-2,2\n3,0,2,-1\n-1,3,2,2\n\
+              -1,0,-1,0\n0,2,1,-2\n3,0,0,0", "4"),
+            ("1,-1,0,1\n2,0,-1,0\n3,2,-1,0\n0,0,3,1\n0,0,-1,-1\n2,3,-2,0\n-2,2,0,0\n\
+              2,-2,0,-1\n1,-1,0,-1\n3,2,0,2", "3"),
+            ("1,-1,-1,-2\n-2,-2,0,1\n0,2,1,3\n-2,3,-2,1\n0,2,3,-2\n-1,-1,1,-2\n0,-2,-1,0\n\
+              -2,2,3,-1\n1,2,2,0\n-1,-2,0,-2", "8"),
+        ];
+        for &(points, constellations) in &examples {
+            assert_eq!(part1(points).unwrap(), constellations, "{}", points);
+        }
+    }
+}
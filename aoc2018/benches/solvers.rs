@@ -0,0 +1,107 @@
+// Benchmarks for the slowest solvers, so that we can tell whether a refactor
+// has made them faster or slower. Run with `cargo bench -p aoc2018`
+//
+// Each solver is run against the example from the puzzle description and, if
+// it's there, the real input (see common::fixtures for where it's looked
+// for). Solvers which use more than one thread are also run with a range of
+// thread counts, to show how well they scale
+//
+// Days 1, 12, 18 and 22 spend most of their time hashing, so to see what the
+// fast-hash feature saves save a baseline without it and compare against that
+// with it:
+//
+//     cargo bench -p aoc2018 -- --save-baseline sip
+//     cargo bench -p aoc2018 --features fast-hash -- --baseline sip
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rayon::ThreadPoolBuilder;
+
+use common::fixtures;
+use common::Solver;
+
+fn bench_solver(c: &mut Criterion, name: &str, day: u32, solver: Solver) {
+    let mut group = c.benchmark_group(name);
+    // Some of these take seconds on the real input so don't take too many
+    // samples
+    group.sample_size(10);
+
+    let example = fixtures::example(day).expect("Benchmarked days have an example");
+    let mut inputs = vec![("example", example.to_string())];
+    if let Some(input) = fixtures::input(day) {
+        inputs.push(("real", input));
+    }
+
+    for (input_name, input) in &inputs {
+        group.bench_with_input(BenchmarkId::from_parameter(input_name), input,
+                               |b, input| b.iter(|| solver(input).unwrap()));
+    }
+    group.finish();
+}
+
+// Run [solver] on the real input if it's there, otherwise the example, with 1,
+// 2, 4 and so on threads up to as many as the machine has
+fn bench_scaling(c: &mut Criterion, name: &str, day: u32, solver: Solver) {
+    let mut group = c.benchmark_group(format!("{} scaling", name));
+    group.sample_size(10);
+
+    let input = fixtures::input(day).or_else(|| fixtures::example(day).map(str::to_string))
+        .expect("Benchmarked days have an example");
+    let cpus = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let counts = std::iter::successors(Some(1), |n| Some(n * 2)).take_while(|&n| n < cpus);
+    for threads in counts.chain(std::iter::once(cpus)) {
+        let pool = ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+        group.bench_with_input(BenchmarkId::new("threads", threads), &input,
+                               |b, input| b.iter(|| pool.install(|| solver(input).unwrap())));
+    }
+    group.finish();
+}
+
+// Day 1's search for the first repeated frequency, through a set of all of
+// the frequencies so far
+fn day1(c: &mut Criterion) {
+    bench_solver(c, "day1 part 2", 1, day01::part2);
+}
+
+// Day 11's search for the most powerful square, over every size at once
+fn day11(c: &mut Criterion) {
+    bench_solver(c, "day11 part 2", 11, day11::part2);
+    bench_scaling(c, "day11 part 2", 11, day11::part2);
+}
+
+// Days 12 and 18 find where their simulations start repeating by hashing
+// every state they reach
+fn day12(c: &mut Criterion) {
+    bench_solver(c, "day12 part 2", 12, day12::part2);
+}
+
+// Day 15's combat simulation, where part 2 reruns the battle for each attack
+// boost, several at once. With one thread that's the same as trying them one
+// after another
+fn day15(c: &mut Criterion) {
+    bench_solver(c, "day15 part 1", 15, day15::part1);
+    bench_solver(c, "day15 part 2", 15, day15::part2);
+    bench_scaling(c, "day15 part 2", 15, day15::part2);
+}
+
+fn day18(c: &mut Criterion) {
+    bench_solver(c, "day18 part 2", 18, day18::part2);
+}
+
+// Day 19's VM. Part 2 only runs the program until it has set up the number
+// whose divisors it sums, then finds the divisors itself
+fn day19(c: &mut Criterion) {
+    bench_solver(c, "day19 part 1", 19, day19::part1);
+    bench_solver(c, "day19 part 2", 19, day19::part2);
+}
+
+// Day 22's cave mapping and pathfinding, with part 2 using each algorithm
+fn day22(c: &mut Criterion) {
+    bench_solver(c, "day22 part 1", 22, day22::part1);
+    bench_solver(c, "day22 part 2 (dense)", 22,
+                 |input| day22::part2_using(input, day22::Algorithm::Dense));
+    bench_solver(c, "day22 part 2 (generic)", 22,
+                 |input| day22::part2_using(input, day22::Algorithm::Generic));
+}
+
+criterion_group!(benches, day1, day11, day12, day15, day18, day19, day22);
+criterion_main!(benches);
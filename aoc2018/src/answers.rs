@@ -0,0 +1,62 @@
+// The known correct answers, for checking the solvers still get them right
+
+use std::collections::HashMap;
+use std::fs;
+
+use serde::Deserialize;
+
+use crate::runner::DayResult;
+use crate::Error;
+
+#[derive(Debug, Default, Deserialize)]
+struct DayAnswers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+// The answers file has a table per day, e.g.
+//
+// [day19]
+// part1 = "1080"
+// part2 = "11106760"
+#[derive(Debug, Default)]
+pub struct Answers {
+    days: HashMap<u32, DayAnswers>,
+}
+
+impl Answers {
+    pub fn load(path: &str) -> Result<Answers, Error> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        let tables: HashMap<String, DayAnswers> = toml::from_str(&contents)
+            .map_err(|e| format!("Failed to parse {}: {}", path, e))?;
+
+        let mut days = HashMap::new();
+        for (name, answers) in tables {
+            let day = name.strip_prefix("day")
+                .and_then(|d| d.parse().ok())
+                .ok_or_else(|| format!("Unexpected table [{}] in {}, \
+                                        expected e.g. [day01]", name, path))?;
+            days.insert(day, answers);
+        }
+        Ok(Answers { days })
+    }
+
+    pub fn expected(&self, day: u32, part: u32) -> Option<&str> {
+        let answers = self.days.get(&day)?;
+        match part {
+            1 => answers.part1.as_deref(),
+            2 => answers.part2.as_deref(),
+            _ => None,
+        }
+    }
+
+    // Fill in the expected answer for every part of [results] that we know
+    pub fn annotate(&self, results: &mut [DayResult]) {
+        for r in results {
+            for p in &mut r.parts {
+                p.expected = self.expected(r.day, p.part).map(|e| e.to_string());
+            }
+        }
+    }
+}
@@ -0,0 +1,143 @@
+// Talking to adventofcode.com, which needs the session cookie from a logged in
+// browser to know whose puzzle input to give us and who's answering
+
+use std::env;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::Error;
+
+const BASE_URL: &str = "https://adventofcode.com/2018";
+
+// The environment variable holding the session cookie
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+// Advent of Code asks automated tools to identify themselves
+const USER_AGENT: &str = "github.com/btabram/AdventOfCode2018 aoc2018 runner";
+
+// The session cookie from the environment, or failing that the config
+pub fn session(config: &Config) -> Result<String, Error> {
+    let session = env::var(SESSION_VAR).ok().or_else(|| config.session.clone());
+    match session {
+        Some(s) if !s.trim().is_empty() => Ok(s.trim().to_string()),
+        _ => Err(format!("No session cookie, set {} or session in the config \
+                          file to the value of the session cookie from \
+                          adventofcode.com", SESSION_VAR).into()),
+    }
+}
+
+fn request(method: &str, url: &str, session: &str) -> ureq::Request {
+    ureq::request(method, url)
+        .set("Cookie", &format!("session={}", session))
+        .set("User-Agent", USER_AGENT)
+}
+
+fn response_text(url: &str, response: Result<ureq::Response, ureq::Error>)
+        -> Result<String, Error> {
+    match response {
+        Ok(r) => Ok(r.into_string()?),
+        // The site gives a 400 for an invalid cookie and a 404 for puzzles
+        // which haven't been released
+        Err(ureq::Error::Status(code, r)) => {
+            let body = r.into_string().unwrap_or_default();
+            Err(format!("{} returned {}: {}", url, code, body.trim()).into())
+        },
+        // The error already says which URL failed
+        Err(e) => Err(format!("Request failed: {}", e).into()),
+    }
+}
+
+// Download the puzzle input for [day]
+pub fn fetch_input(day: u32, session: &str) -> Result<String, Error> {
+    let url = format!("{}/day/{}/input", BASE_URL, day);
+    response_text(&url, request("GET", &url, session).call())
+}
+
+// What the site made of a submitted answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    // Wrong, without a hint as to which way
+    Incorrect,
+    // Wrong answers mean a wait before the next one is allowed
+    RateLimited,
+    // The part has already been solved, or part 1 hasn't been yet
+    WrongLevel,
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::Incorrect => "incorrect",
+            Verdict::RateLimited => "rate limited",
+            Verdict::WrongLevel => "already solved or not unlocked yet",
+            Verdict::Unknown => "unknown",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+// The response to an answer is a whole page, but the interesting bit is the
+// text in its <article>
+fn article_text(html: &str) -> String {
+    let article = match (html.find("<article>"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn parse_verdict(text: &str) -> Verdict {
+    if text.contains("That's the right answer") {
+        Verdict::Correct
+    }
+    else if text.contains("too high") {
+        Verdict::TooHigh
+    }
+    else if text.contains("too low") {
+        Verdict::TooLow
+    }
+    else if text.contains("That's not the right answer") {
+        Verdict::Incorrect
+    }
+    else if text.contains("You gave an answer too recently") {
+        Verdict::RateLimited
+    }
+    else if text.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    }
+    else {
+        Verdict::Unknown
+    }
+}
+
+// Submit [answer] for [part] of [day]. Gives the verdict along with the
+// site's message, which says how long to wait after a wrong answer
+pub fn submit_answer(day: u32, part: u32, answer: &str, session: &str)
+        -> Result<(Verdict, String), Error> {
+    let url = format!("{}/day/{}/answer", BASE_URL, day);
+    let level = part.to_string();
+    let response = request("POST", &url, session)
+        .send_form(&[("level", &level), ("answer", answer)]);
+    let text = article_text(&response_text(&url, response)?);
+    Ok((parse_verdict(&text), text))
+}
@@ -0,0 +1,156 @@
+// Timings saved from one run of `aoc2018 bench`, for later runs to be compared
+// against so that a refactor which slows a day down gets noticed
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::table;
+use crate::Error;
+
+// How long each day took to solve, in milliseconds. They're saved as JSON, e.g.
+//
+// {
+//   "days": {
+//     "1": 5.96,
+//     "11": 17.2
+//   }
+// }
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Timings {
+    pub days: BTreeMap<u32, f64>,
+}
+
+impl Timings {
+    pub fn load(path: &str) -> Result<Timings, Error> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}. Save a baseline to compare \
+                                  against with --save", path, e))?;
+        Ok(serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse {}: {}", path, e))?)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Error> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json + "\n").map_err(|e| format!("Failed to write {}: {}", path, e))?;
+        Ok(())
+    }
+}
+
+// The middle of [times], which is steadier than the mean when the odd run is
+// held up by something else on the machine
+pub fn median(times: &mut [Duration]) -> Duration {
+    times.sort();
+    times[times.len() / 2]
+}
+
+// How much slower than its baseline a day can get before it's a regression
+#[derive(Debug, Clone, Copy)]
+pub struct Tolerance {
+    pub percent: f64,
+    // Days which get slower by less than this aren't regressions however big
+    // a percentage it is, since the quickest days' times are mostly noise
+    pub noise_ms: f64,
+}
+
+// A day's time against its time in the baseline, if it's in the baseline
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    pub day: u32,
+    pub baseline_ms: Option<f64>,
+    pub time_ms: f64,
+}
+
+impl Comparison {
+    // How much slower the day got, as a percentage of the baseline. It's
+    // negative if it got faster
+    pub fn change(&self) -> Option<f64> {
+        self.baseline_ms.filter(|&b| b > 0.0).map(|b| (self.time_ms - b) / b * 100.0)
+    }
+
+    pub fn regressed(&self, tolerance: Tolerance) -> bool {
+        match self.baseline_ms {
+            Some(b) => self.time_ms - b > tolerance.noise_ms
+                && self.time_ms > b * (1.0 + tolerance.percent / 100.0),
+            None => false,
+        }
+    }
+}
+
+// Compare each day in [timings] with the same day in [baseline]. Days which
+// are only in the baseline weren't run so are left out
+pub fn compare(baseline: &Timings, timings: &Timings) -> Vec<Comparison> {
+    timings.days.iter()
+        .map(|(&day, &time_ms)| Comparison {
+            day,
+            baseline_ms: baseline.days.get(&day).copied(),
+            time_ms,
+        })
+        .collect()
+}
+
+// A table of each day's time against the baseline's, marking the days which
+// regressed and the ones which aren't in the baseline yet
+pub fn report(comparisons: &[Comparison], tolerance: Tolerance) -> String {
+    let rows: Vec<_> = comparisons.iter().map(|c| vec![
+        c.day.to_string(),
+        c.baseline_ms.map_or_else(|| "-".to_string(), |b| format!("{:.2}ms", b)),
+        format!("{:.2}ms", c.time_ms),
+        c.change().map_or_else(|| "-".to_string(), |change| format!("{:+.1}%", change)),
+        if c.regressed(tolerance) {
+            "REGRESSED".to_string()
+        }
+        else if c.baseline_ms.is_none() {
+            "new".to_string()
+        }
+        else {
+            String::new()
+        },
+    ]).collect();
+    table::render(&["Day", "Baseline", "Now", "Change", "Status"], &rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOLERANCE: Tolerance = Tolerance { percent: 10.0, noise_ms: 1.0 };
+
+    fn timings(days: &[(u32, f64)]) -> Timings {
+        Timings { days: days.iter().copied().collect() }
+    }
+
+    #[test]
+    fn regressions() {
+        let baseline = timings(&[(1, 100.0), (2, 100.0), (3, 0.1), (5, 100.0)]);
+        let now = timings(&[(1, 109.0), (2, 111.0), (3, 0.5), (4, 1.0), (5, 50.0)]);
+        let regressed: Vec<_> = compare(&baseline, &now).iter()
+            .map(|c| (c.day, c.regressed(TOLERANCE)))
+            .collect();
+        // Day 3 is five times slower but only by 0.4ms, and day 4 is new
+        assert_eq!(regressed, vec![(1, false), (2, true), (3, false), (4, false), (5, false)]);
+    }
+
+    #[test]
+    fn changes() {
+        let comparisons = compare(&timings(&[(1, 200.0)]), &timings(&[(1, 150.0), (2, 1.0)]));
+        assert_eq!(comparisons[0].change(), Some(-25.0));
+        assert_eq!(comparisons[1].change(), None);
+    }
+
+    #[test]
+    fn median_time() {
+        let ms = Duration::from_millis;
+        assert_eq!(median(&mut [ms(5), ms(1), ms(100)]), ms(5));
+        assert_eq!(median(&mut [ms(3)]), ms(3));
+    }
+
+    #[test]
+    fn timings_round_trip_through_json() {
+        let saved = timings(&[(1, 5.96), (11, 17.25)]);
+        let json = serde_json::to_string_pretty(&saved).unwrap();
+        assert_eq!(serde_json::from_str::<Timings>(&json).unwrap(), saved);
+    }
+}
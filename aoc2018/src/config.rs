@@ -0,0 +1,122 @@
+// Settings which would otherwise have to be given on every run. They're read
+// from ~/.config/aoc2018.toml, then from aoc2018.toml in the current directory,
+// with settings in the second overriding the first, e.g.
+//
+// session = "53616c7465645f5f..."
+// inputs_dir = "inputs"
+// format = "json"
+// palette = "colourblind"
+//
+// [days.5]
+// skip = true
+// input = "/somewhere/else/day5.txt"
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use common::viz::ColourScheme;
+use serde::Deserialize;
+
+use crate::output::Format;
+use crate::Error;
+
+pub const LOCAL_CONFIG_PATH: &str = "aoc2018.toml";
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DayConfig {
+    // Leave the day out of `all` and `report`, e.g. because it's slow
+    pub skip: Option<bool>,
+    // Where the day's input is, instead of searching for it
+    pub input: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    session: Option<String>,
+    inputs_dir: Option<PathBuf>,
+    format: Option<Format>,
+    // Parsed afterwards, so that a bad name gets the list of good ones
+    palette: Option<String>,
+    // TOML keys are always strings, so the day numbers are parsed afterwards
+    #[serde(default)]
+    days: HashMap<String, DayConfig>,
+}
+
+#[derive(Debug, Default)]
+pub struct Config {
+    // The adventofcode.com session cookie. The AOC_SESSION environment
+    // variable takes precedence
+    pub session: Option<String>,
+    // Where to look for, and download, inputs named like day19.txt. The
+    // AOC_INPUTS environment variable takes precedence
+    pub inputs_dir: Option<PathBuf>,
+    // How to print results when --format isn't given
+    pub format: Option<Format>,
+    // The colours to watch simulations in when --palette isn't given
+    pub palette: Option<ColourScheme>,
+    pub days: HashMap<u32, DayConfig>,
+}
+
+impl Config {
+    // The user's config overridden by the local one. Neither has to exist
+    pub fn load() -> Result<Config, Error> {
+        let mut config = Config::default();
+        if let Some(home) = dirs::home_dir() {
+            config.merge(Config::load_file(&home.join(".config/aoc2018.toml"))?);
+        }
+        config.merge(Config::load_file(Path::new(LOCAL_CONFIG_PATH))?);
+        Ok(config)
+    }
+
+    fn load_file(path: &Path) -> Result<Config, Error> {
+        if !path.exists() {
+            return Ok(Config::default());
+        }
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let file: ConfigFile = toml::from_str(&contents)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+
+        let mut days = HashMap::new();
+        for (name, day_config) in file.days {
+            let day = name.parse().map_err(|_| {
+                format!("Unexpected table [days.{}] in {}, expected e.g. [days.5]",
+                        name, path.display())
+            })?;
+            days.insert(day, day_config);
+        }
+        let palette = file.palette.map(|name| ColourScheme::parse(&name)).transpose()
+            .map_err(|e| format!("Bad palette in {}: {}", path.display(), e))?;
+        Ok(Config {
+            session: file.session,
+            inputs_dir: file.inputs_dir,
+            format: file.format,
+            palette,
+            days,
+        })
+    }
+
+    // Replace any settings which are also in [other]
+    fn merge(&mut self, other: Config) {
+        self.session = other.session.or(self.session.take());
+        self.inputs_dir = other.inputs_dir.or(self.inputs_dir.take());
+        self.format = other.format.or(self.format);
+        self.palette = other.palette.or(self.palette);
+        for (day, day_config) in other.days {
+            let current = self.days.entry(day).or_default();
+            current.skip = day_config.skip.or(current.skip);
+            current.input = day_config.input.or(current.input.take());
+        }
+    }
+
+    pub fn skip(&self, day: u32) -> bool {
+        self.days.get(&day).and_then(|d| d.skip).unwrap_or(false)
+    }
+
+    pub fn input(&self, day: u32) -> Option<&Path> {
+        self.days.get(&day).and_then(|d| d.input.as_deref())
+    }
+}
@@ -0,0 +1,155 @@
+// The registry of every day which has a solution
+
+use common::Solver;
+use common::simulation::SimulationBuilder;
+use common::viz::animation::AnimationExport;
+use common::viz::dot::GraphExport;
+use common::viz::heatmap::HeatmapExport;
+use common::viz::svg::Drawing;
+
+pub struct Day {
+    pub number: u32,
+    pub part1: Solver,
+    // The last day only has one puzzle
+    pub part2: Option<Solver>,
+    // For days which play out step by step and can be watched
+    pub simulation: Option<SimulationBuilder>,
+    // For days with something worth drawing, like day 10's message
+    pub drawing: Option<Drawing>,
+    // For days with a graph in them, like day 7's steps
+    pub graph: Option<GraphExport>,
+    // For days with a grid of numbers worth seeing, like day 22's erosion
+    // levels
+    pub heatmap: Option<HeatmapExport>,
+    // For days with an animation, like day 15's battle
+    pub animation: Option<AnimationExport>,
+}
+
+impl Day {
+    const fn new(number: u32, part1: Solver, part2: Solver) -> Day {
+        Day { part2: Some(part2), ..Day::part1_only(number, part1) }
+    }
+
+    // A day with only one puzzle, like the last day
+    const fn part1_only(number: u32, part1: Solver) -> Day {
+        Day {
+            number,
+            part1,
+            part2: None,
+            simulation: None,
+            drawing: None,
+            graph: None,
+            heatmap: None,
+            animation: None,
+        }
+    }
+
+    const fn watchable(self, simulation: SimulationBuilder) -> Day {
+        Day { simulation: Some(simulation), ..self }
+    }
+
+    const fn drawable(self, drawing: Drawing) -> Day {
+        Day { drawing: Some(drawing), ..self }
+    }
+
+    const fn graphable(self, graph: GraphExport) -> Day {
+        Day { graph: Some(graph), ..self }
+    }
+
+    const fn heatmapped(self, heatmap: HeatmapExport) -> Day {
+        Day { heatmap: Some(heatmap), ..self }
+    }
+
+    const fn animated(self, animation: AnimationExport) -> Day {
+        Day { animation: Some(animation), ..self }
+    }
+
+    pub fn solver(&self, part: u32) -> Option<Solver> {
+        match part {
+            1 => Some(self.part1),
+            2 => self.part2,
+            _ => None,
+        }
+    }
+
+    // The parts which have a solver, in order
+    pub fn parts(&self) -> Vec<u32> {
+        (1..=2).filter(|&p| self.solver(p).is_some()).collect()
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    Day::new(1, day01::part1, day01::part2),
+    Day::new(2, day02::part1, day02::part2),
+    Day::new(3, day03::part1, day03::part2).drawable(day03::draw),
+    Day::new(4, day04::part1, day04::part2),
+    Day::new(5, day05::part1, day05::part2),
+    Day::new(6, day06::part1, day06::part2),
+    Day::new(7, day07::part1, day07::part2).graphable(day07::graph),
+    Day::new(8, day08::part1, day08::part2).graphable(day08::graph),
+    Day::new(9, day09::part1, day09::part2),
+    Day::new(10, day10::part1, day10::part2)
+        .watchable(day10::simulation).drawable(day10::draw),
+    Day::new(11, day11::part1, day11::part2),
+    Day::new(12, day12::part1, day12::part2),
+    Day::new(13, day13::part1, day13::part2).watchable(day13::simulation),
+    Day::new(14, day14::part1, day14::part2),
+    Day::new(15, day15::part1, day15::part2)
+        .watchable(day15::simulation).animated(day15::animation),
+    Day::new(16, day16::part1, day16::part2),
+    Day::new(17, day17::part1, day17::part2).watchable(day17::simulation),
+    Day::new(18, day18::part1, day18::part2)
+        .watchable(day18::simulation).drawable(day18::draw),
+    Day::new(19, day19::part1, day19::part2).drawable(day19::draw),
+    Day::new(20, day20::part1, day20::part2)
+        .drawable(day20::draw).graphable(day20::graph),
+    Day::new(21, day21::part1, day21::part2).drawable(day21::draw),
+    Day::new(22, day22::part1, day22::part2)
+        .watchable(day22::simulation).drawable(day22::draw).heatmapped(day22::heatmap),
+    Day::new(23, day23::part1, day23::part2),
+    Day::new(24, day24::part1, day24::part2),
+    Day::part1_only(25, day25::part1),
+];
+
+pub fn find(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
+
+// A comma separated list of the implemented days, for error messages
+pub fn implemented() -> String {
+    let numbers: Vec<_> = DAYS.iter().map(|d| d.number.to_string()).collect();
+    numbers.join(", ")
+}
+
+// The same for the days which can be watched
+pub fn watchable() -> String {
+    listed(|d| d.simulation.is_some())
+}
+
+// And for the days which can be drawn
+pub fn drawable() -> String {
+    listed(|d| d.drawing.is_some())
+}
+
+// And for the days with a graph
+pub fn graphable() -> String {
+    listed(|d| d.graph.is_some())
+}
+
+// And for the days with a heatmap
+pub fn heatmapped() -> String {
+    listed(|d| d.heatmap.is_some())
+}
+
+// And for the days with an animation
+pub fn animated() -> String {
+    listed(|d| d.animation.is_some())
+}
+
+fn listed(include: impl Fn(&Day) -> bool) -> String {
+    let numbers: Vec<_> = DAYS.iter()
+        .filter(|d| include(d))
+        .map(|d| d.number.to_string())
+        .collect();
+    numbers.join(", ")
+}
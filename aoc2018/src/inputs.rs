@@ -0,0 +1,58 @@
+// Finding each day's puzzle input. Inputs can be kept together in an inputs/
+// directory, e.g. inputs/day19.txt, or in each day's own directory as
+// input.txt
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+use common::fixtures::{self, INPUTS_DIR, INPUTS_VAR};
+
+use crate::config::Config;
+use crate::Error;
+
+// The AOC_INPUTS environment variable takes precedence over the config, as it
+// does for the benchmarks
+fn inputs_dir(config: &Config) -> PathBuf {
+    env::var_os(INPUTS_VAR).map(PathBuf::from)
+        .or_else(|| config.inputs_dir.clone())
+        .unwrap_or_else(|| PathBuf::from(INPUTS_DIR))
+}
+
+// The places [day]'s input might be, in the order they're tried. An input
+// given in the config is the only candidate
+pub fn candidates(config: &Config, day: u32) -> Vec<PathBuf> {
+    if let Some(path) = config.input(day) {
+        return vec![path.to_path_buf()];
+    }
+    fixtures::input_candidates(Path::new(""), &inputs_dir(config), day).to_vec()
+}
+
+pub fn find(config: &Config, day: u32) -> Result<PathBuf, Error> {
+    let candidates = candidates(config, day);
+    if let Some(path) = candidates.iter().find(|p| p.is_file()) {
+        return Ok(path.clone());
+    }
+    let tried: Vec<_> = candidates.iter().map(|p| p.display().to_string()).collect();
+    Err(format!("Couldn't find the input for day {}, tried {}",
+                day, tried.join(", ")).into())
+}
+
+// Read [day]'s input from [path] if one is given, where - means stdin.
+// Otherwise search for the input
+pub fn read(config: &Config, day: u32, path: Option<&str>) -> Result<String, Error> {
+    match path {
+        Some(path) => Ok(common::read_input_from(Some(path), path)?),
+        None => Ok(common::read_file(&find(config, day)?.to_string_lossy())?),
+    }
+}
+
+// Where a downloaded input should be saved. If there's an inputs directory
+// then all the inputs are kept there
+pub fn save_path(config: &Config, day: u32) -> PathBuf {
+    if let Some(path) = config.input(day) {
+        return path.to_path_buf();
+    }
+    let dir = inputs_dir(config);
+    let [in_inputs_dir, in_day_dir] = fixtures::input_candidates(Path::new(""), &dir, day);
+    if dir.is_dir() { in_inputs_dir } else { in_day_dir }
+}
@@ -0,0 +1,781 @@
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::process;
+
+use clap::{ArgAction, Args, Parser, Subcommand};
+use common::viz::ColourScheme;
+use common::viz::heatmap::Shading;
+use log::{warn, LevelFilter};
+
+
+mod answers;
+mod baseline;
+mod config;
+mod aoc;
+mod days;
+mod inputs;
+mod memory;
+mod output;
+mod report;
+mod runner;
+mod scaffold;
+mod submissions;
+mod table;
+mod watch;
+
+use config::Config;
+use output::Format;
+use runner::{DayResult, FailureKind};
+
+// Errors from the runner itself, e.g. a bad config file or a failed download.
+// The solvers' errors are AocErrors, which are kept with the results
+type Error = Box<dyn std::error::Error>;
+
+// Exit codes, so that scripts can tell what went wrong. clap uses 2 for bad
+// arguments. If several things went wrong the code is for the first of them
+// in the order panic, missing input, error, mismatch
+const EXIT_ERROR: i32 = 1;
+const EXIT_MISMATCH: i32 = 3;
+const EXIT_PANIC: i32 = 4;
+const EXIT_INPUT_MISSING: i32 = 5;
+
+// Options for checking the answers against the known correct ones
+#[derive(Args)]
+struct CheckArgs {
+    /// Check the answers against the known correct ones, failing if any
+    /// don't match
+    #[arg(long)]
+    check: bool,
+    /// The file of known correct answers to check against
+    #[arg(long, default_value = "answers.toml")]
+    answers: String,
+}
+
+// Run the solutions for any day from one place, rather than going into each
+// day's directory
+#[derive(Parser)]
+#[command(name = "aoc2018", about = "Advent of Code 2018 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// How to print the results. Defaults to the format in the config file,
+    /// or text
+    #[arg(short, long, value_enum, global = true)]
+    format: Option<Format>,
+    /// Show what the solvers are doing. -v shows e.g. each round of day 15's
+    /// battles, -vv shows everything including each instruction day 19 runs.
+    /// RUST_LOG works too
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+    /// Only print the answers, one per line, for use in scripts. Failed parts
+    /// get an empty line. Whether anything went wrong is in the exit code
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+    /// Count how many times each part allocates and the most memory it has
+    /// allocated at once. Needs the runner built with `--features mem-stats`
+    #[arg(long, global = true)]
+    mem_stats: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    // Doc comments are what clap shows as help text
+    /// Solve one day's puzzle
+    Run {
+        /// Which day to run
+        #[arg(short, long)]
+        day: u32,
+        /// Which part to run. Both parts are run if this isn't given
+        #[arg(short, long)]
+        part: Option<u32>,
+        /// The puzzle input, or - for stdin. Defaults to inputs/dayNN.txt or
+        /// the input.txt in the day's directory
+        #[arg(short, long)]
+        input: Option<String>,
+        #[command(flatten)]
+        check: CheckArgs,
+    },
+    /// Watch a day which plays out step by step, like day 15's battle, in
+    /// the terminal. Space pauses, the left and right arrows step back and
+    /// forward, g goes to a step, + and - change the speed, shift and the
+    /// arrow keys scroll and q quits
+    Watch {
+        /// Which day to watch
+        #[arg(short, long)]
+        day: u32,
+        /// The puzzle input, as for run
+        #[arg(short, long)]
+        input: Option<String>,
+        /// How many frames to draw each second. Each day has its own default
+        #[arg(long)]
+        fps: Option<u32>,
+        /// Only draw every Nth step, for simulations with lots of small steps
+        /// like day 17's water
+        #[arg(long, value_name = "N")]
+        skip: Option<usize>,
+        /// Take this many steps before drawing anything, e.g. to get to where
+        /// day 18's lumber collection area repeats
+        #[arg(long, value_name = "STEP", default_value_t = 0)]
+        start_at: usize,
+        /// Slow down this many times, 10 if not given, around eventful steps
+        /// like the first crash on day 13
+        #[arg(long, value_name = "FACTOR", num_args = 0..=1, default_missing_value = "10")]
+        slow_motion: Option<u32>,
+        /// What to draw the simulation with. The emoji need a terminal which
+        /// can show them, and twice as many columns
+        #[arg(long, value_enum, conflicts_with_all = ["gif", "frames"])]
+        theme: Option<watch::Theme>,
+        /// The colours to draw the simulation in, one of default,
+        /// high-contrast, colourblind or monochrome. Also applies to --gif,
+        /// --frames and --cast
+        #[arg(long, value_name = "NAME", value_parser = ColourScheme::parse)]
+        palette: Option<ColourScheme>,
+        /// Record the simulation to this animated GIF instead of showing it
+        #[arg(long, conflicts_with_all = ["frames", "cast", "text"])]
+        gif: Option<String>,
+        /// Write each frame of the simulation to this directory as a numbered
+        /// PNG instead of showing it
+        #[arg(long, value_name = "DIR", conflicts_with_all = ["cast", "text"])]
+        frames: Option<String>,
+        /// Record what watching the simulation would show in the terminal to
+        /// this asciinema cast instead of showing it
+        #[arg(long, conflicts_with = "text")]
+        cast: Option<String>,
+        /// Write each frame of the simulation to this directory as a numbered
+        /// text file, without colours or escape codes, instead of showing it
+        #[arg(long, value_name = "DIR")]
+        text: Option<String>,
+        /// The most frames to record with --gif, --frames, --cast or --text
+        #[arg(long, default_value_t = 1000)]
+        max_frames: usize,
+    },
+    /// Draw a picture of a day's puzzle as an SVG, like day 10's message or
+    /// the route through day 22's cave
+    Draw {
+        /// Which day to draw
+        #[arg(short, long)]
+        day: u32,
+        /// The puzzle input, as for run
+        #[arg(short, long)]
+        input: Option<String>,
+        /// Where to write the SVG. Defaults to dayNN.svg
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Write a day's graph in Graphviz's DOT language, like day 7's steps or
+    /// day 20's rooms, e.g. `aoc2018 graph -d 7 | dot -Tsvg > day07.svg`
+    Graph {
+        /// Which day's graph to write
+        #[arg(short, long)]
+        day: u32,
+        /// The puzzle input, as for run
+        #[arg(short, long)]
+        input: Option<String>,
+        /// Where to write the graph. It's printed if this isn't given
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Write a heatmap of a day's numbers as a PNG, like the erosion levels
+    /// across day 22's cave
+    Heatmap {
+        /// Which day's heatmap to write
+        #[arg(short, long)]
+        day: u32,
+        /// The puzzle input, as for run
+        #[arg(short, long)]
+        input: Option<String>,
+        /// Where to write the PNG. Defaults to dayNN-heatmap.png
+        #[arg(short, long)]
+        output: Option<String>,
+        /// Shade from black through red and yellow to white, rather than in
+        /// greys
+        #[arg(long)]
+        heat: bool,
+    },
+    /// Write an animation of a day as a GIF, like day 15's battle with each
+    /// unit shaded by its hit points
+    Animate {
+        /// Which day to animate
+        #[arg(short, long)]
+        day: u32,
+        /// The puzzle input, as for run
+        #[arg(short, long)]
+        input: Option<String>,
+        /// Where to write the GIF. Defaults to dayNN.gif
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Run every day against its input and print a summary of the answers
+    All {
+        #[command(flatten)]
+        check: CheckArgs,
+    },
+    /// Run some days and write a Markdown and/or CSV report of the results.
+    /// The Markdown is printed if neither file is given
+    Report {
+        /// Which days to include, e.g. 1,3,5-7. Defaults to every day
+        #[arg(short, long, value_parser = report::parse_days)]
+        days: Option<report::DayList>,
+        /// Where to write a Markdown report
+        #[arg(long)]
+        markdown: Option<String>,
+        /// Where to write a CSV report
+        #[arg(long)]
+        csv: Option<String>,
+        /// Where to write a web page with the answers and pictures of the
+        /// days which can be drawn
+        #[arg(long)]
+        html: Option<String>,
+    },
+    /// Time some days and compare them with a baseline of earlier timings,
+    /// failing if any has got slower than the threshold allows. Use --save to
+    /// record the baseline first, e.g. before a refactor
+    Bench {
+        /// The file of timings to compare against, or to save to
+        #[arg(long, default_value = "baseline.json")]
+        baseline: String,
+        /// Save the timings as the new baseline instead of comparing them
+        #[arg(long)]
+        save: bool,
+        /// Which days to time, e.g. 1,3,5-7. Defaults to every day
+        #[arg(short, long, value_parser = report::parse_days)]
+        days: Option<report::DayList>,
+        /// How many times to solve each day. The median time is the one used
+        #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..))]
+        runs: u32,
+        /// How much slower than the baseline, as a percentage, a day can get
+        /// before it's a regression
+        #[arg(long, value_name = "PERCENT", default_value_t = 10.0)]
+        threshold: f64,
+        /// Days which get slower by less than this many milliseconds are
+        /// never regressions, since the quickest days' times are mostly noise
+        #[arg(long, value_name = "MS", default_value_t = 1.0)]
+        noise: f64,
+    },
+    /// Download a day's puzzle input from adventofcode.com, using the session
+    /// cookie in the AOC_SESSION environment variable or the config file
+    Fetch {
+        /// Which day's input to download
+        #[arg(short, long)]
+        day: u32,
+        /// Where to write the input. Defaults to inputs/dayNN.txt if there's
+        /// an inputs directory, otherwise the input.txt in the day's directory
+        #[arg(short, long)]
+        output: Option<String>,
+        /// Overwrite the input if it's already been downloaded
+        #[arg(long)]
+        force: bool,
+    },
+    /// Create the crate for a new day from a template, and add it to the
+    /// workspace and the runner
+    New {
+        /// Which day to create
+        day: u32,
+    },
+    /// Solve one part of a day's puzzle and submit the answer to
+    /// adventofcode.com. Every submission is recorded in submissions.toml
+    Submit {
+        /// Which day to submit an answer for
+        #[arg(short, long)]
+        day: u32,
+        /// Which part to submit an answer for
+        #[arg(short, long)]
+        part: u32,
+        /// The puzzle input, as for run
+        #[arg(short, long)]
+        input: Option<String>,
+        /// Submit the answer even if it's been submitted before
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+fn run(config: &Config, day_number: u32, part: Option<u32>, input_path: Option<String>)
+        -> Result<Vec<DayResult>, Error> {
+    let day = days::find(day_number).ok_or_else(|| {
+        format!("Day {} isn't implemented. The implemented days are: {}",
+                day_number, days::implemented())
+    })?;
+
+    let parts = match part {
+        Some(p) => vec![p],
+        None => day.parts(),
+    };
+    for &p in &parts {
+        if day.solver(p).is_none() {
+            return Err(format!("Day {} doesn't have a part {}",
+                               day_number, p).into());
+        }
+    }
+
+    let result = match inputs::read(config, day_number, input_path.as_deref()) {
+        Ok(input) => runner::solve(day, &parts, &input),
+        Err(e) => runner::failed(day, &parts, e.to_string()),
+    };
+    Ok(vec![result])
+}
+
+// The input for [day_number] from wherever it's been saved
+fn day_input(config: &Config, day_number: u32) -> Result<String, Error> {
+    Ok(common::read_file(&inputs::find(config, day_number)?.to_string_lossy())?)
+}
+
+// Run each day against its input
+fn solve_days<'a>(config: &Config, days: impl Iterator<Item = &'a days::Day>)
+        -> Vec<DayResult> {
+    let mut results = vec![];
+    for day in days {
+        let result = match day_input(config, day.number) {
+            Ok(input) => runner::solve(day, &day.parts(), &input),
+            Err(e) => runner::failed(day, &day.parts(), e.to_string()),
+        };
+        results.push(result);
+    }
+    results
+}
+
+fn report(config: &Config, days: Option<report::DayList>, markdown_path: Option<String>,
+          csv_path: Option<String>, html_path: Option<String>) -> Result<Vec<DayResult>, Error> {
+    // Days skipped in the config are only included if they're asked for
+    let days = days.map_or_else(|| report::ALL_DAYS.filter(|&d| !config.skip(d)).collect(),
+                                |d| d.0);
+    let results = solve_days(config, days.iter().filter_map(|&d| days::find(d)));
+
+    if markdown_path.is_none() && csv_path.is_none() && html_path.is_none() {
+        print!("{}", report::markdown(&days, &results));
+    }
+    if let Some(path) = markdown_path {
+        fs::write(&path, report::markdown(&days, &results))
+            .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    }
+    if let Some(path) = csv_path {
+        fs::write(&path, report::csv(&days, &results))
+            .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    }
+    if let Some(path) = html_path {
+        let pictures: Vec<_> = days.iter().flat_map(|&d| pictures(config, d)).collect();
+        fs::write(&path, report::html(&days, &results, &pictures))
+            .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    }
+
+    Ok(results)
+}
+
+// Solve each of [days] [runs] times and compare their median times with the
+// baseline, or save them as the baseline. Only days which solve without any
+// errors are timed, as a day which fails could well fail quickly
+fn bench(config: &Config, days: Option<report::DayList>, runs: u32, baseline_path: &str,
+         save: bool, tolerance: baseline::Tolerance, quiet: bool)
+        -> Result<Vec<DayResult>, Error> {
+    let days = days.map_or_else(|| report::ALL_DAYS.filter(|&d| !config.skip(d)).collect(),
+                                |d| d.0);
+    // Read the baseline first so that a missing one doesn't waste a run
+    let baseline = if save { None } else { Some(baseline::Timings::load(baseline_path)?) };
+
+    let mut results = vec![];
+    let mut timings = baseline::Timings::default();
+    for day in days.iter().filter_map(|&d| days::find(d)) {
+        let input = match day_input(config, day.number) {
+            Ok(input) => input,
+            Err(e) => {
+                results.push(runner::failed(day, &day.parts(), e.to_string()));
+                continue;
+            },
+        };
+        let mut times = vec![];
+        let result = loop {
+            let result = runner::solve(day, &day.parts(), &input);
+            times.push(result.time);
+            if times.len() as u32 == runs || result.parts.iter().any(|p| p.answer.is_err()) {
+                break result;
+            }
+        };
+        if result.parts.iter().all(|p| p.answer.is_ok()) {
+            let time = baseline::median(&mut times);
+            // Microseconds are plenty precise enough
+            let ms = (time.as_secs_f64() * 1_000_000.0).round() / 1000.0;
+            timings.days.insert(day.number, ms);
+        }
+        results.push(result);
+    }
+
+    let baseline = match baseline {
+        Some(baseline) => baseline,
+        None => {
+            timings.save(baseline_path)?;
+            if !quiet {
+                let count = timings.days.len();
+                println!("Saved the times of {} day{} to {}", count,
+                         if count == 1 { "" } else { "s" }, baseline_path);
+            }
+            return Ok(results);
+        },
+    };
+    let comparisons = baseline::compare(&baseline, &timings);
+    if !quiet {
+        println!("{}", baseline::report(&comparisons, tolerance));
+    }
+    let regressed: Vec<_> = comparisons.iter().filter(|c| c.regressed(tolerance))
+        .map(|c| c.day.to_string())
+        .collect();
+    if !regressed.is_empty() {
+        return Err(format!("{} got more than {}% slower than in {}: day {}",
+                           if regressed.len() == 1 { "A day" } else { "Some days" },
+                           tolerance.percent, baseline_path, regressed.join(", ")).into());
+    }
+    Ok(results)
+}
+
+// Whatever pictures can be made of [day_number] for the HTML report: its
+// drawing and its heatmap. A picture which can't be made is left out of the
+// report rather than stopping it
+fn pictures(config: &Config, day_number: u32) -> Vec<(u32, report::Picture)> {
+    let day = match days::find(day_number) {
+        Some(day) if day.drawing.is_some() || day.heatmap.is_some() => day,
+        _ => return vec![],
+    };
+    let input = match day_input(config, day_number) {
+        Ok(input) => input,
+        Err(e) => {
+            warn!("Not drawing day {}: {}", day_number, e);
+            return vec![];
+        },
+    };
+
+    let mut pictures = vec![];
+    if let Some(drawing) = day.drawing {
+        match drawing(&input) {
+            Ok(svg) => pictures.push((day_number, report::Picture::Svg(svg.to_string()))),
+            Err(e) => warn!("Failed to draw day {}: {}", day_number, e),
+        }
+    }
+    if let Some(heatmap) = day.heatmap {
+        let mut png = vec![];
+        match heatmap(&input).map_err(Error::from)
+                .and_then(|h| h.write_png(&mut png, Shading::Heat)) {
+            Ok(()) => pictures.push((day_number, report::Picture::Png(png))),
+            Err(e) => warn!("Failed to make day {}'s heatmap: {}", day_number, e),
+        }
+    }
+    pictures
+}
+
+// Check there's a puzzle for [day]
+fn check_day(day: u32) -> Result<(), Error> {
+    if !report::ALL_DAYS.contains(&day) {
+        return Err(format!("There's no day {}, the puzzles are for days 1 to 25",
+                           day).into());
+    }
+    Ok(())
+}
+
+fn fetch(config: &Config, day: u32, output: Option<String>, force: bool,
+         quiet: bool) -> Result<(), Error> {
+    check_day(day)?;
+    let path = output.map_or_else(|| inputs::save_path(config, day), PathBuf::from);
+    // Inputs never change so there's no need to download them again
+    if !force && path.exists() {
+        return Err(format!("{} already exists, use --force to overwrite it",
+                           path.display()).into());
+    }
+
+    let input = aoc::fetch_input(day, &aoc::session(config)?)?;
+    fs::write(&path, input)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    if !quiet {
+        println!("Saved the input for day {} to {}", day, path.display());
+    }
+    Ok(())
+}
+
+fn draw(config: &Config, day_number: u32, input_path: Option<String>,
+        output: Option<String>, quiet: bool) -> Result<(), Error> {
+    let drawing = days::find(day_number).and_then(|d| d.drawing).ok_or_else(|| {
+        format!("Day {} can't be drawn. The days which can be are: {}",
+                day_number, days::drawable())
+    })?;
+    let input = inputs::read(config, day_number, input_path.as_deref())?;
+    let svg = drawing(&input)?;
+
+    let path = output.unwrap_or_else(|| format!("day{:02}.svg", day_number));
+    fs::write(&path, svg.to_string())
+        .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    if !quiet {
+        println!("Drew day {} in {}", day_number, path);
+    }
+    Ok(())
+}
+
+fn heatmap(config: &Config, day_number: u32, input_path: Option<String>,
+           output: Option<String>, shading: Shading, quiet: bool) -> Result<(), Error> {
+    let heatmap = days::find(day_number).and_then(|d| d.heatmap).ok_or_else(|| {
+        format!("Day {} doesn't have a heatmap. The days which do are: {}",
+                day_number, days::heatmapped())
+    })?;
+    let input = inputs::read(config, day_number, input_path.as_deref())?;
+    let heatmap = heatmap(&input)?;
+
+    let path = output.unwrap_or_else(|| format!("day{:02}-heatmap.png", day_number));
+    let file = File::create(&path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+    heatmap.write_png(BufWriter::new(file), shading)
+        .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    if !quiet {
+        println!("Wrote day {}'s heatmap to {}", day_number, path);
+    }
+    Ok(())
+}
+
+fn animate(config: &Config, day_number: u32, input_path: Option<String>,
+           output: Option<String>, quiet: bool) -> Result<(), Error> {
+    let animation = days::find(day_number).and_then(|d| d.animation).ok_or_else(|| {
+        format!("Day {} doesn't have an animation. The days which do are: {}",
+                day_number, days::animated())
+    })?;
+    let input = inputs::read(config, day_number, input_path.as_deref())?;
+    let animation = animation(&input)?;
+
+    let path = output.unwrap_or_else(|| format!("day{:02}.gif", day_number));
+    let file = File::create(&path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+    animation.write_gif(BufWriter::new(file))
+        .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    if !quiet {
+        println!("Wrote {} frames of day {} to {}", animation.len(), day_number, path);
+    }
+    Ok(())
+}
+
+fn graph(config: &Config, day_number: u32, input_path: Option<String>,
+         output: Option<String>) -> Result<(), Error> {
+    let graph = days::find(day_number).and_then(|d| d.graph).ok_or_else(|| {
+        format!("Day {} doesn't have a graph. The days which do are: {}",
+                day_number, days::graphable())
+    })?;
+    let input = inputs::read(config, day_number, input_path.as_deref())?;
+    let dot = graph(&input)?;
+
+    match output {
+        Some(path) => {
+            fs::write(&path, dot.to_string())
+                .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+        },
+        None => print!("{}", dot),
+    }
+    Ok(())
+}
+
+fn submit(config: &Config, day: u32, part: u32, input_path: Option<String>,
+          force: bool, quiet: bool) -> Result<(), Error> {
+    let results = run(config, day, Some(part), input_path)?;
+    let answer = results[0].parts[0].answer.clone()
+        .map_err(|e| format!("Day {} part {} failed: {}", day, part, e))?;
+    // Day 10's answer is a message drawn in the sky. If its letters can't be
+    // read automatically then it has to be read and typed in by hand
+    if answer.contains('\n') {
+        return Err(format!("The answer for day {} part {} needs reading:\n{}",
+                           day, part, answer).into());
+    }
+
+    let path = submissions::SUBMISSIONS_PATH;
+    let previous = submissions::load(path)?;
+    if let Some(verdict) = submissions::previous_verdict(&previous, day, part, &answer) {
+        if !force {
+            return Err(format!("{} has already been submitted for day {} part {} \
+                                and was {}, use --force to submit it again",
+                               answer, day, part, verdict).into());
+        }
+    }
+
+    let (verdict, message) = aoc::submit_answer(day, part, &answer, &aoc::session(config)?)?;
+    submissions::record(path, day, part, &answer, verdict)?;
+    if quiet {
+        println!("{}", verdict);
+    }
+    else {
+        println!("Day {} part {}: {} is {}", day, part, answer, verdict);
+        println!("{}", message);
+    }
+    if verdict != aoc::Verdict::Correct {
+        return Err(format!("The answer wasn't accepted, it was {}", verdict).into());
+    }
+    Ok(())
+}
+
+fn new_day(day: u32, quiet: bool) -> Result<(), Error> {
+    check_day(day)?;
+    scaffold::new_day(day)?;
+    if !quiet {
+        println!("Created {:02}/ for day {}. Next, get the input with \
+                  `aoc2018 fetch --day {}`", day, day, day);
+    }
+    Ok(())
+}
+
+// Print [results], checking them against the known answers first if asked to.
+// Normally the text output is laid out by [text]
+fn print_results(mut results: Vec<DayResult>, format: Format, check: &CheckArgs,
+                 quiet: bool, text: fn(&[DayResult]) -> String)
+        -> Result<Vec<DayResult>, Error> {
+    if check.check {
+        answers::Answers::load(&check.answers)?.annotate(&mut results);
+    }
+    let text = if quiet {
+        output::bare_answers
+    }
+    else if check.check {
+        output::check_lines
+    }
+    else {
+        text
+    };
+    println!("{}", output::render(&results, format, check.check, text)?);
+    // Structured output has the memory use alongside each answer instead
+    if format == Format::Text && !quiet && results.iter().flat_map(|r| &r.parts)
+            .any(|p| p.memory.is_some()) {
+        println!("\n{}", output::memory_table(&results));
+    }
+    Ok(results)
+}
+
+fn main_inner(cli: Cli) -> Result<Vec<DayResult>, Error> {
+    let config = Config::load()?;
+    let format = cli.format.or(config.format).unwrap_or(Format::Text);
+    if cli.mem_stats {
+        if !memory::available() {
+            return Err("--mem-stats needs the runner built with the counting allocator, \
+                        e.g. `cargo run --release -p aoc2018 --features mem-stats`".into());
+        }
+        memory::report();
+    }
+
+    let results = match cli.command {
+        Command::Run { day, part, input, check } => {
+            let results = run(&config, day, part, input)?;
+            print_results(results, format, &check, cli.quiet, output::answer_lines)?
+        },
+        Command::Watch { day, input, fps, skip, start_at, slow_motion, theme, palette, gif,
+                         frames, cast, text, max_frames } => {
+            let recording = match (gif, frames, cast, text) {
+                (Some(path), _, _, _) => Some((path, watch::Format::Gif)),
+                (None, Some(dir), _, _) => Some((dir, watch::Format::Pngs)),
+                (None, None, Some(path), _) => Some((path, watch::Format::Cast)),
+                (None, None, None, Some(dir)) => Some((dir, watch::Format::Text)),
+                (None, None, None, None) => None,
+            }.map(|(path, format)| watch::Recording { path, format, max_frames });
+            let options = watch::Options {
+                fps,
+                skip,
+                start_at,
+                slow_motion,
+                theme: theme.unwrap_or_default(),
+                palette: palette.or(config.palette).unwrap_or_default(),
+            };
+            watch::watch(&config, day, input.as_deref(), options, recording, cli.quiet)?;
+            vec![]
+        },
+        Command::Draw { day, input, output } => {
+            draw(&config, day, input, output, cli.quiet)?;
+            vec![]
+        },
+        Command::Graph { day, input, output } => {
+            graph(&config, day, input, output)?;
+            vec![]
+        },
+        Command::Heatmap { day, input, output, heat } => {
+            let shading = if heat { Shading::Heat } else { Shading::Grey };
+            heatmap(&config, day, input, output, shading, cli.quiet)?;
+            vec![]
+        },
+        Command::Animate { day, input, output } => {
+            animate(&config, day, input, output, cli.quiet)?;
+            vec![]
+        },
+        Command::All { check } => {
+            let days = days::DAYS.iter().filter(|d| !config.skip(d.number));
+            let results = solve_days(&config, days);
+            print_results(results, format, &check, cli.quiet, output::summary_table)?
+        },
+        Command::Report { days, markdown, csv, html } => {
+            if cli.format.is_some_and(|f| f != Format::Text) {
+                return Err("--format doesn't apply to reports, use --markdown, \
+                            --csv or --html instead".into());
+            }
+            report(&config, days, markdown, csv, html)?
+        },
+        Command::Bench { baseline, save, days, runs, threshold, noise } => {
+            if cli.format.is_some_and(|f| f != Format::Text) {
+                return Err("--format doesn't apply to bench".into());
+            }
+            let tolerance = baseline::Tolerance { percent: threshold, noise_ms: noise };
+            bench(&config, days, runs, &baseline, save, tolerance, cli.quiet)?
+        },
+        Command::Fetch { day, output, force } => {
+            fetch(&config, day, output, force, cli.quiet)?;
+            vec![]
+        },
+        Command::New { day } => {
+            new_day(day, cli.quiet)?;
+            vec![]
+        },
+        Command::Submit { day, part, input, force } => {
+            submit(&config, day, part, input, force, cli.quiet)?;
+            vec![]
+        },
+    };
+    Ok(results)
+}
+
+// What went wrong with [results], if anything, as an exit code and a message
+fn check_results(results: &[DayResult]) -> Option<(i32, String)> {
+    let panics = runner::failures_of_kind(results, FailureKind::Panic);
+    if panics > 0 {
+        return Some((EXIT_PANIC, format!("{} parts panicked", panics)));
+    }
+    let missing = runner::failures_of_kind(results, FailureKind::Input);
+    if missing > 0 {
+        return Some((EXIT_INPUT_MISSING, format!("{} parts had no input", missing)));
+    }
+    let failures = runner::failures(results);
+    if failures > 0 {
+        return Some((EXIT_ERROR, format!("{} parts failed", failures)));
+    }
+    let mismatches = runner::mismatches(results);
+    if mismatches > 0 {
+        return Some((EXIT_MISMATCH, format!("{} answers didn't match the known \
+                                             answers", mismatches)));
+    }
+    None
+}
+
+// Logs go to stderr so they don't get mixed up with the answers
+fn init_logging(verbose: u8) {
+    let level = match verbose {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    };
+    let mut builder = env_logger::Builder::new();
+    builder.filter_level(level).format_timestamp(None);
+    // RUST_LOG can be used for finer control, e.g. RUST_LOG=day15=debug
+    if verbose == 0 {
+        builder.parse_default_env();
+    }
+    builder.init();
+}
+
+fn main() {
+    let cli = Cli::parse();
+    init_logging(cli.verbose);
+    match main_inner(cli) {
+        Ok(results) => {
+            if let Some((code, message)) = check_results(&results) {
+                eprintln!("Error: {}", message);
+                process::exit(code);
+            }
+        },
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(EXIT_ERROR);
+        },
+    }
+}
@@ -0,0 +1,132 @@
+// Counting what the solvers allocate, so that how much memory different
+// approaches use can be compared, like day 22's dense grids against its A*
+// search. Counting needs a global allocator wrapped around the system's one,
+// which is behind the mem-stats feature so that normal builds don't pay for it
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemStats {
+    // How many times memory was allocated or reallocated
+    pub allocations: u64,
+    // The most bytes allocated at once, beyond what was already allocated
+    // beforehand
+    pub peak_bytes: usize,
+}
+
+// Whether what's allocated is being reported
+static REPORTING: AtomicBool = AtomicBool::new(false);
+
+#[cfg(feature = "mem-stats")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+    pub static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+    // The bytes allocated right now, and the most there have been since the
+    // peak was last reset
+    pub static CURRENT: AtomicUsize = AtomicUsize::new(0);
+    pub static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+    pub struct Counting;
+
+    impl Counting {
+        fn grow(&self, bytes: usize) {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            let current = CURRENT.fetch_add(bytes, Ordering::Relaxed) + bytes;
+            PEAK.fetch_max(current, Ordering::Relaxed);
+        }
+    }
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                self.grow(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                self.grow(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+                self.grow(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+}
+
+// Whether the runner was built with the counting allocator
+pub fn available() -> bool {
+    cfg!(feature = "mem-stats")
+}
+
+// Report what's allocated from now on. Only does anything if it's available
+pub fn report() {
+    REPORTING.store(available(), Ordering::Relaxed);
+}
+
+// Run [f], giving what it allocated if that's being reported. Anything other
+// threads allocate meanwhile is counted too, which is what's wanted for days
+// like 11 which share their work out across threads
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<MemStats>) {
+    if !REPORTING.load(Ordering::Relaxed) {
+        return (f(), None);
+    }
+    measure_counted(f)
+}
+
+#[cfg(feature = "mem-stats")]
+fn measure_counted<T>(f: impl FnOnce() -> T) -> (T, Option<MemStats>) {
+    use counting::{ALLOCATIONS, CURRENT, PEAK};
+
+    let before = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(before, Ordering::Relaxed);
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let result = f();
+    let stats = MemStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(before),
+    };
+    (result, Some(stats))
+}
+
+#[cfg(not(feature = "mem-stats"))]
+fn measure_counted<T>(f: impl FnOnce() -> T) -> (T, Option<MemStats>) {
+    (f(), None)
+}
+
+// [bytes] in the biggest unit which keeps it at least 1, e.g. 1.5 MiB
+pub fn format_bytes(bytes: usize) -> String {
+    let units = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < units.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    }
+    else {
+        format!("{:.1} {}", size, units[unit])
+    }
+}
@@ -0,0 +1,213 @@
+// Turning results into something to print, either for people to read or as
+// structured data for scripts
+
+use std::time::Duration;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::memory;
+use crate::runner::{self, DayResult, Failure, Status};
+use crate::table;
+use crate::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    Text,
+    Json,
+    Yaml,
+}
+
+#[derive(Serialize)]
+struct PartRecord<'a> {
+    part: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    answer: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+    // These are only included when checking answers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<&'static str>,
+    // These are only included with --mem-stats
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allocations: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    peak_bytes: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct DayRecord<'a> {
+    day: u32,
+    time_ms: f64,
+    parts: Vec<PartRecord<'a>>,
+}
+
+#[derive(Serialize)]
+struct Record<'a> {
+    days: Vec<DayRecord<'a>>,
+    total_time_ms: f64,
+    failures: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mismatches: Option<usize>,
+}
+
+fn to_ms(time: Duration) -> f64 {
+    time.as_secs_f64() * 1000.0
+}
+
+impl<'a> Record<'a> {
+    fn new(results: &'a [DayResult], check: bool) -> Record<'a> {
+        let days = results.iter().map(|r| DayRecord {
+            day: r.day,
+            time_ms: to_ms(r.time),
+            parts: r.parts.iter().map(|p| PartRecord {
+                part: p.part,
+                answer: p.answer.as_ref().ok().map(|a| a.as_str()),
+                error: p.answer.as_ref().err().map(|e| e.message.as_str()),
+                expected: p.expected.as_deref(),
+                status: if check { Some(p.status().name()) } else { None },
+                allocations: p.memory.map(|m| m.allocations),
+                peak_bytes: p.memory.map(|m| m.peak_bytes),
+            }).collect(),
+        }).collect();
+
+        Record {
+            days,
+            total_time_ms: to_ms(results.iter().map(|r| r.time).sum()),
+            failures: runner::failures(results),
+            mismatches: if check { Some(runner::mismatches(results)) } else { None },
+        }
+    }
+}
+
+// Render [results] in [format]. Text is laid out by [text], since what's most
+// readable depends on how many results there are and whether we're checking
+// the answers
+pub fn render(results: &[DayResult], format: Format, check: bool,
+              text: fn(&[DayResult]) -> String) -> Result<String, Error> {
+    let record = || Record::new(results, check);
+    match format {
+        Format::Text => Ok(text(results)),
+        Format::Json => Ok(serde_json::to_string_pretty(&record())?),
+        // serde_yaml ends the document with a newline, but so will println!
+        Format::Yaml => Ok(serde_yaml::to_string(&record())?.trim_end().to_string()),
+    }
+}
+
+// One line per answer
+pub fn answer_lines(results: &[DayResult]) -> String {
+    let mut lines = vec![];
+    for r in results {
+        for p in &r.parts {
+            match &p.answer {
+                // Some answers, like the message in the stars on day 10, span
+                // several lines so start them on a line of their own
+                Ok(a) if a.contains('\n') => {
+                    lines.push(format!("Day {} part {}:\n{}", r.day, p.part, a));
+                },
+                Ok(a) => lines.push(format!("Day {} part {}: {}", r.day, p.part, a)),
+                Err(e) => {
+                    lines.push(format!("Day {} part {}: ERROR: {}", r.day, p.part, e));
+                },
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+// Just the answers, one per line, with an empty line for any part which failed
+pub fn bare_answers(results: &[DayResult]) -> String {
+    let answers: Vec<_> = results.iter().flat_map(|r| &r.parts)
+        .map(|p| p.answer.as_deref().unwrap_or(""))
+        .collect();
+    answers.join("\n")
+}
+
+// How an answer is shown in a table cell
+fn table_cell(answer: Option<&Result<String, Failure>>) -> String {
+    match answer {
+        // Multi-line answers don't fit in the table so they're shown after it
+        Some(Ok(a)) if a.contains('\n') => "(see below)".to_string(),
+        Some(Ok(a)) => a.clone(),
+        Some(Err(e)) => format!("ERROR: {}", e),
+        None => "-".to_string(),
+    }
+}
+
+// A table with a row for each day
+pub fn summary_table(results: &[DayResult]) -> String {
+    let rows: Vec<_> = results.iter().map(|r| vec![
+        r.day.to_string(),
+        table_cell(r.answer(1)),
+        table_cell(r.answer(2)),
+        format!("{:.2?}", r.time),
+    ]).collect();
+    let mut summary = table::render(&["Day", "Part 1", "Part 2", "Time"], &rows);
+
+    let total_time: Duration = results.iter().map(|r| r.time).sum();
+    summary += &format!("\n\nTotal time: {:.2?}", total_time);
+
+    for r in results {
+        for p in &r.parts {
+            if let Ok(answer) = &p.answer {
+                if answer.contains('\n') {
+                    summary += &format!("\n\nDay {} part {}:\n{}", r.day, p.part, answer);
+                }
+            }
+        }
+    }
+    summary
+}
+
+// A table of what each part allocated, for the parts where that was counted
+pub fn memory_table(results: &[DayResult]) -> String {
+    let rows: Vec<_> = results.iter()
+        .flat_map(|r| r.parts.iter().map(move |p| (r.day, p)))
+        .filter_map(|(day, p)| p.memory.map(|m| vec![
+            day.to_string(),
+            p.part.to_string(),
+            m.allocations.to_string(),
+            memory::format_bytes(m.peak_bytes),
+        ]))
+        .collect();
+    table::render(&["Day", "Part", "Allocations", "Peak memory"], &rows)
+}
+
+// A PASS/FAIL line for each answer, followed by a summary
+pub fn check_lines(results: &[DayResult]) -> String {
+    let mut lines = vec![];
+    let mut counts = vec![];
+    for status in &[Status::Pass, Status::Fail, Status::Unchecked, Status::Error] {
+        let count = results.iter().flat_map(|r| &r.parts)
+            .filter(|p| p.status() == *status).count();
+        counts.push(format!("{} {}", count, status.name()));
+    }
+
+    for r in results {
+        for p in &r.parts {
+            let status = p.status();
+            let prefix = format!("{:9} Day {} part {}", status.name(), r.day, p.part);
+            let line = match (&p.answer, &p.expected) {
+                (Err(e), _) => format!("{}: {}", prefix, e),
+                (Ok(a), Some(e)) if status == Status::Fail => {
+                    if a.contains('\n') || e.contains('\n') {
+                        format!("{}: got\n{}\nbut expected\n{}", prefix, a, e)
+                    }
+                    else {
+                        format!("{}: got {} but expected {}", prefix, a, e)
+                    }
+                },
+                (Ok(a), _) if a.contains('\n') => format!("{}:\n{}", prefix, a),
+                (Ok(a), _) => format!("{}: {}", prefix, a),
+            };
+            lines.push(line);
+        }
+    }
+
+    lines.push(String::new());
+    lines.push(counts.join(", "));
+    lines.join("\n")
+}
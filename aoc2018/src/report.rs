@@ -0,0 +1,247 @@
+// Results reports for keeping a log of the answers, as Markdown, CSV or a web
+// page
+
+use std::time::Duration;
+
+use crate::runner::DayResult;
+
+// Advent of Code has a puzzle every day from the 1st to the 25th
+pub const ALL_DAYS: std::ops::RangeInclusive<u32> = 1..=25;
+
+// A list of days given on the command line
+#[derive(Debug, Clone)]
+pub struct DayList(pub Vec<u32>);
+
+// Parse a list of days like "1,3,5-7"
+pub fn parse_days(s: &str) -> Result<DayList, String> {
+    let mut days = vec![];
+    for item in s.split(',').map(|i| i.trim()) {
+        let parse = |n: &str| n.trim().parse::<u32>()
+            .map_err(|e| format!("Bad day '{}': {}", n, e));
+        match item.find('-') {
+            Some(i) => days.extend(parse(&item[..i])?..=parse(&item[i + 1..])?),
+            None => days.push(parse(item)?),
+        }
+    }
+    Ok(DayList(days))
+}
+
+fn find(results: &[DayResult], day: u32) -> Option<&DayResult> {
+    results.iter().find(|r| r.day == day)
+}
+
+fn answer_text(result: &DayResult, part: u32) -> String {
+    match result.answer(part) {
+        Some(Ok(a)) => a.clone(),
+        Some(Err(e)) => format!("ERROR: {}", e),
+        None => "-".to_string(),
+    }
+}
+
+fn total_time(results: &[DayResult]) -> Duration {
+    results.iter().map(|r| r.time).sum()
+}
+
+fn solved_count(results: &[DayResult]) -> usize {
+    results.iter().flat_map(|r| &r.parts).filter(|p| p.answer.is_ok()).count()
+}
+
+// A Markdown table with a row for each of [days]. Days without a solution are
+// listed as unimplemented. Answers which span several lines, like day 10's,
+// can't go in a table cell so they follow the table in code blocks
+pub fn markdown(days: &[u32], results: &[DayResult]) -> String {
+    let mut lines = vec![
+        "| Day | Part 1 | Part 2 | Time |".to_string(),
+        "| --: | ------ | ------ | ---: |".to_string(),
+    ];
+    let mut multi_line_answers = vec![];
+    let mut unimplemented = vec![];
+
+    for &day in days {
+        match find(results, day) {
+            Some(r) => {
+                let mut cells = vec![];
+                for part in 1..=2 {
+                    let text = answer_text(r, part);
+                    if text.contains('\n') {
+                        cells.push("(see below)".to_string());
+                        multi_line_answers.push((day, part, text));
+                    }
+                    else {
+                        cells.push(text.replace('|', "\\|"));
+                    }
+                }
+                lines.push(format!("| {} | {} | {} | {:.2?} |",
+                                   day, cells[0], cells[1], r.time));
+            },
+            None => {
+                lines.push(format!("| {} | _not implemented_ | | |", day));
+                unimplemented.push(day.to_string());
+            },
+        }
+    }
+    lines.push(format!("| **Total** | {} parts solved | | {:.2?} |",
+                       solved_count(results), total_time(results)));
+
+    if !unimplemented.is_empty() {
+        lines.push(String::new());
+        lines.push(format!("Unimplemented days: {}", unimplemented.join(", ")));
+    }
+
+    for (day, part, answer) in multi_line_answers {
+        lines.push(String::new());
+        lines.push(format!("Day {} part {}:", day, part));
+        lines.push(String::new());
+        lines.push(format!("```\n{}\n```", answer));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+// Quote a CSV field if it needs it, see RFC 4180
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    }
+    else {
+        s.to_string()
+    }
+}
+
+// The same information as the Markdown report, with one record per day and a
+// final record for the totals. Times are in milliseconds
+pub fn csv(days: &[u32], results: &[DayResult]) -> String {
+    let mut lines = vec!["day,part1,part2,time_ms".to_string()];
+    for &day in days {
+        match find(results, day) {
+            Some(r) => {
+                lines.push(format!("{},{},{},{:.3}", day,
+                                   csv_field(&answer_text(r, 1)),
+                                   csv_field(&answer_text(r, 2)),
+                                   r.time.as_secs_f64() * 1000.0));
+            },
+            None => lines.push(format!("{},not implemented,,", day)),
+        }
+    }
+    lines.push(format!("total,{} parts solved,,{:.3}", solved_count(results),
+                       total_time(results).as_secs_f64() * 1000.0));
+    lines.join("\n") + "\n"
+}
+
+// A picture of a day's puzzle to go in the HTML report
+#[derive(Debug, Clone)]
+pub enum Picture {
+    Svg(String),
+    Png(Vec<u8>),
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+// Standard base64 with padding, for embedding PNGs as data: URLs
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, &b)| n | u32::from(b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(char::from(ALPHABET[((n >> (18 - 6 * i)) & 63) as usize]));
+            }
+            else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+const STYLE: &str = "body { font-family: sans-serif; max-width: 60em; margin: 2em auto; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.8em; text-align: left; vertical-align: top; }
+td.number { text-align: right; }
+td.error { color: #c00; }
+pre { margin: 0; }
+figure svg, figure img { max-width: 100%; height: auto; }";
+
+fn html_answer(result: &DayResult, part: u32) -> String {
+    let text = html_escape(&answer_text(result, part));
+    match result.answer(part) {
+        Some(Err(_)) => format!(r#"<td class="error">{}</td>"#, text),
+        _ if text.contains('\n') => format!("<td><pre>{}</pre></td>", text),
+        _ => format!("<td>{}</td>", text),
+    }
+}
+
+// A web page with the same table as the Markdown report, followed by
+// [pictures] of the days. Everything is in the one file, with SVGs inline and
+// PNGs as data: URLs, so that it can be put anywhere
+pub fn html(days: &[u32], results: &[DayResult], pictures: &[(u32, Picture)]) -> String {
+    let mut lines = vec![
+        "<!DOCTYPE html>".to_string(),
+        r#"<html lang="en">"#.to_string(),
+        "<head>".to_string(),
+        r#"<meta charset="utf-8">"#.to_string(),
+        "<title>Advent of Code 2018</title>".to_string(),
+        format!("<style>\n{}\n</style>", STYLE),
+        "</head>".to_string(),
+        "<body>".to_string(),
+        "<h1>Advent of Code 2018</h1>".to_string(),
+        "<table>".to_string(),
+        "<tr><th>Day</th><th>Part 1</th><th>Part 2</th><th>Time</th></tr>".to_string(),
+    ];
+    let mut unimplemented = vec![];
+
+    for &day in days {
+        match find(results, day) {
+            Some(r) => {
+                lines.push(format!(r#"<tr><td class="number">{}</td>{}{}<td class="number">{:.2?}</td></tr>"#,
+                                   day, html_answer(r, 1), html_answer(r, 2), r.time));
+            },
+            None => {
+                lines.push(format!(r#"<tr><td class="number">{}</td><td colspan="3"><em>not implemented</em></td></tr>"#,
+                                   day));
+                unimplemented.push(day.to_string());
+            },
+        }
+    }
+    lines.push(format!(r#"<tr><th>Total</th><th colspan="2">{} parts solved</th><th class="number">{:.2?}</th></tr>"#,
+                       solved_count(results), total_time(results)));
+    lines.push("</table>".to_string());
+
+    if !unimplemented.is_empty() {
+        lines.push(format!("<p>Unimplemented days: {}</p>", unimplemented.join(", ")));
+    }
+
+    for (day, picture) in pictures {
+        lines.push(format!("<h2>Day {}</h2>", day));
+        lines.push("<figure>".to_string());
+        match picture {
+            Picture::Svg(svg) => lines.push(svg.trim_end().to_string()),
+            Picture::Png(png) => {
+                lines.push(format!(r#"<img alt="Day {}" src="data:image/png;base64,{}">"#,
+                                   day, base64(png)));
+            },
+        }
+        lines.push("</figure>".to_string());
+    }
+
+    lines.push("</body>".to_string());
+    lines.push("</html>".to_string());
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_padding() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar\xff"), "Zm9vYmFy/w==");
+    }
+}
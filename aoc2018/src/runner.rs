@@ -0,0 +1,155 @@
+// Running the solvers for a day and keeping track of what happened
+
+use std::fmt;
+use std::panic;
+use std::time::{Duration, Instant};
+
+use log::debug;
+
+use crate::days::Day;
+use crate::memory::{self, MemStats};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureKind {
+    // The solver returned an error
+    Error,
+    // The solver panicked
+    Panic,
+    // We couldn't get the input to give to the solver
+    Input,
+}
+
+// Why a part didn't produce an answer. The error is kept as a string so that
+// one failing part doesn't stop the rest of the results being shown
+#[derive(Debug, Clone)]
+pub struct Failure {
+    pub kind: FailureKind,
+    pub message: String,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+pub struct PartResult {
+    pub part: u32,
+    pub answer: Result<String, Failure>,
+    // The known correct answer, when checking answers
+    pub expected: Option<String>,
+    // What the part allocated, when that's being reported
+    pub memory: Option<MemStats>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    // There's no known answer to check against
+    Unchecked,
+    Error,
+}
+
+impl Status {
+    pub fn name(self) -> &'static str {
+        match self {
+            Status::Pass => "PASS",
+            Status::Fail => "FAIL",
+            Status::Unchecked => "UNCHECKED",
+            Status::Error => "ERROR",
+        }
+    }
+}
+
+impl PartResult {
+    pub fn status(&self) -> Status {
+        match (&self.answer, &self.expected) {
+            (Err(_), _) => Status::Error,
+            (Ok(_), None) => Status::Unchecked,
+            (Ok(a), Some(e)) if a == e => Status::Pass,
+            (Ok(_), Some(_)) => Status::Fail,
+        }
+    }
+}
+
+pub struct DayResult {
+    pub day: u32,
+    pub parts: Vec<PartResult>,
+    // Wall-clock time for solving all the parts, not including reading the
+    // input
+    pub time: Duration,
+}
+
+impl DayResult {
+    pub fn answer(&self, part: u32) -> Option<&Result<String, Failure>> {
+        self.parts.iter().find(|p| p.part == part).map(|p| &p.answer)
+    }
+}
+
+pub fn solve(day: &Day, parts: &[u32], input: &str) -> DayResult {
+    let start = Instant::now();
+    let mut results = vec![];
+    for &part in parts {
+        let (answer, memory) = match day.solver(part) {
+            Some(solver) => memory::measure(|| run_solver(solver, input)),
+            None => (Err(Failure {
+                kind: FailureKind::Error,
+                message: format!("Day {} doesn't have a part {}", day.number, part),
+            }), None),
+        };
+        results.push(PartResult { part, answer, expected: None, memory });
+    }
+    DayResult { day: day.number, parts: results, time: start.elapsed() }
+}
+
+// Run [solver], catching any panic so that the other parts still get run
+fn run_solver(solver: common::Solver, input: &str) -> Result<String, Failure> {
+    // The panic is reported with the results, so the default hook printing it
+    // as well would just be noise. Where it happened is logged for debugging
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|info| debug!("Solver {}", info)));
+    let result = panic::catch_unwind(|| solver(input));
+    panic::set_hook(default_hook);
+
+    match result {
+        Ok(Ok(answer)) => Ok(answer),
+        Ok(Err(e)) => Err(Failure { kind: FailureKind::Error, message: e.to_string() }),
+        Err(payload) => {
+            // Panics almost always have a message, as either a &str or String
+            let message = payload.downcast_ref::<&str>().map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            Err(Failure { kind: FailureKind::Panic, message: format!("Panicked: {}", message) })
+        },
+    }
+}
+
+// The result for a day where we couldn't even get as far as running the
+// solvers, because the input is missing or couldn't be read
+pub fn failed(day: &Day, parts: &[u32], error: String) -> DayResult {
+    let failure = Failure { kind: FailureKind::Input, message: error };
+    let parts = parts.iter()
+        .map(|&part| PartResult { part, answer: Err(failure.clone()), expected: None,
+                                  memory: None })
+        .collect();
+    DayResult { day: day.number, parts, time: Duration::default() }
+}
+
+// How many parts, across all the days, didn't produce an answer
+pub fn failures(results: &[DayResult]) -> usize {
+    results.iter().flat_map(|r| &r.parts).filter(|p| p.answer.is_err()).count()
+}
+
+// How many parts, across all the days, failed in the way given
+pub fn failures_of_kind(results: &[DayResult], kind: FailureKind) -> usize {
+    results.iter().flat_map(|r| &r.parts)
+        .filter(|p| matches!(&p.answer, Err(f) if f.kind == kind))
+        .count()
+}
+
+// How many parts, across all the days, got a different answer to the known
+// correct one
+pub fn mismatches(results: &[DayResult]) -> usize {
+    results.iter().flat_map(|r| &r.parts).filter(|p| p.status() == Status::Fail).count()
+}
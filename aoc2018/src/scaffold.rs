@@ -0,0 +1,163 @@
+// Creating the crate for a new day from a template and registering it with
+// the workspace and the runner. Everything is relative to the top of the
+// repository
+
+use std::fs;
+use std::path::Path;
+
+use crate::Error;
+
+const WORKSPACE_MANIFEST: &str = "Cargo.toml";
+const RUNNER_MANIFEST: &str = "aoc2018/Cargo.toml";
+const DAYS_RS: &str = "aoc2018/src/days.rs";
+
+// In the templates DAY is replaced by the day's number and NN by the number
+// padded to two digits, e.g. 7 and 07
+const CARGO_TOML: &str = r#"[package]
+name = "dayNN"
+version = "0.1.0"
+authors = ["btabram <btabram@users.noreply.github.com>"]
+edition = "2018"
+
+[dependencies]
+common = { path = "../common" }
+"#;
+
+const LIB_RS: &str = r#"use common::AocError;
+
+pub fn part1(_input: &str) -> Result<String, AocError> {
+    Err(AocError::Unsupported("day DAY part 1 hasn't been solved yet".to_string()))
+}
+
+pub fn part2(_input: &str) -> Result<String, AocError> {
+    Err(AocError::Unsupported("day DAY part 2 hasn't been solved yet".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The example from the puzzle description
+    const EXAMPLE: &str = "";
+
+    #[test]
+    #[ignore = "the example and its answer need filling in"]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), "");
+    }
+
+    #[test]
+    #[ignore = "the example and its answer need filling in"]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), "");
+    }
+}
+"#;
+
+const MAIN_RS: &str = r#"use common::AocError;
+
+fn main() -> Result<(), AocError> {
+    let input = common::read_input()?;
+
+    println!("The answer to part 1 is {}", dayNN::part1(&input)?);
+    println!("The answer to part 2 is {}", dayNN::part2(&input)?);
+
+    Ok(())
+}
+"#;
+
+fn fill_in(template: &str, day: u32) -> String {
+    template.replace("NN", &format!("{:02}", day)).replace("DAY", &day.to_string())
+}
+
+fn read(path: &str) -> Result<String, Error> {
+    Ok(fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?)
+}
+
+fn write(path: &str, contents: &str) -> Result<(), Error> {
+    Ok(fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path, e))?)
+}
+
+// The workspace members are listed with the day crates ten to a line
+fn add_workspace_member(manifest: &str, day: u32) -> Result<String, Error> {
+    let start = manifest.find("members = [")
+        .ok_or_else(|| format!("Didn't find the members list in {}", WORKSPACE_MANIFEST))?;
+    let end = start + manifest[start..].find(']')
+        .ok_or_else(|| format!("The members list in {} isn't closed", WORKSPACE_MANIFEST))?;
+
+    let mut others = vec![];
+    let mut days = vec![format!("{:02}", day)];
+    for member in manifest[start..end].split('"').skip(1).step_by(2) {
+        if member.parse::<u32>().is_ok() {
+            days.push(member.to_string());
+        }
+        else {
+            others.push(member.to_string());
+        }
+    }
+    days.sort();
+
+    let mut lines = vec!["members = [".to_string()];
+    lines.extend(others.iter().map(|m| format!("    \"{}\",", m)));
+    for row in days.chunks(10) {
+        let row: Vec<_> = row.iter().map(|d| format!("\"{}\",", d)).collect();
+        lines.push(format!("    {}", row.join(" ")));
+    }
+    Ok(format!("{}{}\n{}", &manifest[..start], lines.join("\n"), &manifest[end..]))
+}
+
+// Insert [line] into [text] so that the lines for each day stay in order.
+// [day_of] gives the day a line is for, if it's for one
+fn insert_line(text: &str, line: &str, day: u32,
+               day_of: impl Fn(&str) -> Option<u32>) -> Result<String, Error> {
+    let mut lines: Vec<_> = text.lines().collect();
+    let days: Vec<_> = lines.iter().map(|l| day_of(l)).collect();
+    let position = days.iter().position(|d| d.is_some_and(|d| d > day))
+        .or_else(|| days.iter().rposition(|d| d.is_some()).map(|p| p + 1))
+        .ok_or_else(|| format!("Didn't find where to add day {}", day))?;
+    lines.insert(position, line);
+    Ok(lines.join("\n") + "\n")
+}
+
+fn add_runner_dependency(manifest: &str, day: u32) -> Result<String, Error> {
+    let line = format!("day{:02} = {{ path = \"../{:02}\" }}", day, day);
+    insert_line(manifest, &line, day, |l| l.strip_prefix("day")?.get(..2)?.parse().ok())
+}
+
+fn register_day(days_rs: &str, day: u32) -> Result<String, Error> {
+    let line = format!("    Day::new({}, day{:02}::part1, day{:02}::part2),", day, day, day);
+    insert_line(days_rs, &line, day, |l| {
+        let l = l.trim();
+        let number = l.strip_prefix("Day::new(").or_else(|| l.strip_prefix("Day::part1_only("))?;
+        number.split(|c: char| !c.is_ascii_digit()).next()?.parse().ok()
+    })
+}
+
+pub fn new_day(day: u32) -> Result<(), Error> {
+    let dir = format!("{:02}", day);
+    if Path::new(&dir).exists() {
+        return Err(format!("{} already exists", dir).into());
+    }
+    if !Path::new(DAYS_RS).exists() {
+        return Err("Run this from the top of the repository".into());
+    }
+
+    // Work out all the changes before making any, so that a failure doesn't
+    // leave things half done
+    let workspace = add_workspace_member(&read(WORKSPACE_MANIFEST)?, day)?;
+    let runner = add_runner_dependency(&read(RUNNER_MANIFEST)?, day)?;
+    let days_rs = register_day(&read(DAYS_RS)?, day)?;
+
+    fs::create_dir_all(format!("{}/src", dir))
+        .map_err(|e| format!("Failed to create {}: {}", dir, e))?;
+    write(&format!("{}/Cargo.toml", dir), &fill_in(CARGO_TOML, day))?;
+    write(&format!("{}/src/lib.rs", dir), &fill_in(LIB_RS, day))?;
+    write(&format!("{}/src/main.rs", dir), &fill_in(MAIN_RS, day))?;
+    // A placeholder until the real input is fetched
+    write(&format!("{}/input.txt", dir), "")?;
+
+    write(WORKSPACE_MANIFEST, &workspace)?;
+    write(RUNNER_MANIFEST, &runner)?;
+    write(DAYS_RS, &days_rs)?;
+    Ok(())
+}
@@ -0,0 +1,66 @@
+// A log of every answer submitted to adventofcode.com and what the site said,
+// so that we don't submit the same wrong answer twice
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::aoc::Verdict;
+use crate::Error;
+
+pub const SUBMISSIONS_PATH: &str = "submissions.toml";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Submission {
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub verdict: Verdict,
+    // Seconds since the Unix epoch
+    pub time: u64,
+}
+
+// The log is an array of tables, one per submission, so new submissions can be
+// appended without rewriting the file
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Log {
+    #[serde(default)]
+    submission: Vec<Submission>,
+}
+
+pub fn load(path: &str) -> Result<Vec<Submission>, Error> {
+    if !Path::new(path).exists() {
+        return Ok(vec![]);
+    }
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let log: Log = toml::from_str(&contents)
+        .map_err(|e| format!("Failed to parse {}: {}", path, e))?;
+    Ok(log.submission)
+}
+
+// The verdict the last time [answer] was submitted for [part] of [day], not
+// counting times when we weren't allowed to answer
+pub fn previous_verdict(submissions: &[Submission], day: u32, part: u32,
+                        answer: &str) -> Option<Verdict> {
+    submissions.iter().rev()
+        .filter(|s| s.day == day && s.part == part && s.answer == answer)
+        .map(|s| s.verdict)
+        .find(|&v| v != Verdict::RateLimited && v != Verdict::Unknown)
+}
+
+pub fn record(path: &str, day: u32, part: u32, answer: &str, verdict: Verdict)
+        -> Result<(), Error> {
+    let time = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let submission = Submission { day, part, answer: answer.to_string(), verdict, time };
+    let entry = toml::to_string(&Log { submission: vec![submission] })?;
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    writeln!(file, "{}", entry)
+        .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    Ok(())
+}
@@ -0,0 +1,27 @@
+// Plain text tables for printing results in the terminal
+
+// Lay out [rows] in columns under [headers], with each column as wide as its
+// widest cell
+pub fn render(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<_> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |cells: Vec<&str>| {
+        let padded: Vec<_> = cells.iter().zip(&widths)
+            .map(|(cell, &width)| format!("{:width$}", cell, width = width))
+            .collect();
+        padded.join(" | ").trim_end().to_string()
+    };
+
+    let mut lines = vec![format_row(headers.to_vec())];
+    let dividers: Vec<_> = widths.iter().map(|&w| "-".repeat(w)).collect();
+    lines.push(dividers.join("-+-"));
+    for row in rows {
+        lines.push(format_row(row.iter().map(|c| c.as_str()).collect()));
+    }
+    lines.join("\n")
+}
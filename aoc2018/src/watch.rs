@@ -0,0 +1,169 @@
+// Watching the days which play out step by step, in the terminal
+
+use std::fs::File;
+use std::io::{self, BufWriter, IsTerminal};
+use std::path::Path;
+
+use clap::ValueEnum;
+use common::viz::{Color, ColourScheme, Glyphs, Palette, Viewer};
+
+use crate::Error;
+use crate::config::Config;
+use crate::days;
+use crate::inputs;
+
+// The colours and speed which suit each day, with colours for the characters
+// of each theme
+fn viewer(day: u32) -> Viewer {
+    match day {
+        10 => Viewer::new().fps(15).palette(Palette::new()
+            .with("#█", Color::Yellow)),
+        13 => Viewer::new().fps(20).palette(Palette::new()
+            .with("<>^v◀▶▲▼", Color::Red)
+            .with("X", Color::Yellow)
+            .with("*", Color::Magenta)),
+        15 => Viewer::new().fps(5).palette(Palette::new()
+            .with("#█", Color::DarkGrey)
+            .with("E", Color::Green)
+            .with("G", Color::Red)),
+        17 => Viewer::new().fps(20).palette(Palette::new()
+            .with("#▓", Color::DarkYellow)
+            .with("~|≈┊", Color::Blue)
+            .with("+", Color::Cyan)),
+        18 => Viewer::new().fps(20).palette(Palette::new()
+            .with("|♣", Color::Green)
+            .with("#▓", Color::DarkYellow)
+            .with("▁▂▃▄▅▆▇█", Color::Cyan)),
+        22 => Viewer::new().fps(30).palette(Palette::new()
+            .with("=≈", Color::Blue)
+            .with("|░", Color::DarkGrey)
+            .with("*•@", Color::Yellow)
+            .with("MT", Color::Red)),
+        _ => Viewer::new(),
+    }
+}
+
+// What the days' frames are drawn with in the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Theme {
+    // The characters the puzzles use, which is what's wanted when the frames
+    // are copied or compared with the puzzle
+    #[default]
+    Ascii,
+    // Box drawing characters and blocks
+    Unicode,
+    // Pictures, which are twice as wide as other characters so they need a
+    // wider terminal
+    Emoji,
+}
+
+// The glyphs for each day in [theme]. Days without any are drawn in ASCII
+fn glyphs(day: u32, theme: Theme) -> Glyphs {
+    match (theme, day) {
+        (Theme::Unicode, 10) => Glyphs::new().with('#', "█"),
+        (Theme::Unicode, 13) => Glyphs::new()
+            .with('-', "─").with('|', "│").with('+', "┼").with('/', "╱").with('\\', "╲")
+            .with('<', "◀").with('>', "▶").with('^', "▲").with('v', "▼"),
+        (Theme::Unicode, 15) => Glyphs::new().with('#', "█").with('.', "·"),
+        (Theme::Unicode, 17) => Glyphs::new()
+            .with('#', "▓").with('.', " ").with('|', "┊").with('~', "≈"),
+        (Theme::Unicode, 18) => Glyphs::new().with('|', "♣").with('#', "▓").with('.', "·"),
+        (Theme::Unicode, 22) => Glyphs::new()
+            .with('.', "·").with('=', "≈").with('|', "░").with('*', "•"),
+        (Theme::Emoji, 10) => Glyphs::wide().with('#', "🌟"),
+        (Theme::Emoji, 15) => Glyphs::wide()
+            .with('#', "🧱").with('.', "  ").with('E', "🧝").with('G', "👺"),
+        (Theme::Emoji, 17) => Glyphs::wide()
+            .with('#', "🟫").with('.', "  ").with('|', "💧").with('~', "🟦").with('+', "🚰"),
+        (Theme::Emoji, 18) => Glyphs::wide().with('|', "🌲").with('#', "🪵").with('.', "  "),
+        // The mouth of the cave isn't given a glyph as there's an M in the
+        // heading, which would then be drawn as though it were part of the
+        // cave
+        (Theme::Emoji, 22) => Glyphs::wide()
+            .with('.', "  ").with('=', "🌊").with('|', "🪨").with('*', "👣").with('@', "🧗")
+            .with('T', "🎯"),
+        _ => Glyphs::new(),
+    }
+}
+
+// How to show a simulation, where it's asked for rather than left to the day
+pub struct Options {
+    pub fps: Option<u32>,
+    pub skip: Option<usize>,
+    pub start_at: usize,
+    pub slow_motion: Option<u32>,
+    pub theme: Theme,
+    pub palette: ColourScheme,
+}
+
+// Where to record a simulation to instead of watching it
+pub struct Recording {
+    pub path: String,
+    pub format: Format,
+    pub max_frames: usize,
+}
+
+pub enum Format {
+    // An animated GIF at [path]
+    Gif,
+    // A PNG for each frame, in the directory [path]
+    Pngs,
+    // An asciinema cast at [path]
+    Cast,
+    // A text file for each frame, in the directory [path]
+    Text,
+}
+
+pub fn watch(config: &Config, day_number: u32, input_path: Option<&str>,
+             options: Options, recording: Option<Recording>, quiet: bool)
+        -> Result<(), Error> {
+    let simulation = days::find(day_number).and_then(|d| d.simulation).ok_or_else(|| {
+        format!("Day {} can't be watched. The days which can be are: {}",
+                day_number, days::watchable())
+    })?;
+    let input = inputs::read(config, day_number, input_path)?;
+    let mut sim = simulation(&input)?;
+
+    let mut viewer = viewer(day_number).glyphs(glyphs(day_number, options.theme))
+        .colour_scheme(options.palette).start_at(options.start_at);
+    if let Some(fps) = options.fps {
+        viewer = viewer.fps(fps);
+    }
+    if let Some(steps) = options.skip {
+        viewer = viewer.steps_per_frame(steps);
+    }
+    if let Some(factor) = options.slow_motion {
+        viewer = viewer.slow_motion(factor);
+    }
+
+    match recording {
+        Some(Recording { path, format, max_frames }) => {
+            let frames = match format {
+                Format::Gif => {
+                    let file = File::create(&path)
+                        .map_err(|e| format!("Failed to create {}: {}", path, e))?;
+                    viewer.record_gif(sim.as_mut(), BufWriter::new(file), max_frames)
+                },
+                Format::Pngs => viewer.record_pngs(sim.as_mut(), Path::new(&path), max_frames),
+                Format::Cast => {
+                    let file = File::create(&path)
+                        .map_err(|e| format!("Failed to create {}: {}", path, e))?;
+                    viewer.record_cast(sim.as_mut(), BufWriter::new(file), max_frames)
+                },
+                Format::Text => viewer.record_text(sim.as_mut(), Path::new(&path), max_frames),
+            }.map_err(|e| format!("Failed to record {}: {}", path, e))?;
+            if !quiet {
+                println!("Recorded {} frames of day {} to {}", frames, day_number, path);
+            }
+        },
+        None => {
+            // Rather than filling a log or a pipe with escape codes
+            if !io::stdout().is_terminal() {
+                return Err("Watching needs a terminal. Without one, record the simulation \
+                            with --gif, --frames, --cast or --text instead".into());
+            }
+            viewer.play(sim.as_mut())?;
+        },
+    }
+    Ok(())
+}
@@ -0,0 +1,36 @@
+// The AOC_INPUTS environment variable picks the inputs directory for the
+// runner as well as the benchmarks, ahead of the inputs_dir in the config
+
+use std::env;
+use std::fs;
+use std::process::Command;
+
+#[test]
+fn inputs_var_overrides_the_config() {
+    let dir = env::temp_dir().join(format!("aoc2018-inputs-{}", std::process::id()));
+    fs::create_dir_all(dir.join("configured")).unwrap();
+    fs::create_dir_all(dir.join("elsewhere")).unwrap();
+    fs::write(dir.join("aoc2018.toml"), "inputs_dir = \"configured\"\n").unwrap();
+    fs::write(dir.join("configured/day01.txt"), "+1\n+1\n-2\n").unwrap();
+    fs::write(dir.join("elsewhere/day01.txt"), "+1\n-2\n+3\n+1\n").unwrap();
+
+    let run = |inputs: Option<&str>| {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_aoc2018"));
+        command.args(["run", "--day", "1", "--part", "1"])
+            .current_dir(&dir)
+            .env("HOME", &dir)
+            .env_remove("AOC_INPUTS");
+        if let Some(inputs) = inputs {
+            command.env("AOC_INPUTS", dir.join(inputs));
+        }
+        let output = command.output().unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+    let configured = run(None);
+    let elsewhere = run(Some("elsewhere"));
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(configured, "Day 1 part 1: 0\n");
+    assert_eq!(elsewhere, "Day 1 part 1: 3\n");
+}
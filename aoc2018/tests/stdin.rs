@@ -0,0 +1,44 @@
+// The runner only reads stdin when it's given `--input -`. Otherwise a stdin
+// which is left open with nothing written to it, as under ssh, CI or an IDE,
+// would keep it waiting forever rather than it using the day's input file
+
+use std::env;
+use std::fs;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn open_stdin_falls_back_to_the_input_file() {
+    let dir = env::temp_dir().join(format!("aoc2018-stdin-{}", std::process::id()));
+    fs::create_dir_all(dir.join("inputs")).unwrap();
+    fs::write(dir.join("inputs/day01.txt"), "+1\n-2\n+3\n+1\n").unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc2018"))
+        .args(["run", "--day", "1"])
+        .current_dir(&dir)
+        .env("HOME", &dir)
+        .env_remove("AOC_INPUTS")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // Hold on to stdin without writing to it or closing it
+    let stdin = child.stdin.take();
+
+    let start = Instant::now();
+    while child.try_wait().unwrap().is_none() {
+        if start.elapsed() > Duration::from_secs(30) {
+            child.kill().unwrap();
+            panic!("The runner waited on stdin instead of reading inputs/day01.txt");
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+    let output = child.wait_with_output().unwrap();
+    drop(stdin);
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(),
+               "Day 1 part 1: 3\nDay 1 part 2: 2\n");
+}
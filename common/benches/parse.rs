@@ -0,0 +1,50 @@
+// How long parsing takes on inputs far bigger than the puzzles', to keep an
+// eye on the parsers staying zero-copy: the fields and numbers they pick out
+// are slices of the input rather than Strings, and there's no Vec for each
+// line. Run with `cargo bench -p common --bench parse`
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use common::elfcode;
+use common::parse;
+
+// An elfcode program of [lines] instructions, like days 19 and 21's inputs
+fn program(lines: usize) -> String {
+    let ops = ["addi", "mulr", "seti", "eqrr", "gtri", "banr"];
+    let mut program = "#ip 3\n".to_string();
+    for i in 0..lines {
+        program += &format!("{} {} {} {}\n", ops[i % ops.len()], i % 6, (i * 7) % 6, i % 5);
+    }
+    program
+}
+
+// Lines like day 3's claims, "#1 @ 1,3: 4x4"
+fn claims(lines: usize) -> String {
+    (0..lines).map(|i| format!("#{} @ {},{}: {}x{}\n", i + 1, i % 997, i % 991, i % 29 + 1,
+                               i % 31 + 1))
+        .collect()
+}
+
+fn parsing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parsing");
+    for &lines in [1_000, 100_000].iter() {
+        let input = program(lines);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("elfcode program", lines), &input,
+                               |b, input| b.iter(|| elfcode::parse_program(input).unwrap()));
+
+        let input = claims(lines);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("fixed_ints", lines), &input, |b, input| {
+            b.iter(|| {
+                for line in input.lines() {
+                    black_box(parse::fixed_ints::<usize, 5>(line).unwrap());
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, parsing);
+criterion_main!(benches);
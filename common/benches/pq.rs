@@ -0,0 +1,90 @@
+// Compares Dijkstra using IndexedHeap's decrease-key with the usual BinaryHeap
+// approach of pushing duplicates and skipping the stale ones. Run with
+// `cargo bench -p common`
+//
+// The graph is a square grid where entering each cell has a cost from 1 to 9,
+// like day 22's cave system where changing tools costs more than moving
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use common::grid::Grid;
+use common::pq::IndexedHeap;
+
+// The same pseudo-random costs every run
+fn costs(size: usize) -> Grid<usize> {
+    let mut state: u64 = 12345;
+    Grid::from_fn(size, size, |_, _| {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (state >> 33) as usize % 9 + 1
+    })
+}
+
+// The cheapest way from the top left to the bottom right corner
+fn indexed_heap(costs: &Grid<usize>) -> usize {
+    let mut best = Grid::new(costs.width(), costs.height(), usize::MAX);
+    let mut heap = IndexedHeap::new();
+    best[(0, 0)] = 0;
+    heap.push(0, 0);
+
+    // Cells are indexed in reading order
+    let width = costs.width();
+    while let Some((i, cost)) = heap.pop() {
+        let (x, y) = (i % width, i / width);
+        if (x, y) == (costs.width() - 1, costs.height() - 1) {
+            return cost;
+        }
+        for next in costs.neighbours(x, y) {
+            let next_cost = cost + costs[next];
+            if next_cost < best[next] {
+                best[next] = next_cost;
+                heap.push(next.0 + next.1 * width, next_cost);
+            }
+        }
+    }
+    unreachable!()
+}
+
+fn binary_heap(costs: &Grid<usize>) -> usize {
+    let mut best = Grid::new(costs.width(), costs.height(), usize::MAX);
+    let mut heap = BinaryHeap::new();
+    best[(0, 0)] = 0;
+    heap.push(Reverse((0, (0, 0))));
+
+    while let Some(Reverse((cost, (x, y)))) = heap.pop() {
+        // A stale entry, there's been a cheaper way here since it was pushed
+        if cost > best[(x, y)] {
+            continue;
+        }
+        if (x, y) == (costs.width() - 1, costs.height() - 1) {
+            return cost;
+        }
+        for next in costs.neighbours(x, y) {
+            let next_cost = cost + costs[next];
+            if next_cost < best[next] {
+                best[next] = next_cost;
+                heap.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+    unreachable!()
+}
+
+fn dijkstra(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dijkstra");
+    group.sample_size(10);
+    for &size in [100, 300].iter() {
+        let costs = costs(size);
+        assert_eq!(indexed_heap(&costs), binary_heap(&costs));
+        group.bench_with_input(BenchmarkId::new("IndexedHeap", size), &costs,
+                               |b, costs| b.iter(|| indexed_heap(costs)));
+        group.bench_with_input(BenchmarkId::new("BinaryHeap", size), &costs,
+                               |b, costs| b.iter(|| binary_heap(costs)));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, dijkstra);
+criterion_main!(benches);
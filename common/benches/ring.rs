@@ -0,0 +1,70 @@
+// Compares Ring, which keeps the current value at the back of a VecDeque and
+// rotates it, with the naive approach of a Vec and an index into it, where
+// every insert and removal moves all the values after it. Run with
+// `cargo bench -p common`
+//
+// Each plays day 9's marble game, where part 2 has 100 times as many marbles
+// as part 1. Ring's time grows in line with the number of marbles and the
+// Vec's with its square, which is why part 2 needs Ring
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use common::ring::Ring;
+
+const PLAYERS: usize = 430;
+
+// The winning score
+fn ring(max_marble: usize) -> usize {
+    let mut marbles = Ring::with_capacity(max_marble);
+    marbles.insert_after_current(0);
+    let mut scores = vec![0; PLAYERS];
+    for m in 1..=max_marble {
+        if m % 23 == 0 {
+            marbles.move_counter_clockwise(7);
+            scores[m % PLAYERS] += m + marbles.remove_current().unwrap();
+        }
+        else {
+            marbles.move_clockwise(1);
+            marbles.insert_after_current(m);
+        }
+    }
+    scores.into_iter().max().unwrap()
+}
+
+fn vec_insert(max_marble: usize) -> usize {
+    let mut marbles = Vec::with_capacity(max_marble);
+    marbles.push(0);
+    let mut current = 0;
+    let mut scores = vec![0; PLAYERS];
+    for m in 1..=max_marble {
+        if m % 23 == 0 {
+            current = (current + marbles.len() - 7 % marbles.len()) % marbles.len();
+            scores[m % PLAYERS] += m + marbles.remove(current);
+            current %= marbles.len();
+        }
+        else {
+            current = (current + 1) % marbles.len() + 1;
+            marbles.insert(current, m);
+        }
+    }
+    scores.into_iter().max().unwrap()
+}
+
+fn marble_game(c: &mut Criterion) {
+    let mut group = c.benchmark_group("marble game");
+    group.sample_size(10);
+    for &max_marble in [10_000, 100_000].iter() {
+        assert_eq!(ring(max_marble), vec_insert(max_marble));
+        group.bench_with_input(BenchmarkId::new("Ring", max_marble), &max_marble,
+                               |b, &max_marble| b.iter(|| ring(max_marble)));
+        group.bench_with_input(BenchmarkId::new("Vec::insert", max_marble), &max_marble,
+                               |b, &max_marble| b.iter(|| vec_insert(max_marble)));
+    }
+    // Too slow to wait for with a Vec
+    group.bench_with_input(BenchmarkId::new("Ring", 1_000_000), &1_000_000,
+                           |b, &max_marble| b.iter(|| ring(max_marble)));
+    group.finish();
+}
+
+criterion_group!(benches, marble_game);
+criterion_main!(benches);
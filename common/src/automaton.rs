@@ -0,0 +1,271 @@
+// Cellular automata, where every cell of a grid changes at once according to
+// a rule which looks at the cell and some of the cells around it, like day
+// 18's lumber collection area or (with a grid one row high) day 12's pots.
+//
+// Automata usually settle into a cycle, so the automaton can be run to a
+// generation billions of steps away by remembering every state it's been in
+// and skipping the whole cycles once a state repeats
+
+use std::hash::Hash;
+
+use crate::AocError;
+use crate::cycle;
+use crate::grid::Grid;
+use crate::hash::HashMap;
+use crate::simulation::{Simulation, StepResult};
+
+// The offsets of the cells a rule looks at, relative to the cell it decides
+pub type Kernel = &'static [(isize, isize)];
+
+// The eight cells around a cell, in reading order
+pub const MOORE: Kernel = &[(-1, -1), (0, -1), (1, -1), (-1, 0),
+                            (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+// The four cells up, left, right and down of a cell
+pub const VON_NEUMANN: Kernel = &[(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+pub struct Automaton<T, F> {
+    cells: Grid<T>,
+    // The next generation is written here, to save allocating a new grid for
+    // each step
+    next: Grid<T>,
+    kernel: Kernel,
+    // What every cell beyond the edges of the grid is
+    outside: T,
+    rule: F,
+    // Whether the grid grows and shrinks to fit the cells which aren't
+    // [outside], in which case [origin] is where the grid's top left corner is
+    growing: bool,
+    origin: (i64, i64),
+    generation: usize,
+}
+
+impl<T, F> Automaton<T, F>
+where
+    T: Clone + Eq + Hash,
+    F: Fn(&T, &[&T]) -> T,
+{
+    // An automaton where each step replaces every cell with [rule] of the cell
+    // and the cells at the offsets in [kernel], in the same order, with cells
+    // beyond the edge of the grid taken to be [outside]
+    pub fn new(cells: Grid<T>, kernel: Kernel, outside: T, rule: F) -> Automaton<T, F> {
+        Automaton {
+            next: cells.clone(),
+            cells,
+            kernel,
+            outside,
+            rule,
+            growing: false,
+            origin: (0, 0),
+            generation: 0,
+        }
+    }
+
+    // Let the cells spread beyond the grid, for an automaton on an endless
+    // plane (or line) of [outside] cells. The grid is trimmed to the cells
+    // which aren't [outside], so that cycles are still found when the pattern
+    // drifts along. [rule] must give [outside] when everything it's looking at
+    // is [outside], or the automaton would fill the plane
+    pub fn growing(mut self) -> Automaton<T, F> {
+        self.growing = true;
+        self.trim();
+        self
+    }
+
+    pub fn cells(&self) -> &Grid<T> {
+        &self.cells
+    }
+
+    // Where the top left corner of the grid is, which only changes if the
+    // automaton is growing
+    pub fn origin(&self) -> (i64, i64) {
+        self.origin
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    // How far the kernel reaches from a cell in x and y
+    fn reach(&self) -> (usize, usize) {
+        let x = self.kernel.iter().map(|&(dx, _)| dx.unsigned_abs()).max().unwrap_or(0);
+        let y = self.kernel.iter().map(|&(_, dy)| dy.unsigned_abs()).max().unwrap_or(0);
+        (x, y)
+    }
+
+    // Surround the grid with enough [outside] cells for the pattern to spread
+    // into during the next step
+    fn pad(&mut self) {
+        let (rx, ry) = self.reach();
+        let (cells, outside) = (&self.cells, &self.outside);
+        self.cells = Grid::from_fn(cells.width() + 2 * rx, cells.height() + 2 * ry, |x, y| {
+            let cell = match (x.checked_sub(rx), y.checked_sub(ry)) {
+                (Some(x), Some(y)) => cells.get(x, y),
+                _ => None,
+            };
+            cell.unwrap_or(outside).clone()
+        });
+        self.origin.0 -= rx as i64;
+        self.origin.1 -= ry as i64;
+    }
+
+    // Shrink the grid to the smallest rectangle holding every cell which
+    // isn't [outside]
+    fn trim(&mut self) {
+        let mut used = self.cells.iter_reading_order()
+            .filter(|(_, cell)| **cell != self.outside)
+            .map(|(p, _)| p);
+        let bounds = used.next().map(|(x, y)| {
+            used.fold(((x, y), (x, y)), |(min, max), (x, y)| {
+                ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y)))
+            })
+        });
+        let (min, max) = match bounds {
+            Some(bounds) => bounds,
+            None => {
+                self.cells = Grid::new(0, 0, self.outside.clone());
+                return;
+            },
+        };
+
+        let cells = &self.cells;
+        self.cells = Grid::from_fn(max.0 - min.0 + 1, max.1 - min.1 + 1, |x, y| {
+            cells[(x + min.0, y + min.1)].clone()
+        });
+        self.origin.0 += min.0 as i64;
+        self.origin.1 += min.1 as i64;
+    }
+
+    // Move on to the next generation. Gives whether any cell changed
+    pub fn step(&mut self) -> bool {
+        if self.growing {
+            self.pad();
+        }
+        if (self.next.width(), self.next.height()) != (self.cells.width(), self.cells.height()) {
+            self.next = self.cells.clone();
+        }
+
+        let Automaton { cells, next, kernel, outside, rule, .. } = self;
+        let mut around = Vec::with_capacity(kernel.len());
+        for y in 0..cells.height() {
+            for x in 0..cells.width() {
+                around.clear();
+                around.extend(kernel.iter().map(|&(dx, dy)| {
+                    let cell = match (x.checked_add_signed(dx), y.checked_add_signed(dy)) {
+                        (Some(nx), Some(ny)) => cells.get(nx, ny),
+                        _ => None,
+                    };
+                    cell.unwrap_or(outside)
+                }));
+                next[(x, y)] = rule(&cells[(x, y)], &around);
+            }
+        }
+        let changed = self.cells != self.next;
+        std::mem::swap(&mut self.cells, &mut self.next);
+
+        if self.growing {
+            self.trim();
+        }
+        self.generation += 1;
+        changed
+    }
+
+    pub fn run(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.step();
+        }
+    }
+
+    // Step until generation [n], or until the cells are the same as in an
+    // earlier generation, in which case the rest of the cycle is skipped over
+    // and the automaton jumps straight to the state it would be in at
+    // generation [n]. A growing automaton's origin moves by the same amount
+    // each time round the cycle. Gives the cycle if one was found, as the
+    // generation it starts at and its length
+    pub fn run_to(&mut self, n: usize) -> Option<(usize, usize)> {
+        let first = self.generation;
+        let mut seen: HashMap<Grid<T>, usize> = HashMap::default();
+        let mut origins = vec![];
+
+        while self.generation < n {
+            let i = self.generation - first;
+            if let Some(&start) = seen.get(&self.cells) {
+                let phase = cycle::index_in_cycle(n - first, (start, i - start));
+                let cycles = ((n - first - phase) / (i - start)) as i64;
+                let (x, y): (i64, i64) = origins[phase];
+                let drift = (self.origin.0 - origins[start].0, self.origin.1 - origins[start].1);
+
+                self.cells = seen.into_iter().find(|&(_, j)| j == phase).unwrap().0;
+                self.origin = (x + cycles * drift.0, y + cycles * drift.1);
+                self.generation = n;
+                return Some((first + start, i - start));
+            }
+            seen.insert(self.cells.clone(), i);
+            origins.push(self.origin);
+            self.step();
+        }
+        None
+    }
+}
+
+// Each step is a generation. Cells are drawn as the character they convert to
+impl<T, F> Simulation for Automaton<T, F>
+where
+    T: Clone + Eq + Hash + Into<char>,
+    F: Fn(&T, &[&T]) -> T,
+{
+    fn step(&mut self) -> Result<StepResult, AocError> {
+        Ok(if Automaton::step(self) { StepResult::Changed } else { StepResult::Unchanged })
+    }
+
+    fn render(&self) -> String {
+        self.cells.display_with(|cell| cell.clone().into()).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Conway's Game of Life
+    fn life(cell: &bool, around: &[&bool]) -> bool {
+        let alive = around.iter().filter(|&&&c| c).count();
+        alive == 3 || (*cell && alive == 2)
+    }
+
+    fn parse(s: &str) -> Grid<bool> {
+        Grid::parse(s, |c| Some(c == '#')).unwrap()
+    }
+
+    #[test]
+    fn blinker() {
+        let mut life = Automaton::new(parse(".....\n..#..\n..#..\n..#..\n....."), MOORE, false, life);
+        life.step();
+        assert_eq!(life.cells(), &parse(".....\n.....\n.###.\n.....\n....."));
+        assert_eq!(life.run_to(1_000_000_001), Some((1, 2)));
+        assert_eq!(life.generation(), 1_000_000_001);
+        assert_eq!(life.cells(), &parse(".....\n.....\n.###.\n.....\n....."));
+    }
+
+    #[test]
+    fn glider() {
+        let mut life = Automaton::new(parse(".#.\n..#\n###"), MOORE, false, life).growing();
+        life.run(4);
+        // Every four generations a glider moves one cell down and to the right
+        assert_eq!(life.cells(), &parse(".#.\n..#\n###"));
+        assert_eq!(life.origin(), (1, 1));
+        assert_eq!(life.run_to(4_000_000_000), Some((4, 4)));
+        assert_eq!(life.cells(), &parse(".#.\n..#\n###"));
+        assert_eq!(life.origin(), (1_000_000_000, 1_000_000_000));
+    }
+
+    #[test]
+    fn dies_out() {
+        let mut life = Automaton::new(parse("#..\n...\n..#"), MOORE, false, life).growing();
+        assert!(life.step());
+        assert!(!life.step());
+        assert_eq!((life.cells().width(), life.cells().height()), (0, 0));
+        life.run_to(100);
+        assert_eq!(life.generation(), 100);
+    }
+}
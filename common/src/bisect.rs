@@ -0,0 +1,81 @@
+// Binary search for the smallest value which is good enough, for when anything
+// bigger is good enough too. Day 24's boosts aren't like that, since a bigger
+// boost can end in a stalemate, so it tries them all in turn
+
+use std::ops::RangeInclusive;
+
+// The smallest value in [range] for which [pred] is true, where [pred] is
+// false for every value up to some point and true from then on. Gives None if
+// [pred] isn't true anywhere in [range].
+//
+// The search gallops up from the start of the range, doubling the step each
+// time, before bisecting. That keeps [pred] away from big values when the
+// answer is small, which matters when [pred] is slower for bigger values, and
+// means [range] can safely be much bigger than needed.
+//
+// If [pred] isn't really monotonic, the value found is still one where [pred]
+// is true and [pred] of the value before it is false, but there may be a
+// smaller value where [pred] is true
+pub fn search_min(range: RangeInclusive<i64>, mut pred: impl FnMut(i64) -> bool) -> Option<i64> {
+    let (start, end) = range.into_inner();
+    if start > end {
+        return None;
+    }
+    if pred(start) {
+        return Some(start);
+    }
+
+    // [low] is always a value where [pred] is false, and [high] one where
+    // it's true
+    let mut low = start;
+    let mut step = 1;
+    let mut high = loop {
+        let next = low.saturating_add(step).min(end);
+        if pred(next) {
+            break next;
+        }
+        if next == end {
+            return None;
+        }
+        low = next;
+        step = step.saturating_mul(2);
+    };
+
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if pred(mid) {
+            high = mid;
+        }
+        else {
+            low = mid;
+        }
+    }
+    Some(high)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_threshold() {
+        for threshold in -5..40 {
+            assert_eq!(search_min(-5..=40, |x| x >= threshold), Some(threshold));
+        }
+        assert_eq!(search_min(0..=10, |x| x > 10), None);
+        assert_eq!(search_min(RangeInclusive::new(3, 2), |_| true), None);
+        assert_eq!(search_min(i64::MIN..=i64::MAX, |x| x >= 1_000_000_007), Some(1_000_000_007));
+    }
+
+    #[test]
+    fn gallops_from_the_start() {
+        // A small answer in a huge range only looks at small values
+        let mut biggest = 0;
+        let found = search_min(0..=i64::MAX, |x| {
+            biggest = biggest.max(x);
+            x * x >= 50
+        });
+        assert_eq!(found, Some(8));
+        assert!(biggest <= 16);
+    }
+}
@@ -0,0 +1,202 @@
+// A set of small numbers, 0 to 63, packed into the bits of a u64. Good for
+// sets of letters, opcodes or group IDs, which are copied about and counted
+// far more cheaply than a HashSet
+
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Sub, SubAssign};
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BitSet(u64);
+
+impl BitSet {
+    // The biggest number which can be stored, plus one
+    pub const CAPACITY: usize = 64;
+
+    pub const EMPTY: BitSet = BitSet(0);
+
+    pub fn new() -> BitSet {
+        BitSet::EMPTY
+    }
+
+    // The set of 0 to [n] - 1
+    pub fn full(n: usize) -> BitSet {
+        assert!(n <= BitSet::CAPACITY, "{} is too big for a BitSet", n);
+        BitSet(if n == BitSet::CAPACITY { u64::MAX } else { (1 << n) - 1 })
+    }
+
+    fn bit(value: usize) -> u64 {
+        assert!(value < BitSet::CAPACITY, "{} is too big for a BitSet", value);
+        1 << value
+    }
+
+    // Gives whether [value] wasn't already in the set
+    pub fn insert(&mut self, value: usize) -> bool {
+        let was_absent = !self.contains(value);
+        self.0 |= BitSet::bit(value);
+        was_absent
+    }
+
+    // Gives whether [value] was in the set
+    pub fn remove(&mut self, value: usize) -> bool {
+        let was_present = self.contains(value);
+        self.0 &= !BitSet::bit(value);
+        was_present
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        value < BitSet::CAPACITY && self.0 & (1 << value) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    // The smallest number in the set
+    pub fn first(&self) -> Option<usize> {
+        if self.is_empty() { None } else { Some(self.0.trailing_zeros() as usize) }
+    }
+
+    pub fn is_subset(&self, other: BitSet) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    // The numbers in the set, smallest first
+    pub fn iter(&self) -> Iter {
+        Iter(self.0)
+    }
+}
+
+pub struct Iter(u64);
+
+impl Iterator for Iter {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.0 == 0 {
+            return None;
+        }
+        let value = self.0.trailing_zeros() as usize;
+        // Clear the lowest set bit
+        self.0 &= self.0 - 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl IntoIterator for BitSet {
+    type Item = usize;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Iter {
+        self.iter()
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> BitSet {
+        let mut set = BitSet::new();
+        for value in iter {
+            set.insert(value);
+        }
+        set
+    }
+}
+
+// Displayed like a HashSet, e.g. {1, 4, 9}
+impl fmt::Debug for BitSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+// | is the union, & the intersection and - the difference
+impl BitOr for BitSet {
+    type Output = BitSet;
+
+    fn bitor(self, other: BitSet) -> BitSet {
+        BitSet(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for BitSet {
+    fn bitor_assign(&mut self, other: BitSet) {
+        self.0 |= other.0;
+    }
+}
+
+impl BitAnd for BitSet {
+    type Output = BitSet;
+
+    fn bitand(self, other: BitSet) -> BitSet {
+        BitSet(self.0 & other.0)
+    }
+}
+
+impl BitAndAssign for BitSet {
+    fn bitand_assign(&mut self, other: BitSet) {
+        self.0 &= other.0;
+    }
+}
+
+impl Sub for BitSet {
+    type Output = BitSet;
+
+    fn sub(self, other: BitSet) -> BitSet {
+        BitSet(self.0 & !other.0)
+    }
+}
+
+impl SubAssign for BitSet {
+    fn sub_assign(&mut self, other: BitSet) {
+        self.0 &= !other.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_remove_and_iterate() {
+        let mut set = BitSet::new();
+        assert!(set.insert(63));
+        assert!(set.insert(0));
+        assert!(set.insert(17));
+        assert!(!set.insert(17));
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.first(), Some(0));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0, 17, 63]);
+        assert!(set.remove(0));
+        assert!(!set.remove(0));
+        assert!(!set.contains(0) && set.contains(63) && !set.contains(64));
+        assert_eq!(format!("{:?}", set), "{17, 63}");
+    }
+
+    #[test]
+    fn set_operations() {
+        let a: BitSet = vec![1, 2, 3].into_iter().collect();
+        let b: BitSet = vec![3, 4].into_iter().collect();
+        assert_eq!((a | b).iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!((a & b).iter().collect::<Vec<_>>(), vec![3]);
+        assert_eq!((a - b).iter().collect::<Vec<_>>(), vec![1, 2]);
+        assert!((a & b).is_subset(a) && !a.is_subset(b));
+        assert_eq!(BitSet::full(4) - a, vec![0].into_iter().collect());
+        assert_eq!(BitSet::full(64).len(), 64);
+        assert!(BitSet::full(0).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_big() {
+        BitSet::new().insert(64);
+    }
+}
@@ -0,0 +1,89 @@
+// Combinatorics for puzzles which compare everything with everything else,
+// like day 2's box IDs or day 25's points, or which try every ordering of a
+// few things
+
+// Every pair of items from [iter] where the first comes before the second,
+// e.g. (a, b), (a, c), (b, c) for [a, b, c]. The iterator is cloned to go over
+// the later items again, so it should be cheap to clone, like a slice's
+pub fn pairs<I>(iter: I) -> impl Iterator<Item = (I::Item, I::Item)>
+where
+    I: IntoIterator,
+    I::IntoIter: Clone,
+    I::Item: Clone,
+{
+    let mut firsts = iter.into_iter();
+    std::iter::from_fn(move || {
+        let first = firsts.next()?;
+        let seconds = firsts.clone();
+        Some(seconds.map(move |second| (first.clone(), second)))
+    }).flatten()
+}
+
+// Rearrange [items] into the next permutation in lexicographic order, giving
+// false (and leaving them sorted) if they were already the last one
+pub fn next_permutation<T: Ord>(items: &mut [T]) -> bool {
+    // Find the longest decreasing run at the end. The item before it is the
+    // one to increase
+    let pivot = match (1..items.len()).rev().find(|&i| items[i - 1] < items[i]) {
+        Some(i) => i - 1,
+        None => {
+            items.reverse();
+            return false;
+        },
+    };
+    // Swap it with the smallest bigger item after it, and put the run in
+    // increasing order
+    let successor = (pivot + 1..items.len()).rev().find(|&i| items[i] > items[pivot]).unwrap();
+    items.swap(pivot, successor);
+    items[pivot + 1..].reverse();
+    true
+}
+
+// Every ordering of [items] in lexicographic order, starting from the sorted
+// order. Equal items aren't told apart, so there are no repeats. There are n!
+// orderings of n different items, so this is only for a handful of items
+pub fn permutations<T: Ord + Clone>(items: &[T]) -> impl Iterator<Item = Vec<T>> {
+    let mut next = Some(items.to_vec());
+    if let Some(first) = next.as_mut() {
+        first.sort();
+    }
+    std::iter::from_fn(move || {
+        let current = next.take()?;
+        let mut following = current.clone();
+        if next_permutation(&mut following) {
+            next = Some(following);
+        }
+        Some(current)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_pairs() {
+        let pairs: Vec<_> = pairs(&['a', 'b', 'c', 'd']).map(|(a, b)| (*a, *b)).collect();
+        assert_eq!(pairs, vec![('a', 'b'), ('a', 'c'), ('a', 'd'),
+                               ('b', 'c'), ('b', 'd'), ('c', 'd')]);
+        assert_eq!(super::pairs(0..1).count(), 0);
+        assert_eq!(super::pairs(0..100).count(), 4950);
+    }
+
+    #[test]
+    fn permutations_in_order() {
+        let all: Vec<_> = permutations(&[3, 1, 2]).collect();
+        assert_eq!(all, vec![vec![1, 2, 3], vec![1, 3, 2], vec![2, 1, 3],
+                             vec![2, 3, 1], vec![3, 1, 2], vec![3, 2, 1]]);
+        assert_eq!(permutations(&[1, 2, 3, 4, 5, 6]).count(), 720);
+        assert_eq!(permutations(&[1, 1, 2]).count(), 3);
+        assert_eq!(permutations::<u8>(&[]).collect::<Vec<_>>(), vec![vec![]]);
+    }
+
+    #[test]
+    fn last_permutation_wraps_around() {
+        let mut items = [3, 2, 1];
+        assert!(!next_permutation(&mut items));
+        assert_eq!(items, [1, 2, 3]);
+    }
+}
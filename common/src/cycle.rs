@@ -0,0 +1,75 @@
+// Finding where a sequence of states starts repeating, so that simulations
+// which run for billions of steps can skip over all but the first few cycles
+
+use std::hash::Hash;
+
+use crate::hash::HashMap;
+
+// The index of the first state which is part of the cycle, and the length of
+// the cycle, if any state in [states] repeats. Stops reading [states] at the
+// first repeat, so it can be given an endless iterator which always repeats
+pub fn find_cycle<S: Hash + Eq>(states: impl IntoIterator<Item = S>) -> Option<(usize, usize)> {
+    let mut seen = HashMap::default();
+    for (i, state) in states.into_iter().enumerate() {
+        if let Some(start) = seen.insert(state, i) {
+            return Some((start, i - start));
+        }
+    }
+    None
+}
+
+// The index of the state before the cycle repeated which is the same as the
+// state at index [n]
+pub fn index_in_cycle(n: usize, (start, length): (usize, usize)) -> usize {
+    if n < start {
+        n
+    }
+    else {
+        start + (n - start) % length
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_repeats() {
+        assert_eq!(find_cycle(0..100), None);
+        assert_eq!(find_cycle(Vec::<u32>::new()), None);
+    }
+
+    #[test]
+    fn constant() {
+        assert_eq!(find_cycle(std::iter::repeat(7)), Some((0, 1)));
+    }
+
+    #[test]
+    fn cycle_after_a_lead_in() {
+        // 10, 11, 12 then 0, 1, 2, 3, 4 forever
+        let states = (10..13).chain((0..5).cycle());
+        assert_eq!(find_cycle(states), Some((3, 5)));
+    }
+
+    #[test]
+    fn stops_at_the_first_repeat() {
+        // A sequence which only repeats after reaching 1, like the Collatz
+        // sequence, and which would go on forever afterwards
+        let states = std::iter::successors(Some(27u64), |&n| {
+            Some(if n == 1 { 4 } else if n % 2 == 0 { n / 2 } else { 3 * n + 1 })
+        });
+        let (start, length) = find_cycle(states).unwrap();
+        assert_eq!(length, 3);
+        assert_eq!(start, 109);
+    }
+
+    #[test]
+    fn index_mapping() {
+        let cycle = (3, 5);
+        assert_eq!(index_in_cycle(2, cycle), 2);
+        assert_eq!(index_in_cycle(3, cycle), 3);
+        assert_eq!(index_in_cycle(7, cycle), 7);
+        assert_eq!(index_in_cycle(8, cycle), 3);
+        assert_eq!(index_in_cycle(1_000_000_000, cycle), 3 + (1_000_000_000 - 3) % 5);
+    }
+}
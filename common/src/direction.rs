@@ -0,0 +1,88 @@
+// The four directions on a map, with up being towards smaller y as in Grid
+
+use crate::point::Point;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+use self::Direction::*;
+
+impl Direction {
+    // In reading order, like Point::neighbours
+    pub const ALL: [Direction; 4] = [Up, Left, Right, Down];
+
+    // Turn a quarter turn anticlockwise
+    pub fn turn_left(self) -> Direction {
+        match self {
+            Up => Left,
+            Left => Down,
+            Down => Right,
+            Right => Up,
+        }
+    }
+
+    // Turn a quarter turn clockwise
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Up => Right,
+            Right => Down,
+            Down => Left,
+            Left => Up,
+        }
+    }
+
+    pub fn reverse(self) -> Direction {
+        match self {
+            Up => Down,
+            Down => Up,
+            Left => Right,
+            Right => Left,
+        }
+    }
+
+    // The offset of one step in this direction
+    pub fn offset(self) -> Point {
+        match self {
+            Up => Point::new(0, -1),
+            Down => Point::new(0, 1),
+            Left => Point::new(-1, 0),
+            Right => Point::new(1, 0),
+        }
+    }
+
+    // Puzzles give directions either as the compass points N, E, S and W or
+    // as U, D, L and R
+    pub fn from_char(c: char) -> Option<Direction> {
+        match c {
+            'U' | 'N' => Some(Up),
+            'D' | 'S' => Some(Down),
+            'L' | 'W' => Some(Left),
+            'R' | 'E' => Some(Right),
+            _ => None,
+        }
+    }
+
+    // Things drawn on a map facing in a direction are shown as ^, v, < and >
+    pub fn from_arrow(c: char) -> Option<Direction> {
+        match c {
+            '^' => Some(Up),
+            'v' => Some(Down),
+            '<' => Some(Left),
+            '>' => Some(Right),
+            _ => None,
+        }
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Up => '^',
+            Down => 'v',
+            Left => '<',
+            Right => '>',
+        }
+    }
+}
@@ -0,0 +1,143 @@
+// Union-find over the indexes 0..size, for grouping things together when we
+// only find out pairwise which belong together, e.g. day 25's constellations
+
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+    count: usize,
+}
+
+impl DisjointSet {
+    // Every index starts off in a set of its own
+    pub fn new(size: usize) -> DisjointSet {
+        DisjointSet {
+            parent: (0..size).collect(),
+            rank: vec![0; size],
+            count: size,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    // The number of separate sets
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    // The index which represents the set containing [i]
+    pub fn find(&mut self, i: usize) -> usize {
+        let parent = self.parent[i];
+        if parent == i {
+            return i;
+        }
+        // Path compression, point straight at the root for next time
+        let root = self.find(parent);
+        self.parent[i] = root;
+        root
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    // Merge the sets containing [a] and [b]. Gives whether they were
+    // separate sets before
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let a_root = self.find(a);
+        let b_root = self.find(b);
+        if a_root == b_root {
+            return false;
+        }
+
+        // Union by rank, attach the shallower tree to the deeper one
+        if self.rank[a_root] < self.rank[b_root] {
+            self.parent[a_root] = b_root;
+        }
+        else {
+            self.parent[b_root] = a_root;
+            if self.rank[a_root] == self.rank[b_root] {
+                self.rank[a_root] += 1;
+            }
+        }
+        self.count -= 1;
+        true
+    }
+
+    // The members of each set, each in increasing order. The sets are ordered
+    // by their smallest member
+    pub fn sets(&mut self) -> Vec<Vec<usize>> {
+        let mut sets: Vec<Vec<usize>> = vec![];
+        let mut set_of_root: Vec<Option<usize>> = vec![None; self.len()];
+        for i in 0..self.len() {
+            let root = self.find(i);
+            match set_of_root[root] {
+                Some(set) => sets[set].push(i),
+                None => {
+                    set_of_root[root] = Some(sets.len());
+                    sets.push(vec![i]);
+                },
+            }
+        }
+        sets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starts_separate() {
+        let mut dsu = DisjointSet::new(3);
+        assert_eq!(dsu.count(), 3);
+        assert!(!dsu.same(0, 1));
+        assert_eq!(dsu.sets(), vec![vec![0], vec![1], vec![2]]);
+    }
+
+    #[test]
+    fn union_merges_sets() {
+        let mut dsu = DisjointSet::new(5);
+        assert!(dsu.union(0, 3));
+        assert!(dsu.union(4, 1));
+        assert!(dsu.union(3, 4));
+        assert_eq!(dsu.count(), 2);
+        assert!(dsu.same(0, 1));
+        assert!(!dsu.same(0, 2));
+        assert_eq!(dsu.sets(), vec![vec![0, 1, 3, 4], vec![2]]);
+    }
+
+    #[test]
+    fn union_within_a_set_does_nothing() {
+        let mut dsu = DisjointSet::new(3);
+        dsu.union(0, 1);
+        dsu.union(1, 2);
+        assert!(!dsu.union(2, 0));
+        assert_eq!(dsu.count(), 1);
+    }
+
+    #[test]
+    fn long_chains_share_a_root() {
+        let mut dsu = DisjointSet::new(1000);
+        for i in 1..1000 {
+            dsu.union(i - 1, i);
+        }
+        let root = dsu.find(0);
+        assert!((0..1000).all(|i| dsu.find(i) == root));
+        assert_eq!(dsu.count(), 1);
+    }
+
+    #[test]
+    fn empty() {
+        let mut dsu = DisjointSet::new(0);
+        assert!(dsu.is_empty());
+        assert_eq!(dsu.count(), 0);
+        assert!(dsu.sets().is_empty());
+    }
+}
@@ -0,0 +1,445 @@
+// Running the elfcode programs which days 19 and 21 give as their input. A
+// program has six registers, one of which is bound to the instruction
+// pointer, so that jumps are made by writing to it. The program halts when
+// the instruction pointer goes outside it. Also keeps a profile of a run,
+// which shows the program's loops for working out what it does by hand
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::AocError;
+use crate::parse;
+use crate::point::Point;
+use crate::viz::svg::{Style, Svg};
+
+// How many registers the machine has
+pub const REGISTERS: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Addr, Addi,
+    Mulr, Muli,
+    Banr, Bani,
+    Borr, Bori,
+    Setr, Seti,
+    Gtir, Gtri, Gtrr,
+    Eqir, Eqri, Eqrr,
+}
+use self::Op::*;
+
+impl Op {
+    pub const ALL: [Op; 16] = [
+        Addr, Addi, Mulr, Muli, Banr, Bani, Borr, Bori,
+        Setr, Seti, Gtir, Gtri, Gtrr, Eqir, Eqri, Eqrr,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Addr => "addr", Addi => "addi",
+            Mulr => "mulr", Muli => "muli",
+            Banr => "banr", Bani => "bani",
+            Borr => "borr", Bori => "bori",
+            Setr => "setr", Seti => "seti",
+            Gtir => "gtir", Gtri => "gtri", Gtrr => "gtrr",
+            Eqir => "eqir", Eqri => "eqri", Eqrr => "eqrr",
+        }
+    }
+
+    // Work out the result of the operation with inputs [a] and [b], where
+    // each is a register or a value depending on the operation. Adding and
+    // multiplying wrap around on overflow, the same in debug and release builds
+    pub fn apply(self, registers: &[i64], a: i64, b: i64) -> i64 {
+        let r = |i: i64| registers[i as usize];
+        let flag = |test: bool| if test { 1 } else { 0 };
+        match self {
+            Addr => r(a).wrapping_add(r(b)),
+            Addi => r(a).wrapping_add(b),
+            Mulr => r(a).wrapping_mul(r(b)),
+            Muli => r(a).wrapping_mul(b),
+            Banr => r(a) & r(b),
+            Bani => r(a) & b,
+            Borr => r(a) | r(b),
+            Bori => r(a) | b,
+            Setr => r(a),
+            Seti => a,
+            Gtir => flag(a > r(b)),
+            Gtri => flag(r(a) > b),
+            Gtrr => flag(r(a) > r(b)),
+            Eqir => flag(a == r(b)),
+            Eqri => flag(r(a) == b),
+            Eqrr => flag(r(a) == r(b)),
+        }
+    }
+
+    // The inputs which are registers, as (a, b)
+    pub fn reads_registers(self) -> (bool, bool) {
+        match self {
+            Addr | Mulr | Banr | Borr | Gtrr | Eqrr => (true, true),
+            Addi | Muli | Bani | Bori | Setr | Gtri | Eqri => (true, false),
+            Gtir | Eqir => (false, true),
+            Seti => (false, false),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub op: Op,
+    pub a: i64,
+    pub b: i64,
+    pub c: i64,
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {} {}", self.op.name(), self.a, self.b, self.c)
+    }
+}
+
+// Instructions look like "addi 1 2 3"
+fn parse_instruction(line: &str) -> Result<Instruction, String> {
+    let [name, a, b, c] = parse::fields(line, " ")?;
+    let op = Op::ALL.iter().copied().find(|op| op.name() == name)
+        .ok_or_else(|| format!("'{}' isn't an operation", name))?;
+    let instruction = Instruction { op, a: parse::value(a)?, b: parse::value(b)?,
+                                    c: parse::value(c)? };
+    // Registers are checked here so that running the program can't fail
+    let (a_register, b_register) = op.reads_registers();
+    let operands = [(a_register, "a", instruction.a), (b_register, "b", instruction.b),
+                    (true, "c", instruction.c)];
+    for &(is_register, operand, register) in &operands {
+        if is_register && !register_exists(register) {
+            return Err(format!("{} is register {} but there are only registers 0 to {}",
+                               operand, register, REGISTERS - 1));
+        }
+    }
+    Ok(instruction)
+}
+
+fn register_exists(register: i64) -> bool {
+    usize::try_from(register).is_ok_and(|r| r < REGISTERS)
+}
+
+// An instruction which has been run, shown the way day 19's puzzle does: the
+// instruction pointer, the registers before, the instruction and the registers
+// after, e.g. "ip=0 [0, 0, 0, 0, 0, 0] seti 5 0 1 [0, 5, 0, 0, 0, 0]". The
+// registers after are from before the instruction pointer moves on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TracedStep {
+    pub ip: usize,
+    pub before: [i64; REGISTERS],
+    pub instruction: Instruction,
+    pub after: [i64; REGISTERS],
+}
+
+impl fmt::Display for TracedStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ip={} {:?} {} {:?}", self.ip, self.before, self.instruction, self.after)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Program {
+    pub ip_register: usize,
+    pub instructions: Vec<Instruction>,
+}
+
+// Programs are a "#ip N" line saying which register is the instruction
+// pointer, and then an instruction per line
+pub fn parse_program(input: &str) -> Result<Program, AocError> {
+    let mut ip_register = None;
+    let mut instructions = vec![];
+    for (i, line) in input.lines().enumerate() {
+        if let Some(register) = line.strip_prefix("#ip ") {
+            if ip_register.is_some() {
+                return Err(AocError::parse(i + 1, line, "there's more than one #ip line"));
+            }
+            let register: i64 = parse::value(register).map_err(|e| AocError::parse(i + 1, line, e))?;
+            if !register_exists(register) {
+                return Err(AocError::parse(i + 1, line, format!(
+                    "there are only registers 0 to {}", REGISTERS - 1)));
+            }
+            ip_register = Some(register as usize);
+        }
+        else {
+            instructions.push(parse_instruction(line).map_err(|e| AocError::parse(i + 1, line, e))?);
+        }
+    }
+
+    let ip_register = ip_register.ok_or_else(|| {
+        AocError::parse_input("there's no #ip line giving the instruction pointer register")
+    })?;
+    Ok(Program { ip_register, instructions })
+}
+
+impl Program {
+    // The instruction the instruction pointer in [registers] points to, if
+    // it's still in the program
+    fn next(&self, registers: &[i64; REGISTERS]) -> Option<(usize, &Instruction)> {
+        let ip = usize::try_from(registers[self.ip_register]).ok()?;
+        self.instructions.get(ip).map(|instruction| (ip, instruction))
+    }
+
+    // Run one instruction, unless the program has halted. Gives the line
+    // which was run
+    pub fn step(&self, registers: &mut [i64; REGISTERS]) -> Option<usize> {
+        let (ip, instruction) = self.next(registers)?;
+        registers[instruction.c as usize] = instruction.op.apply(registers, instruction.a,
+                                                                 instruction.b);
+        registers[self.ip_register] += 1;
+        Some(ip)
+    }
+
+    // Run the next instruction like step, giving what it did
+    pub fn step_traced(&self, registers: &mut [i64; REGISTERS]) -> Option<TracedStep> {
+        let before = *registers;
+        let (ip, &instruction) = self.next(registers)?;
+        registers[instruction.c as usize] = instruction.op.apply(registers, instruction.a,
+                                                                 instruction.b);
+        let after = *registers;
+        registers[self.ip_register] += 1;
+        Some(TracedStep { ip, before, instruction, after })
+    }
+
+    // Run until the program halts. Gives the number of instructions run
+    pub fn run(&self, registers: &mut [i64; REGISTERS]) -> u64 {
+        let mut count = 0;
+        while self.step(registers).is_some() {
+            count += 1;
+        }
+        count
+    }
+
+    // Run for up to [max_steps] instructions, counting how many times each
+    // line is run and keeping the first [trace_len] lines run, in order
+    pub fn profile(&self, registers: &mut [i64; REGISTERS], max_steps: u64, trace_len: usize)
+            -> Profile {
+        let mut profile = Profile {
+            counts: vec![0; self.instructions.len()],
+            trace: vec![],
+            halted: false,
+        };
+        for _ in 0..max_steps {
+            match self.step(registers) {
+                Some(ip) => {
+                    profile.counts[ip] += 1;
+                    if profile.trace.len() < trace_len {
+                        profile.trace.push(ip);
+                    }
+                },
+                None => {
+                    profile.halted = true;
+                    break;
+                },
+            }
+        }
+        profile.halted |= self.next(registers).is_none();
+        profile
+    }
+}
+
+// How a run of a program went
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    // How many times each line was run
+    pub counts: Vec<u64>,
+    // The lines which were run, in order, from the start of the run
+    pub trace: Vec<usize>,
+    // Whether the program halted, rather than being stopped
+    pub halted: bool,
+}
+
+// How wide the parts of the picture of a profile are, in cells
+const LISTING_WIDTH: i32 = 16;
+const BAR_WIDTH: f64 = 40.0;
+const COUNT_WIDTH: f64 = 10.0;
+// How many cells across each instruction in the trace is
+const TRACE_STEP: f64 = 0.25;
+
+// A picture of [profile], a run of [program]. Each line of the program has a
+// row with the line in it, then a bar for how many times it was run, and
+// then, along the same row, when it was run in the trace. The bars go up in
+// powers of ten, so that the loops inside loops stand out one above another
+// rather than the innermost one dwarfing the rest
+pub fn draw_profile(program: &Program, profile: &Profile) -> Svg {
+    let mut svg = Svg::new().scale(12.0);
+    let max = profile.counts.iter().copied().max().unwrap_or(0);
+    let length = |count: u64| {
+        if max == 0 { 0.0 } else { BAR_WIDTH * ((count + 1) as f64).ln() / ((max + 1) as f64).ln() }
+    };
+    let bar_x = f64::from(LISTING_WIDTH);
+    let trace_x = bar_x + BAR_WIDTH + COUNT_WIDTH;
+
+    for (i, instruction) in program.instructions.iter().enumerate() {
+        let y = i as i32;
+        let style = if i == program.ip_register { "#888" } else { "black" };
+        svg.text(Point::new(0, y), &format!("{:>2} {}", i, instruction), &Style::fill(style));
+        let count = profile.counts[i];
+        if count > 0 {
+            svg.rect(bar_x, f64::from(y) + 0.1, length(count), 0.8, &Style::fill("#4682b4"));
+        }
+        svg.text(Point::new((bar_x + length(count)) as i32 + 1, y), &count.to_string(),
+                 &Style::fill("#555"));
+        // A faint line along each row of the trace, to follow it across
+        svg.rect(trace_x, f64::from(y) + 0.45, profile.trace.len() as f64 * TRACE_STEP, 0.1,
+                 &Style::fill("#eee"));
+    }
+
+    // The trace as a line stepping from row to row, so that jumps back to the
+    // start of a loop are easy to see
+    let mut previous = None;
+    for (t, &ip) in profile.trace.iter().enumerate() {
+        let (x, y) = (trace_x + t as f64 * TRACE_STEP, ip as f64);
+        if let Some(from) = previous {
+            let top = f64::min(from, y);
+            svg.rect(x, top + 0.45, 0.05, (from - y).abs() + 0.1, &Style::fill("#f4a582"));
+        }
+        svg.rect(x, y + 0.3, TRACE_STEP, 0.4, &Style::fill("#d6604d"));
+        previous = Some(y);
+    }
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    // The example from day 19
+    use crate::fixtures::DAY19 as EXAMPLE;
+
+    #[test]
+    fn runs_the_example() {
+        let program = parse_program(EXAMPLE).unwrap();
+        let mut registers = [0; 6];
+        assert_eq!(program.run(&mut registers), 5);
+        assert_eq!(registers, [7, 5, 6, 0, 0, 9]);
+        assert_eq!(program.instructions[2].to_string(), "addi 0 1 0");
+    }
+
+    // The same as the run shown in day 19's puzzle
+    #[test]
+    fn traces_the_example() {
+        let program = parse_program(EXAMPLE).unwrap();
+        let mut registers = [0; 6];
+        let trace: Vec<_> = std::iter::from_fn(|| program.step_traced(&mut registers))
+            .map(|step| step.to_string())
+            .collect();
+        crate::assert_snapshot!("example_trace", trace.join("\n"));
+        assert_eq!(registers, [7, 5, 6, 0, 0, 9]);
+    }
+
+    #[test]
+    fn profiles_the_example() {
+        let program = parse_program(EXAMPLE).unwrap();
+        let profile = program.profile(&mut [0; 6], 100, 3);
+        assert_eq!(profile, Profile { counts: vec![1, 1, 1, 0, 1, 0, 1], trace: vec![0, 1, 2],
+                                      halted: true });
+        let stopped = program.profile(&mut [0; 6], 2, 10);
+        assert!(!stopped.halted);
+        assert!(draw_profile(&program, &profile).to_string().contains("addi 0 1 0"));
+    }
+
+    #[test]
+    fn bad_programs() {
+        assert!(parse_program("seti 5 0 1").is_err());
+        assert!(parse_program("#ip 0\nseti 5 0 6").is_err());
+        assert!(parse_program("#ip 0\nnope 1 2 3").is_err());
+        assert!(parse_program("#ip 0\n#ip 1").is_err());
+        assert!(parse_program("#ip 6").is_err());
+        // Only operands which are registers have to be one
+        assert!(parse_program("#ip 0\nseti 6 -1 2").is_ok());
+        let error = |program| parse_program(program).unwrap_err().to_string();
+        assert_eq!(error("#ip 0\nseti 5 0 1\naddr 1 6 3"),
+                   "Failed to parse line 3, `addr 1 6 3`: b is register 6 but there are only \
+                    registers 0 to 5");
+        assert_eq!(error("#ip 0\ngtri -1 2 3"),
+                   "Failed to parse line 2, `gtri -1 2 3`: a is register -1 but there are only \
+                    registers 0 to 5");
+    }
+
+    // Whether each of the operation's inputs is a register, going by its name
+    // as the puzzle describes them: the last letter is for b and, for the
+    // comparisons, the one before it is for a. Otherwise a is a register,
+    // except for seti where it's a value and b isn't used at all
+    fn register_inputs(name: &str) -> (bool, bool) {
+        let mode = |i: usize| name.as_bytes()[i] == b'r';
+        match &name[..2] {
+            "gt" | "eq" => (mode(2), mode(3)),
+            "se" => (mode(3), false),
+            _ => (true, mode(3)),
+        }
+    }
+
+    // The operation called [name], written from the puzzle description rather
+    // than from Op. The arithmetic is done with room to spare and then cut
+    // down to 64 bits, which is what wrapping around gives
+    fn reference(name: &str, registers: &[i64; REGISTERS], a: i64, b: i64) -> i64 {
+        let (a_register, b_register) = register_inputs(name);
+        let x = if a_register { registers[a as usize] } else { a };
+        let y = if b_register { registers[b as usize] } else { b };
+        match &name[..name.len() - 1] {
+            "add" => (i128::from(x) + i128::from(y)) as i64,
+            "mul" => (i128::from(x) * i128::from(y)) as i64,
+            "ban" => x & y,
+            "bor" => x | y,
+            "set" => x,
+            "gti" | "gtr" => (x > y) as i64,
+            "eqi" | "eqr" => (x == y) as i64,
+            _ => unreachable!("{} isn't an operation", name),
+        }
+    }
+
+    // Values at the edges of what a register holds turn up often, to catch
+    // overflow, as do small ones which are likely to be equal to each other
+    fn value() -> impl Strategy<Value = i64> {
+        prop_oneof![
+            Just(i64::MIN), Just(i64::MAX), Just(i64::MIN + 1), Just(i64::MAX - 1),
+            -3i64..=3,
+            any::<i64>(),
+        ]
+    }
+
+    // An operation with inputs which suit it: a register number for each
+    // input that's a register and any value for the others
+    fn operation() -> impl Strategy<Value = (Op, i64, i64)> {
+        (prop::sample::select(Op::ALL.to_vec()), 0i64..6, 0i64..6, value(), value())
+            .prop_map(|(op, a_register, b_register, a_value, b_value)| {
+                let (a_is_register, b_is_register) = register_inputs(op.name());
+                (op,
+                 if a_is_register { a_register } else { a_value },
+                 if b_is_register { b_register } else { b_value })
+            })
+    }
+
+    #[test]
+    fn reads_the_registers_the_names_say() {
+        for op in Op::ALL.iter() {
+            assert_eq!(op.reads_registers(), register_inputs(op.name()), "{}", op.name());
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn operations_match_the_reference(registers in prop::array::uniform6(value()),
+                                          (op, a, b) in operation()) {
+            prop_assert_eq!(op.apply(&registers, a, b), reference(op.name(), &registers, a, b),
+                            "{} {} {} with {:?}", op.name(), a, b, registers);
+        }
+
+        // Parsing and stepping through an instruction writes the operation's
+        // result to register c and moves on to the next instruction
+        #[test]
+        fn steps_through_an_instruction(registers in prop::array::uniform5(value()),
+                                        (op, a, b) in operation(), c in 0usize..5) {
+            let program = parse_program(&format!("#ip 5\n{} {} {} {}", op.name(), a, b, c))
+                .unwrap();
+            let mut registers = [registers[0], registers[1], registers[2], registers[3],
+                                 registers[4], 0];
+            let expected = reference(op.name(), &registers, a, b);
+            prop_assert_eq!(program.step(&mut registers), Some(0));
+            prop_assert_eq!((registers[c], registers[5]), (expected, 1));
+        }
+    }
+}
@@ -0,0 +1,99 @@
+// The ways reading an input or solving a puzzle can fail
+
+use std::fmt;
+use std::io;
+use std::num::ParseIntError;
+
+pub enum AocError {
+    // Reading the input failed. The path is "stdin" when reading from stdin
+    Io { path: String, source: io::Error },
+    // The input couldn't be understood. The line is its number, counting from
+    // 1 like in an editor, and what's on it. There's no line if the problem is
+    // with the input as a whole e.g. something is missing
+    Parse { line: Option<(usize, String)>, reason: String },
+    // The input made sense but there's no answer, e.g. a search came up empty
+    NoSolution(String),
+    // The solver can't handle this input, e.g. because the solution was worked
+    // out by hand for a particular input
+    Unsupported(String),
+}
+
+impl AocError {
+    // What's wrong with line number [line], which is [text]
+    pub fn parse(line: usize, text: &str, reason: impl fmt::Display) -> AocError {
+        AocError::Parse { line: Some((line, text.to_string())), reason: reason.to_string() }
+    }
+
+    pub fn parse_input(reason: impl fmt::Display) -> AocError {
+        AocError::Parse { line: None, reason: reason.to_string() }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AocError::Io { path, source } => write!(f, "Failed to read {}: {}", path, source),
+            AocError::Parse { line: Some((line, text)), reason } => {
+                write!(f, "Failed to parse line {}, `{}`: {}", line, shorten(text), reason)
+            },
+            AocError::Parse { line: None, reason } => {
+                write!(f, "Failed to parse the input: {}", reason)
+            },
+            AocError::NoSolution(reason) => write!(f, "No solution: {}", reason),
+            AocError::Unsupported(reason) => write!(f, "Unsupported input: {}", reason),
+        }
+    }
+}
+
+// The most of a line shown in an error. Some lines, like day 5's polymer, are
+// thousands of characters long
+const MAX_SHOWN: usize = 40;
+
+// [text] cut down to at most MAX_SHOWN characters
+fn shorten(text: &str) -> String {
+    let text = text.trim_end();
+    match text.char_indices().nth(MAX_SHOWN) {
+        Some((end, _)) => format!("{}...", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+// Each day's main function returns an AocError, which is printed with Debug, so
+// make that as readable as Display
+impl fmt::Debug for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AocError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+// So that numbers can be parsed with ?. Callers which know the line should
+// add it with AocError::parse instead
+impl From<ParseIntError> for AocError {
+    fn from(e: ParseIntError) -> AocError {
+        AocError::parse_input(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_errors_show_the_line() {
+        let error = AocError::parse(7, "addz 1 2 3", "'addz' isn't an operation");
+        assert_eq!(error.to_string(), "Failed to parse line 7, `addz 1 2 3`: 'addz' isn't an \
+                                       operation");
+        let long = "dabAcCaCBAcCcaDA".repeat(10);
+        assert_eq!(AocError::parse(1, &long, "no").to_string(),
+                   format!("Failed to parse line 1, `{}...`: no", &long[..MAX_SHOWN]));
+    }
+}
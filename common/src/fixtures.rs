@@ -0,0 +1,96 @@
+// Inputs for tests and benchmarks: the examples from the puzzle descriptions,
+// kept in the fixtures directory so that the days' tests and the runner's
+// benchmarks share them, and the real puzzle inputs, for the ones which can
+// use them when they've been downloaded
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// The examples for the days with one example which everything uses. Days
+// with lots of small examples, like day 1's frequency changes, keep them
+// with their tests
+pub const DAY01: &str = include_str!("../fixtures/day01.txt");
+pub const DAY03: &str = include_str!("../fixtures/day03.txt");
+pub const DAY04: &str = include_str!("../fixtures/day04.txt");
+pub const DAY05: &str = include_str!("../fixtures/day05.txt");
+pub const DAY06: &str = include_str!("../fixtures/day06.txt");
+pub const DAY07: &str = include_str!("../fixtures/day07.txt");
+pub const DAY08: &str = include_str!("../fixtures/day08.txt");
+pub const DAY10: &str = include_str!("../fixtures/day10.txt");
+pub const DAY11: &str = include_str!("../fixtures/day11.txt");
+pub const DAY12: &str = include_str!("../fixtures/day12.txt");
+pub const DAY15: &str = include_str!("../fixtures/day15.txt");
+pub const DAY17: &str = include_str!("../fixtures/day17.txt");
+pub const DAY18: &str = include_str!("../fixtures/day18.txt");
+pub const DAY19: &str = include_str!("../fixtures/day19.txt");
+pub const DAY22: &str = include_str!("../fixtures/day22.txt");
+pub const DAY24: &str = include_str!("../fixtures/day24.txt");
+
+// [day]'s example, for when the day is only known at run time
+pub fn example(day: u32) -> Option<&'static str> {
+    match day {
+        1 => Some(DAY01), 3 => Some(DAY03), 4 => Some(DAY04), 5 => Some(DAY05),
+        6 => Some(DAY06), 7 => Some(DAY07), 8 => Some(DAY08), 10 => Some(DAY10),
+        11 => Some(DAY11), 12 => Some(DAY12), 15 => Some(DAY15), 17 => Some(DAY17),
+        18 => Some(DAY18), 19 => Some(DAY19), 22 => Some(DAY22), 24 => Some(DAY24),
+        _ => None,
+    }
+}
+
+// Where the runner keeps real puzzle inputs, named like day19.txt, when
+// they're kept together rather than in each day's directory
+pub const INPUTS_DIR: &str = "inputs";
+
+// A directory to use in place of the inputs directory, for when the real
+// inputs are kept outside the repository. The runner uses it too, ahead of
+// the inputs_dir in its config
+pub const INPUTS_VAR: &str = "AOC_INPUTS";
+
+// The places under [root] that [day]'s real input might be, in the order
+// they're tried: in [inputs_dir], then the input.txt in the day's directory.
+// The runner searches the same places
+pub fn input_candidates(root: &Path, inputs_dir: &Path, day: u32) -> [PathBuf; 2] {
+    [
+        root.join(inputs_dir).join(format!("day{:02}.txt", day)),
+        root.join(format!("{:02}/input.txt", day)),
+    ]
+}
+
+// Where [day]'s real input is, if anywhere. The inputs directory is the one
+// in AOC_INPUTS if it's set
+pub fn input_path(day: u32) -> Option<PathBuf> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let inputs_dir = env::var_os(INPUTS_VAR)
+        .map_or_else(|| PathBuf::from(INPUTS_DIR), PathBuf::from);
+    IntoIterator::into_iter(input_candidates(&root, &inputs_dir, day)).find(|path| path.is_file())
+}
+
+// [day]'s real input, if it's there. `aoc2018 new` starts days off with an
+// empty input.txt, which doesn't count
+pub fn input(day: u32) -> Option<String> {
+    fs::read_to_string(input_path(day)?).ok().filter(|input| !input.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples_by_day() {
+        for day in 1..=25 {
+            let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join(format!("fixtures/day{:02}.txt", day));
+            assert_eq!(example(day).is_some(), path.is_file(), "day {}", day);
+        }
+        assert_eq!(example(22), Some("depth: 510\ntarget: 10,10\n"));
+    }
+
+    #[test]
+    fn inputs_dir_then_day_dir() {
+        assert_eq!(input_candidates(Path::new(""), Path::new(INPUTS_DIR), 7),
+                   [PathBuf::from("inputs/day07.txt"), PathBuf::from("07/input.txt")]);
+        assert_eq!(input_candidates(Path::new("/aoc"), Path::new("/elsewhere"), 19),
+                   [PathBuf::from("/elsewhere/day19.txt"), PathBuf::from("/aoc/19/input.txt")]);
+    }
+}
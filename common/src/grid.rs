@@ -0,0 +1,590 @@
+// A dense 2D grid, for the many puzzles set on a map. (0, 0) is the top left
+// corner, x increases to the right and y increases downwards. SparseGrid is
+// for maps where the extent isn't known up front, or which are mostly empty,
+// and FixedGrid for ones which are always the same size
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::iter::FromIterator;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::AocError;
+use crate::point::Point;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    // Stored row by row
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    // A grid filled in by calling [f] with the position of each cell
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T)
+            -> Grid<T> {
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(f(x, y));
+            }
+        }
+        Grid { cells, width, height }
+    }
+
+    // Parse a grid with a line per row and a character per cell. [f] turns
+    // each character into a cell, or gives None if it isn't valid
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> Option<T>)
+            -> Result<Grid<T>, AocError> {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+        for (i, line) in input.lines().enumerate() {
+            let mut line_width = 0;
+            for c in line.chars() {
+                let cell = f(c).ok_or_else(|| {
+                    AocError::parse(i + 1, line, format!("unexpected character '{}'", c))
+                })?;
+                cells.push(cell);
+                line_width += 1;
+            }
+            match width {
+                None => width = Some(line_width),
+                Some(w) if w != line_width => {
+                    return Err(AocError::parse(i + 1, line, format!(
+                        "the line is {} characters long but the first is {}",
+                        line_width, w)));
+                },
+                Some(_) => (),
+            }
+            height += 1;
+        }
+        Ok(Grid { cells, width: width.unwrap_or(0), height })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if self.contains(x, y) { self.cells.get(x + y * self.width) } else { None }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if self.contains(x, y) { self.cells.get_mut(x + y * self.width) } else { None }
+    }
+
+    // The cell at [p], or None if it's outside the grid, including when it has
+    // a negative coordinate
+    pub fn at(&self, p: Point) -> Option<&T> {
+        self.get(usize::try_from(p.x).ok()?, usize::try_from(p.y).ok()?)
+    }
+
+    pub fn at_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.get_mut(usize::try_from(p.x).ok()?, usize::try_from(p.y).ok()?)
+    }
+
+    // Every cell, in reading order
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    // Every cell with its position, in reading order
+    pub fn iter_reading_order(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells.iter().enumerate().map(move |(i, c)| ((i % width, i / width), c))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks() doesn't allow a chunk size of 0
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1)).take(self.height)
+    }
+
+    // The positions up, left, right and down of (x, y) which are in the grid.
+    // That's reading order, which many puzzles use to break ties
+    pub fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.offset_positions(x, y, &[(0, -1), (-1, 0), (1, 0), (0, 1)])
+    }
+
+    // The positions of all eight cells around (x, y) which are in the grid,
+    // in reading order
+    pub fn neighbours_with_diagonals(&self, x: usize, y: usize)
+            -> impl Iterator<Item = (usize, usize)> {
+        self.offset_positions(x, y, &[(-1, -1), (0, -1), (1, -1), (-1, 0),
+                                      (1, 0), (-1, 1), (0, 1), (1, 1)])
+    }
+
+    fn offset_positions(&self, x: usize, y: usize, offsets: &'static [(isize, isize)])
+            -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let nx = x.checked_add_signed(dx)?;
+            let ny = y.checked_add_signed(dy)?;
+            if nx < width && ny < height { Some((nx, ny)) } else { None }
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { cells: self.cells.iter().map(f).collect(), width: self.width, height: self.height }
+    }
+
+    // Display the grid with [f] giving the character for each cell
+    pub fn display_with<F: Fn(&T) -> char>(&self, f: F) -> GridDisplay<'_, T, F> {
+        GridDisplay { grid: self, f }
+    }
+}
+
+// An (x, y) position which is ordered in reading order: top to bottom, then
+// left to right. Many puzzles break ties this way, e.g. sort_by_key(|u|
+// ReadingOrder(u.x, u.y)) puts units in the order they take their turns. It's
+// the only place reading order is worked out, so Point's ordering uses it too
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReadingOrder<T>(pub T, pub T);
+
+impl<T: Ord> Ord for ReadingOrder<T> {
+    fn cmp(&self, other: &ReadingOrder<T>) -> Ordering {
+        self.1.cmp(&other.1).then_with(|| self.0.cmp(&other.0))
+    }
+}
+
+impl<T: Ord> PartialOrd for ReadingOrder<T> {
+    fn partial_cmp(&self, other: &ReadingOrder<T>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid { cells: vec![value; width * height], width, height }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        match self.get(x, y) {
+            Some(cell) => cell,
+            None => panic!("({}, {}) is outside the {}x{} grid", x, y, self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(x, y) {
+            Some(cell) => cell,
+            None => panic!("({}, {}) is outside the {}x{} grid", x, y, width, height),
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        match self.at(p) {
+            Some(cell) => cell,
+            None => panic!("{} is outside the {}x{} grid", p, self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.at_mut(p) {
+            Some(cell) => cell,
+            None => panic!("{} is outside the {}x{} grid", p, width, height),
+        }
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Grid<char>, AocError> {
+        Grid::parse(s, Some)
+    }
+}
+
+// A row per line
+impl fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_with(|&c| c))
+    }
+}
+
+pub struct GridDisplay<'a, T, F> {
+    grid: &'a Grid<T>,
+    f: F,
+}
+
+impl<'a, T, F: Fn(&T) -> char> fmt::Display for GridDisplay<'a, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.grid.rows() {
+            let line: String = row.iter().map(&self.f).collect();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+// A dense grid whose size is part of its type, for the hot loops of days where
+// it's always the same, like day 11's 300x300 fuel cells. The cells are kept
+// inline rather than on the heap, and loops which only go up to [W] and [H]
+// let the compiler leave out the bounds checks. Big ones are best kept
+// somewhere with plenty of stack, since that's where they're built
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FixedGrid<T, const W: usize, const H: usize> {
+    rows: [[T; W]; H],
+}
+
+impl<T, const W: usize, const H: usize> FixedGrid<T, W, H> {
+    // A grid filled in by calling [f] with the position of each cell, in
+    // reading order
+    pub fn from_fn(mut f: impl FnMut(usize, usize) -> T) -> FixedGrid<T, W, H> {
+        FixedGrid { rows: std::array::from_fn(|y| std::array::from_fn(|x| f(x, y))) }
+    }
+
+    // The cells of [grid], if it's the right size
+    pub fn from_grid(grid: &Grid<T>) -> Option<FixedGrid<T, W, H>>
+    where
+        T: Clone,
+    {
+        if (grid.width(), grid.height()) != (W, H) {
+            return None;
+        }
+        Some(FixedGrid::from_fn(|x, y| grid[(x, y)].clone()))
+    }
+
+    pub fn width(&self) -> usize {
+        W
+    }
+
+    pub fn height(&self) -> usize {
+        H
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.rows.get(y)?.get(x)
+    }
+
+    // Every cell in reading order
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.rows.iter().flatten()
+    }
+
+    pub fn row(&self, y: usize) -> &[T; W] {
+        &self.rows[y]
+    }
+
+    // The positions of all eight cells around (x, y) which are in the grid,
+    // in reading order
+    pub fn neighbours_with_diagonals(&self, x: usize, y: usize)
+            -> impl Iterator<Item = (usize, usize)> {
+        let xs = x.saturating_sub(1)..(x + 2).min(W);
+        (y.saturating_sub(1)..(y + 2).min(H))
+            .flat_map(move |ny| xs.clone().map(move |nx| (nx, ny)))
+            .filter(move |&p| p != (x, y))
+    }
+}
+
+impl<T: Copy, const W: usize, const H: usize> FixedGrid<T, W, H> {
+    pub fn new(value: T) -> FixedGrid<T, W, H> {
+        FixedGrid { rows: [[value; W]; H] }
+    }
+}
+
+impl<T, const W: usize, const H: usize> Index<(usize, usize)> for FixedGrid<T, W, H> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        &self.rows[y][x]
+    }
+}
+
+impl<T, const W: usize, const H: usize> IndexMut<(usize, usize)> for FixedGrid<T, W, H> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        &mut self.rows[y][x]
+    }
+}
+
+// A map which only stores the cells which have been set, and keeps track of
+// the smallest rectangle which covers them all. Cells can't be removed, so
+// the bounds only ever grow
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    // The top left and bottom right corners, if there are any cells
+    bounds: Option<(Point, Point)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> SparseGrid<T> {
+        SparseGrid::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid { cells: HashMap::new(), bounds: None }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    fn extend_bounds(&mut self, p: Point) {
+        self.bounds = Some(match self.bounds {
+            None => (p, p),
+            Some((min, max)) => (Point::new(min.x.min(p.x), min.y.min(p.y)),
+                                 Point::new(max.x.max(p.x), max.y.max(p.y))),
+        });
+    }
+
+    // Set the cell at [p], giving what was there before
+    pub fn insert(&mut self, p: Point, value: T) -> Option<T> {
+        self.extend_bounds(p);
+        self.cells.insert(p, value)
+    }
+
+    // The cell at [p], which is set to [f]() first if it's empty
+    pub fn get_or_insert_with(&mut self, p: Point, f: impl FnOnce() -> T) -> &mut T {
+        self.extend_bounds(p);
+        self.cells.entry(p).or_insert_with(f)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    // The top left corner of the bounds
+    pub fn min(&self) -> Option<Point> {
+        self.bounds.map(|(min, _)| min)
+    }
+
+    // The bottom right corner of the bounds
+    pub fn max(&self) -> Option<Point> {
+        self.bounds.map(|(_, max)| max)
+    }
+
+    pub fn width(&self) -> usize {
+        self.bounds.map_or(0, |(min, max)| (max.x - min.x + 1) as usize)
+    }
+
+    pub fn height(&self) -> usize {
+        self.bounds.map_or(0, |(min, max)| (max.y - min.y + 1) as usize)
+    }
+
+    // Whether [p] is on the edge of the bounds
+    pub fn is_on_edge(&self, p: Point) -> bool {
+        self.bounds.is_some_and(|(min, max)| {
+            (p.x == min.x || p.x == max.x) && (min.y..=max.y).contains(&p.y)
+                || (p.y == min.y || p.y == max.y) && (min.x..=max.x).contains(&p.x)
+        })
+    }
+
+    // Every cell which has been set with its position, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&p, v)| (p, v))
+    }
+
+    // Every position within the bounds, whether it's been set or not, in
+    // reading order
+    pub fn positions(&self) -> impl Iterator<Item = Point> {
+        let (min, max) = self.bounds.unwrap_or((Point::ORIGIN, Point::new(-1, -1)));
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+    }
+
+    // The cells which have been set up, left, right and down of [p], in
+    // reading order
+    pub fn neighbours(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        p.neighbours().filter_map(move |n| self.get(n).map(|v| (n, v)))
+    }
+
+    // The cells which have been set in the eight positions around [p], in
+    // reading order
+    pub fn neighbours_with_diagonals(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        p.neighbours_with_diagonals().filter_map(move |n| self.get(n).map(|v| (n, v)))
+    }
+
+    // Display the bounds with [f] giving the character for each position,
+    // which is passed None for positions which haven't been set
+    pub fn display_with<F: Fn(Option<&T>) -> char>(&self, f: F) -> SparseGridDisplay<'_, T, F> {
+        SparseGridDisplay { grid: self, f }
+    }
+}
+
+impl<T> Index<Point> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        match self.get(p) {
+            Some(cell) => cell,
+            None => panic!("{} hasn't been set in the sparse grid", p),
+        }
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> SparseGrid<T> {
+        let mut grid = SparseGrid::new();
+        for (p, value) in iter {
+            grid.insert(p, value);
+        }
+        grid
+    }
+}
+
+pub struct SparseGridDisplay<'a, T, F> {
+    grid: &'a SparseGrid<T>,
+    f: F,
+}
+
+impl<'a, T, F: Fn(Option<&T>) -> char> fmt::Display for SparseGridDisplay<'a, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (min, max) = match self.grid.bounds {
+            Some(bounds) => bounds,
+            None => return Ok(()),
+        };
+        for y in min.y..=max.y {
+            let line: String = (min.x..=max.x)
+                .map(|x| (self.f)(self.grid.get(Point::new(x, y))))
+                .collect();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reading_order() {
+        let mut positions = vec![ReadingOrder(2, 1), ReadingOrder(0, 2), ReadingOrder(5, 0),
+                                 ReadingOrder(1, 1), ReadingOrder(0, 0)];
+        positions.sort();
+        assert_eq!(positions, vec![ReadingOrder(0, 0), ReadingOrder(5, 0), ReadingOrder(1, 1),
+                                   ReadingOrder(2, 1), ReadingOrder(0, 2)]);
+        // Points are ordered the same way
+        let mut points: Vec<_> = [(2, 1), (0, 2), (5, 0), (-1, 1), (0, -3)].iter()
+            .map(|&p| Point::from(p))
+            .collect();
+        points.sort();
+        assert_eq!(points, vec![Point::new(0, -3), Point::new(5, 0), Point::new(-1, 1),
+                                Point::new(2, 1), Point::new(0, 2)]);
+    }
+
+    #[test]
+    fn iter_reading_order() {
+        let grid: Grid<char> = "ab\ncd\nef".parse().unwrap();
+        let cells: Vec<_> = grid.iter_reading_order().collect();
+        assert_eq!(cells, vec![((0, 0), &'a'), ((1, 0), &'b'), ((0, 1), &'c'),
+                               ((1, 1), &'d'), ((0, 2), &'e'), ((1, 2), &'f')]);
+        assert!(cells.windows(2).all(|w| ReadingOrder(w[0].0 .0, w[0].0 .1)
+                                         < ReadingOrder(w[1].0 .0, w[1].0 .1)));
+    }
+
+    #[test]
+    fn fixed_grids() {
+        let grid: Grid<char> = "abc\ndef".parse().unwrap();
+        let fixed: FixedGrid<char, 3, 2> = FixedGrid::from_grid(&grid).unwrap();
+        assert_eq!((fixed[(2, 0)], fixed[(0, 1)], fixed.get(3, 0)), ('c', 'd', None));
+        assert_eq!(fixed.iter().collect::<String>(), "abcdef");
+        assert!(FixedGrid::<char, 2, 3>::from_grid(&grid).is_none());
+
+        let fixed: FixedGrid<(), 3, 3> = FixedGrid::new(());
+        let unfixed = Grid::new(3, 3, ());
+        for (x, y) in [(0, 0), (1, 1), (2, 1), (2, 2)].iter().copied() {
+            assert_eq!(fixed.neighbours_with_diagonals(x, y).collect::<Vec<_>>(),
+                       unfixed.neighbours_with_diagonals(x, y).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn neighbours_in_reading_order() {
+        let grid = Grid::new(3, 3, ());
+        let neighbours: Vec<_> = grid.neighbours(1, 1).collect();
+        assert_eq!(neighbours, vec![(1, 0), (0, 1), (2, 1), (1, 2)]);
+        let mut sorted = neighbours.clone();
+        sorted.sort_by_key(|&(x, y)| ReadingOrder(x, y));
+        assert_eq!(neighbours, sorted);
+        assert_eq!(grid.neighbours(0, 0).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+    }
+
+    #[test]
+    fn sparse_grid_bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!((grid.min(), grid.width(), grid.height()), (None, 0, 0));
+        assert_eq!(grid.positions().count(), 0);
+
+        grid.insert(Point::new(3, -2), 'a');
+        grid.insert(Point::new(-1, 4), 'b');
+        *grid.get_or_insert_with(Point::new(0, 0), || 'c') = 'd';
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid.min(), Some(Point::new(-1, -2)));
+        assert_eq!(grid.max(), Some(Point::new(3, 4)));
+        assert_eq!((grid.width(), grid.height()), (5, 7));
+        assert_eq!(grid[Point::ORIGIN], 'd');
+        assert_eq!(grid.positions().count(), 35);
+        assert_eq!(grid.positions().next(), Some(Point::new(-1, -2)));
+
+        assert!(grid.is_on_edge(Point::new(-1, 1)));
+        assert!(grid.is_on_edge(Point::new(2, 4)));
+        assert!(!grid.is_on_edge(Point::new(2, 3)));
+        assert!(!grid.is_on_edge(Point::new(-1, 5)));
+    }
+
+    #[test]
+    fn sparse_grid_neighbours_and_display() {
+        let grid: SparseGrid<char> = "#..\n.##\n..#".parse::<Grid<char>>().unwrap()
+            .iter_reading_order()
+            .filter(|&(_, &c)| c == '#')
+            .map(|((x, y), &c)| (Point::new(x as i32 + 10, y as i32 - 5), c))
+            .collect();
+        let neighbours: Vec<_> = grid.neighbours(Point::new(11, -4)).map(|(p, _)| p).collect();
+        assert_eq!(neighbours, vec![Point::new(12, -4)]);
+        assert_eq!(grid.neighbours_with_diagonals(Point::new(11, -4)).count(), 3);
+        assert_eq!(grid.display_with(|c| if c.is_some() { '#' } else { '.' }).to_string(),
+                   "#..\n.##\n..#\n");
+    }
+}
@@ -0,0 +1,19 @@
+// The hash maps and sets used by the days which hash a lot, like the states
+// days 12 and 18 have seen while looking for a cycle, or the searches' visited
+// nodes. With the fast-hash feature they use FxHash, which is several times
+// quicker than the standard library's SipHash for small keys like points and
+// grids of bytes. It doesn't resist keys chosen to collide, but puzzle inputs
+// aren't trying to. Either way they're made with default() rather than new()
+
+#[cfg(feature = "fast-hash")]
+pub type BuildHasher = rustc_hash::FxBuildHasher;
+#[cfg(not(feature = "fast-hash"))]
+pub type BuildHasher = std::collections::hash_map::RandomState;
+
+pub type HashMap<K, V> = std::collections::HashMap<K, V, BuildHasher>;
+pub type HashSet<T> = std::collections::HashSet<T, BuildHasher>;
+
+// Whether the fast hasher is being used, for benchmarks to say
+pub fn is_fast() -> bool {
+    cfg!(feature = "fast-hash")
+}
@@ -0,0 +1,66 @@
+// Code shared between the solutions for each day
+
+use std::fs;
+use std::io::{self, Read};
+
+pub mod automaton;
+pub mod bisect;
+pub mod bitset;
+pub mod combin;
+pub mod cycle;
+pub mod direction;
+pub mod dsu;
+pub mod elfcode;
+mod error;
+pub mod fixtures;
+pub mod grid;
+pub mod hash;
+pub mod ocr;
+pub mod parse;
+pub mod point;
+pub mod pq;
+pub mod ranges;
+pub mod ring;
+pub mod search;
+pub mod simulation;
+pub mod snapshot;
+pub mod viz;
+
+pub use error::AocError;
+
+// Each day's library has a part1 and part2 function with this signature. They
+// take the puzzle input and return the answer. Answers come in all sorts of
+// shapes (numbers, coordinates, words) so they're always given as a String
+pub type Solver = fn(&str) -> Result<String, AocError>;
+
+// Read the puzzle input. The path can be given as the first command line
+// argument, otherwise input.txt in the current directory is used
+pub fn read_input() -> Result<String, AocError> {
+    let path = std::env::args().nth(1);
+    read_input_from(path.as_deref(), "input.txt")
+}
+
+// Read the puzzle input from [path], where - means stdin, or from
+// [default_path] if there's no [path]. Stdin is only read when it's asked for:
+// under ssh, CI or an IDE it can be left open with nothing ever written to it,
+// and waiting for it to close would wait forever
+pub fn read_input_from(path: Option<&str>, default_path: &str)
+        -> Result<String, AocError> {
+    match path {
+        Some("-") => read_stdin(),
+        Some(path) => read_file(path),
+        None => read_file(default_path),
+    }
+}
+
+pub fn read_file(path: &str) -> Result<String, AocError> {
+    fs::read_to_string(path)
+        .map_err(|source| AocError::Io { path: path.to_string(), source })
+}
+
+fn read_stdin() -> Result<String, AocError> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)
+        .map_err(|source| AocError::Io { path: "stdin".to_string(), source })?;
+    Ok(input)
+}
@@ -0,0 +1,136 @@
+// Reading the capital letters some puzzles draw as their answer, like the
+// message in the stars on day 10. The puzzles use two fonts, one 6 pixels
+// high and one 10 pixels high. Only the letters which have turned up in
+// puzzles are known
+
+// Each glyph is its letter followed by its rows, trimmed of blank columns
+const FONT_6: &[(char, [&str; 6])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+const FONT_10: &[(char, [&str; 10])] = &[
+    ('A', ["..##..", ".#..#.", "#....#", "#....#", "#....#",
+           "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', ["#####.", "#....#", "#....#", "#....#", "#####.",
+           "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', [".####.", "#....#", "#.....", "#.....", "#.....",
+           "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', ["######", "#.....", "#.....", "#.....", "#####.",
+           "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', ["######", "#.....", "#.....", "#.....", "#####.",
+           "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', [".####.", "#....#", "#.....", "#.....", "#.....",
+           "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', ["#....#", "#....#", "#....#", "#....#", "######",
+           "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', ["...###", "....#.", "....#.", "....#.", "....#.",
+           "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', ["#....#", "#...#.", "#..#..", "#.#...", "##....",
+           "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', ["#.....", "#.....", "#.....", "#.....", "#.....",
+           "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', ["#....#", "##...#", "##...#", "#.#..#", "#.#..#",
+           "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', ["#####.", "#....#", "#....#", "#....#", "#####.",
+           "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', ["#####.", "#....#", "#....#", "#....#", "#####.",
+           "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..",
+           "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', ["######", ".....#", ".....#", "....#.", "...#..",
+           "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+fn find_glyph<const H: usize>(font: &[(char, [&str; H])], glyph: &[String]) -> Option<char> {
+    font.iter().find(|(_, rows)| rows.iter().eq(glyph.iter())).map(|&(c, _)| c)
+}
+
+// Read the letters drawn in [image] with # for lit pixels and anything else,
+// usually . or a space, for unlit ones. Blank rows and columns around the
+// letters are ignored. Gives the reason if the letters can't all be read
+pub fn decode(image: &str) -> Result<String, String> {
+    let rows: Vec<Vec<bool>> = image.lines()
+        .map(|line| line.chars().map(|c| c == '#').collect())
+        .filter(|row: &Vec<bool>| row.contains(&true))
+        .collect();
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let lit = |x: usize, y: usize| rows[y].get(x).copied().unwrap_or(false);
+
+    // The letters are separated by blank columns
+    let mut glyphs = vec![];
+    let mut x = 0;
+    while x < width {
+        if !(0..rows.len()).any(|y| lit(x, y)) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < width && (0..rows.len()).any(|y| lit(x, y)) {
+            x += 1;
+        }
+        let glyph: Vec<String> = (0..rows.len()).map(|y| {
+            (start..x).map(|x| if lit(x, y) { '#' } else { '.' }).collect()
+        }).collect();
+        glyphs.push((start, glyph));
+    }
+
+    if glyphs.is_empty() {
+        return Err("there are no letters".to_string());
+    }
+    glyphs.iter().map(|(start, glyph)| {
+        let letter = match rows.len() {
+            6 => find_glyph(FONT_6, glyph),
+            10 => find_glyph(FONT_10, glyph),
+            height => return Err(format!("the letters are {} pixels high, not 6 or 10", height)),
+        };
+        letter.ok_or_else(|| format!("the letter at column {} isn't recognised", start + 1))
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_letters() {
+        let image = "\
+.##..###..#..#.
+#..#.#..#.#..#.
+#..#.###..####.
+####.#..#.#..#.
+#..#.#..#.#..#.
+#..#.###..#..#.";
+        assert_eq!(decode(image), Ok("ABH".to_string()));
+    }
+
+    #[test]
+    fn large_letters_with_padding() {
+        let rows = FONT_10.iter().find(|(c, _)| *c == 'Z').unwrap().1;
+        let image: Vec<_> = rows.iter().map(|r| format!("  {}  ", r.replace('.', " "))).collect();
+        let image = format!("\n{}\n\n", image.join("\n"));
+        assert_eq!(decode(&image), Ok("Z".to_string()));
+    }
+
+    #[test]
+    fn unknown_letter() {
+        let image = "#.#\n.#.\n#.#\n#.#\n.#.\n#.#";
+        assert!(decode(image).is_err());
+    }
+}
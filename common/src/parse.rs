@@ -0,0 +1,160 @@
+// Helpers for picking apart puzzle input lines. Errors are plain strings
+// describing what was wrong with the line, which callers turn into an
+// AocError with the line number
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use regex::{Captures, Regex};
+
+// Parse [s], ignoring surrounding whitespace
+pub fn value<T: FromStr>(s: &str) -> Result<T, String>
+        where T::Err: Display {
+    s.trim().parse().map_err(|e| format!("'{}' isn't valid: {}", s.trim(), e))
+}
+
+// The text of every integer in [line], e.g. "3" and "-2" for "position=<3, -2>",
+// whatever punctuation is around them. A - is only taken as a sign when it's
+// right before a digit and not right after one, so ranges like "2-5" give "2"
+// and "5"
+pub fn int_tokens(line: &str) -> impl Iterator<Item = &str> {
+    let bytes = line.as_bytes();
+    let mut i = 0;
+    std::iter::from_fn(move || {
+        while i < bytes.len() {
+            let negative = bytes[i] == b'-' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+                && (i == 0 || !bytes[i - 1].is_ascii_digit());
+            if negative || bytes[i].is_ascii_digit() {
+                let start = i;
+                i += 1;
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+                return Some(&line[start..i]);
+            }
+            i += 1;
+        }
+        None
+    })
+}
+
+// Every integer in [line], e.g. [3, -2] for "position=<3, -2>". Integers too
+// big for [T] are skipped
+pub fn ints_in_line<T: FromStr>(line: &str) -> Vec<T> {
+    int_tokens(line).filter_map(|token| token.parse().ok()).collect()
+}
+
+// Exactly [N] integers from [line], e.g. for "#1 @ 1,3: 4x4" with N = 5. The
+// integers are counted first so that they can be parsed straight into the
+// array, rather than into a Vec which is thrown away
+pub fn fixed_ints<T: FromStr, const N: usize>(line: &str) -> Result<[T; N], String>
+        where T::Err: Display {
+    let found = int_tokens(line).count();
+    if found != N {
+        return Err(format!("expected {} number{} but found {}",
+                           N, if N == 1 { "" } else { "s" }, found));
+    }
+    let mut tokens = int_tokens(line);
+    let mut error = None;
+    let ints: [Option<T>; N] = std::array::from_fn(|_| {
+        value(tokens.next()?).map_err(|e| { error.get_or_insert(e); }).ok()
+    });
+    match error {
+        Some(e) => Err(e),
+        None => Ok(ints.map(|int| int.expect("every token was parsed"))),
+    }
+}
+
+// Split [line] on [separator] into exactly [N] fields, which are slices of
+// [line]
+pub fn fields<'a, const N: usize>(line: &'a str, separator: &str)
+        -> Result<[&'a str; N], String> {
+    let mut split = line.split(separator);
+    let mut fields = [""; N];
+    let mut found = 0;
+    for field in fields.iter_mut() {
+        match split.next() {
+            Some(f) => *field = f,
+            None => break,
+        }
+        found += 1;
+    }
+    found += split.count();
+    if found != N {
+        return Err(format!("expected {} fields separated by '{}' but found {}",
+                           N, separator, found));
+    }
+    Ok(fields)
+}
+
+// A tuple which can be built from a regex's capture groups, parsing each one
+pub trait FromCaptures: Sized {
+    fn from_captures(captures: &Captures) -> Result<Self, String>;
+}
+
+fn group<T: FromStr>(captures: &Captures, i: usize) -> Result<T, String>
+        where T::Err: Display {
+    let group = captures.get(i).ok_or_else(|| format!("group {} didn't match", i))?;
+    value(group.as_str())
+}
+
+macro_rules! impl_from_captures {
+    ($($t:ident $i:literal),+) => {
+        impl<$($t: FromStr),+> FromCaptures for ($($t,)+)
+                where $($t::Err: Display),+ {
+            fn from_captures(captures: &Captures) -> Result<Self, String> {
+                Ok(($(group::<$t>(captures, $i)?,)+))
+            }
+        }
+    };
+}
+
+impl_from_captures!(A 1);
+impl_from_captures!(A 1, B 2);
+impl_from_captures!(A 1, B 2, C 3);
+impl_from_captures!(A 1, B 2, C 3, D 4);
+impl_from_captures!(A 1, B 2, C 3, D 4, E 5);
+impl_from_captures!(A 1, B 2, C 3, D 4, E 5, F 6);
+
+// Match [line] against [re] and parse its capture groups into a tuple, e.g.
+// (i32, String) from r"^(\d+) (\w+)$"
+pub fn captures<T: FromCaptures>(re: &Regex, line: &str) -> Result<T, String> {
+    let captures = re.captures(line)
+        .ok_or_else(|| format!("'{}' doesn't match the expected format", line))?;
+    T::from_captures(&captures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ints_whatever_the_punctuation() {
+        assert_eq!(ints_in_line::<i64>("pos=<1,-2,3>, r=4"), vec![1, -2, 3, 4]);
+        assert_eq!(ints_in_line::<i32>("position=< 9,  1> velocity=<-10, -2>"),
+                   vec![9, 1, -10, -2]);
+        assert_eq!(ints_in_line::<i32>("-1,0,-3,12"), vec![-1, 0, -3, 12]);
+        assert_eq!(ints_in_line::<i32>("#1 @ 1,3: 4x4"), vec![1, 1, 3, 4, 4]);
+        assert_eq!(ints_in_line::<i32>("y=2..7"), vec![2, 7]);
+        assert_eq!(ints_in_line::<i32>("2-5 - -x --3"), vec![2, 5, -3]);
+        assert!(ints_in_line::<i32>("no numbers here").is_empty());
+    }
+
+    #[test]
+    fn fixed_number_of_ints() {
+        assert_eq!(fixed_ints::<i64, 4>("pos=<1,-2,3>, r=4"), Ok([1, -2, 3, 4]));
+        assert_eq!(fixed_ints::<i32, 3>("1,2"), Err("expected 3 numbers but found 2".to_string()));
+        assert!(fixed_ints::<u8, 1>("r=300").unwrap_err().contains("'300' isn't valid"));
+        assert!(fixed_ints::<u8, 1>("r=-3").is_err());
+    }
+
+    #[test]
+    fn fixed_number_of_fields() {
+        assert_eq!(fields::<3>("addi 1 2", " "), Ok(["addi", "1", "2"]));
+        assert_eq!(fields::<2>("a b c", " "),
+                   Err("expected 2 fields separated by ' ' but found 3".to_string()));
+        assert_eq!(fields::<3>("a,b", ","),
+                   Err("expected 3 fields separated by ',' but found 2".to_string()));
+        assert!(fixed_ints::<u8, 2>("300 400").unwrap_err().contains("'300'"));
+    }
+}
@@ -0,0 +1,117 @@
+// A position (or an offset) on a 2D map. As with Grid, x increases to the
+// right and y increases downwards
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::grid::ReadingOrder;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i32, y: i32) -> Point {
+        Point { x, y }
+    }
+
+    // The Manhattan length of the point, i.e. its distance from the origin
+    pub fn manhattan(self) -> i32 {
+        self.x.abs() + self.y.abs()
+    }
+
+    // The Manhattan distance between two points
+    pub fn distance(self, other: Point) -> i32 {
+        (self - other).manhattan()
+    }
+
+    // The four points up, left, right and down of this one. That's reading
+    // order, which many puzzles use to break ties
+    pub fn neighbours(self) -> impl Iterator<Item = Point> {
+        self.offsets(&[(0, -1), (-1, 0), (1, 0), (0, 1)])
+    }
+
+    // All eight points around this one, in reading order
+    pub fn neighbours_with_diagonals(self) -> impl Iterator<Item = Point> {
+        self.offsets(&[(-1, -1), (0, -1), (1, -1), (-1, 0),
+                       (1, 0), (-1, 1), (0, 1), (1, 1)])
+    }
+
+    fn offsets(self, offsets: &'static [(i32, i32)]) -> impl Iterator<Item = Point> {
+        offsets.iter().map(move |&(dx, dy)| Point::new(self.x + dx, self.y + dy))
+    }
+}
+
+impl From<(i32, i32)> for Point {
+    fn from((x, y): (i32, i32)) -> Point {
+        Point { x, y }
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+// Points are ordered in reading order: top to bottom, then left to right
+impl Ord for Point {
+    fn cmp(&self, other: &Point) -> Ordering {
+        ReadingOrder(self.x, self.y).cmp(&ReadingOrder(other.x, other.y))
+    }
+}
+
+impl PartialOrd for Point {
+    fn partial_cmp(&self, other: &Point) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, n: i32) -> Point {
+        Point::new(self.x * n, self.y * n)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
@@ -0,0 +1,172 @@
+// A min-priority queue of indexes, e.g. of nodes in a graph, which knows where
+// each index is in the heap so that an index's priority can be lowered in
+// place. With std's BinaryHeap the usual trick is to push the index again and
+// skip the stale entries when they're popped, which lets the heap grow much
+// bigger than the number of indexes
+
+// Marks an index which isn't in the heap
+const ABSENT: usize = usize::MAX;
+
+#[derive(Debug, Clone)]
+pub struct IndexedHeap<P> {
+    heap: Vec<(usize, P)>,
+    // Where each index is in [heap]. Grows as bigger indexes are pushed
+    positions: Vec<usize>,
+}
+
+impl<P: Ord> Default for IndexedHeap<P> {
+    fn default() -> IndexedHeap<P> {
+        IndexedHeap::new()
+    }
+}
+
+impl<P: Ord> IndexedHeap<P> {
+    pub fn new() -> IndexedHeap<P> {
+        IndexedHeap { heap: vec![], positions: vec![] }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    fn position(&self, index: usize) -> Option<usize> {
+        self.positions.get(index).copied().filter(|&p| p != ABSENT)
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        self.position(index).is_some()
+    }
+
+    pub fn priority(&self, index: usize) -> Option<&P> {
+        self.position(index).map(|p| &self.heap[p].1)
+    }
+
+    // The index with the lowest priority
+    pub fn peek(&self) -> Option<(usize, &P)> {
+        self.heap.first().map(|(i, p)| (*i, p))
+    }
+
+    // Add [index] with [priority], or lower its priority if it's already
+    // queued with a higher one. Gives whether anything changed
+    pub fn push(&mut self, index: usize, priority: P) -> bool {
+        match self.position(index) {
+            Some(p) => {
+                if priority >= self.heap[p].1 {
+                    return false;
+                }
+                self.heap[p].1 = priority;
+                self.sift_up(p);
+            },
+            None => {
+                if index >= self.positions.len() {
+                    self.positions.resize(index + 1, ABSENT);
+                }
+                self.positions[index] = self.heap.len();
+                self.heap.push((index, priority));
+                self.sift_up(self.heap.len() - 1);
+            },
+        }
+        true
+    }
+
+    // Remove the index with the lowest priority
+    pub fn pop(&mut self) -> Option<(usize, P)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let last = self.heap.len() - 1;
+        self.swap(0, last);
+        let (index, priority) = self.heap.pop()?;
+        self.positions[index] = ABSENT;
+        if !self.heap.is_empty() {
+            self.sift_down(0);
+        }
+        Some((index, priority))
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.positions[self.heap[a].0] = a;
+        self.positions[self.heap[b].0] = b;
+    }
+
+    fn sift_up(&mut self, mut p: usize) {
+        while p > 0 {
+            let parent = (p - 1) / 2;
+            if self.heap[p].1 >= self.heap[parent].1 {
+                break;
+            }
+            self.swap(p, parent);
+            p = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut p: usize) {
+        loop {
+            let mut smallest = p;
+            for &child in [2 * p + 1, 2 * p + 2].iter() {
+                if child < self.heap.len() && self.heap[child].1 < self.heap[smallest].1 {
+                    smallest = child;
+                }
+            }
+            if smallest == p {
+                break;
+            }
+            self.swap(p, smallest);
+            p = smallest;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pops_in_priority_order() {
+        let mut heap = IndexedHeap::new();
+        for &(index, priority) in [(2, 'c'), (0, 'a'), (4, 'e'), (1, 'b'), (3, 'd')].iter() {
+            heap.push(index, priority);
+        }
+        let popped: Vec<_> = std::iter::from_fn(|| heap.pop()).map(|(i, _)| i).collect();
+        assert_eq!(popped, vec![0, 1, 2, 3, 4]);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn lowers_priorities() {
+        let mut heap = IndexedHeap::new();
+        heap.push(0, 10);
+        heap.push(1, 20);
+        assert!(heap.push(1, 5));
+        assert_eq!(heap.len(), 2);
+        assert_eq!(heap.priority(1), Some(&5));
+        assert_eq!(heap.pop(), Some((1, 5)));
+        assert_eq!(heap.pop(), Some((0, 10)));
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn ignores_higher_priorities() {
+        let mut heap = IndexedHeap::new();
+        heap.push(1, 3);
+        assert!(!heap.push(1, 4));
+        assert!(!heap.push(1, 3));
+        assert_eq!(heap.peek(), Some((1, &3)));
+    }
+
+    #[test]
+    fn indexes_can_be_pushed_again_after_popping() {
+        let mut heap = IndexedHeap::new();
+        heap.push(100, 1);
+        heap.pop();
+        assert!(!heap.contains(100));
+        assert!(!heap.contains(1000));
+        assert!(heap.push(100, 2));
+        assert_eq!(heap.pop(), Some((100, 2)));
+    }
+}
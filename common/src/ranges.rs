@@ -0,0 +1,139 @@
+// A set of integers stored as the ranges they make up, e.g. the columns a row
+// of clay covers on day 17. Overlapping and touching ranges are merged as
+// they're inserted, so the ranges are always sorted and separated by gaps
+
+use std::iter::FromIterator;
+use std::ops::Range;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RangeSet {
+    // Half-open like Range, so a..b holds a to b - 1
+    ranges: Vec<(i64, i64)>,
+}
+
+impl RangeSet {
+    pub fn new() -> RangeSet {
+        RangeSet { ranges: vec![] }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // Add every value in [range], merging it with any ranges it overlaps or
+    // touches
+    pub fn insert(&mut self, range: Range<i64>) {
+        if range.is_empty() {
+            return;
+        }
+        // The ranges which end before this one starts, with a gap, and the
+        // ones which start after it ends are unaffected
+        let first = self.ranges.partition_point(|&(_, end)| end < range.start);
+        let last = self.ranges.partition_point(|&(start, _)| start <= range.end);
+        let mut merged = (range.start, range.end);
+        if first < last {
+            merged.0 = merged.0.min(self.ranges[first].0);
+            merged.1 = merged.1.max(self.ranges[last - 1].1);
+        }
+        self.ranges.splice(first..last, std::iter::once(merged));
+    }
+
+    // The number of values in the set
+    pub fn total_len(&self) -> i64 {
+        self.ranges.iter().map(|&(start, end)| end - start).sum()
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.range_containing(value).is_some()
+    }
+
+    // Whether every value in [range] is in the set
+    pub fn contains_range(&self, range: Range<i64>) -> bool {
+        range.is_empty() || self.range_containing(range.start)
+            .is_some_and(|r| range.end <= r.end)
+    }
+
+    // The range in the set which [value] is part of
+    pub fn range_containing(&self, value: i64) -> Option<Range<i64>> {
+        let i = self.ranges.partition_point(|&(_, end)| end <= value);
+        self.ranges.get(i).filter(|&&(start, _)| start <= value).map(|&(start, end)| start..end)
+    }
+
+    // The separate ranges making up the set, in order
+    pub fn ranges(&self) -> impl Iterator<Item = Range<i64>> + '_ {
+        self.ranges.iter().map(|&(start, end)| start..end)
+    }
+}
+
+impl FromIterator<Range<i64>> for RangeSet {
+    fn from_iter<I: IntoIterator<Item = Range<i64>>>(iter: I) -> RangeSet {
+        let mut set = RangeSet::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn merges_overlapping_and_touching_ranges() {
+        let set: RangeSet = vec![10..12, 0..3, 5..7, 2..4, 7..8, 20..20].into_iter().collect();
+        assert_eq!(set.ranges().collect::<Vec<_>>(), vec![0..4, 5..8, 10..12]);
+        assert_eq!(set.total_len(), 9);
+        assert!(set.contains(3) && !set.contains(4) && set.contains(5));
+        assert!(set.contains_range(5..8) && !set.contains_range(3..6));
+        assert_eq!(set.range_containing(11), Some(10..12));
+        assert_eq!(set.range_containing(12), None);
+    }
+
+    #[test]
+    fn insert_covering_everything() {
+        let mut set: RangeSet = vec![-5..-3, 0..1, 4..9].into_iter().collect();
+        set.insert(-10..10);
+        assert_eq!(set.ranges().collect::<Vec<_>>(), vec![-10..10]);
+    }
+
+    fn small_ranges() -> impl Strategy<Value = Vec<Range<i64>>> {
+        prop::collection::vec((-50i64..50, 0i64..15).prop_map(|(start, len)| start..start + len),
+                              0..20)
+    }
+
+    proptest! {
+        // The set holds exactly the values in the ranges inserted, however
+        // they overlap
+        #[test]
+        fn matches_a_set_of_values(ranges in small_ranges()) {
+            let set: RangeSet = ranges.iter().cloned().collect();
+            let values: BTreeSet<i64> = ranges.into_iter().flatten().collect();
+            prop_assert_eq!(set.total_len(), values.len() as i64);
+            for v in -60..70 {
+                prop_assert_eq!(set.contains(v), values.contains(&v));
+            }
+        }
+
+        // The ranges are sorted, non-empty and have gaps between them
+        #[test]
+        fn ranges_are_fully_merged(ranges in small_ranges()) {
+            let set: RangeSet = ranges.into_iter().collect();
+            let merged: Vec<_> = set.ranges().collect();
+            prop_assert!(merged.iter().all(|r| !r.is_empty()));
+            prop_assert!(merged.windows(2).all(|w| w[0].end < w[1].start));
+        }
+
+        // The order the ranges are inserted in doesn't matter
+        #[test]
+        fn insertion_order_is_irrelevant(ranges in small_ranges()) {
+            let forwards: RangeSet = ranges.iter().cloned().collect();
+            let backwards: RangeSet = ranges.into_iter().rev().collect();
+            prop_assert_eq!(forwards, backwards);
+        }
+    }
+}
@@ -0,0 +1,72 @@
+// A circle of values with a current position, which can be walked around and
+// inserted into or removed from at the current position in O(1) per step.
+//
+// The current value is kept at the back of a VecDeque, so walking the circle
+// is a rotation of the deque. Inserting into the middle of a Vec (or
+// VecDeque) instead moves all the subsequent values, which is far too slow
+// for circles with millions of values like day 9's marbles
+
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone, Default)]
+pub struct Ring<T> {
+    values: VecDeque<T>,
+}
+
+impl<T> Ring<T> {
+    pub fn new() -> Ring<T> {
+        Ring { values: VecDeque::new() }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Ring<T> {
+        Ring { values: VecDeque::with_capacity(capacity) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn current(&self) -> Option<&T> {
+        self.values.back()
+    }
+
+    // Make the value [n] steps clockwise of the current one current
+    pub fn move_clockwise(&mut self, n: usize) {
+        if !self.values.is_empty() {
+            let n = n % self.values.len();
+            self.values.rotate_left(n);
+        }
+    }
+
+    // Make the value [n] steps counter-clockwise of the current one current
+    pub fn move_counter_clockwise(&mut self, n: usize) {
+        if !self.values.is_empty() {
+            let n = n % self.values.len();
+            self.values.rotate_right(n);
+        }
+    }
+
+    // Insert [value] clockwise of the current value. The new value becomes
+    // the current one
+    pub fn insert_after_current(&mut self, value: T) {
+        self.values.push_back(value);
+    }
+
+    // Remove the current value. The value clockwise of it becomes the
+    // current one
+    pub fn remove_current(&mut self) -> Option<T> {
+        let removed = self.values.pop_back()?;
+        self.move_clockwise(1);
+        Some(removed)
+    }
+
+    // Every value, going clockwise from the one after the current value and
+    // ending with the current value
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.values.iter()
+    }
+}
@@ -0,0 +1,279 @@
+// Shortest path searches over anything which can say where you can get to in
+// one step from a node. BFS is for when every step costs the same, Dijkstra
+// and A* for when they don't
+
+use std::collections::VecDeque;
+use std::collections::hash_map::Entry;
+use std::hash::Hash;
+
+use crate::hash::HashMap;
+use crate::pq::IndexedHeap;
+
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    // The nodes one step from [node], each with the cost of taking that step.
+    // BFS ignores the costs, and explores the nodes in the order given here,
+    // so that order decides between paths of the same length
+    fn edges(&self, node: &Self::Node) -> Vec<(Self::Node, usize)>;
+}
+
+// Follow the parents back from [end] to get the path from the start
+fn path_to<N: Clone>(nodes: &[N], parents: &[Option<usize>], end: usize) -> Vec<N> {
+    let mut path = vec![nodes[end].clone()];
+    let mut i = end;
+    while let Some(parent) = parents[i] {
+        path.push(nodes[parent].clone());
+        i = parent;
+    }
+    path.reverse();
+    path
+}
+
+// The fewest steps from [start] to every node which can be reached from it
+pub fn bfs_distances<G: Graph>(graph: &G, start: &G::Node) -> HashMap<G::Node, usize> {
+    let mut distances = HashMap::default();
+    let mut queue = VecDeque::new();
+    distances.insert(start.clone(), 0);
+    queue.push_back(start.clone());
+
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[&node];
+        for (next, _) in graph.edges(&node) {
+            if let Entry::Vacant(e) = distances.entry(next) {
+                queue.push_back(e.key().clone());
+                e.insert(distance + 1);
+            }
+        }
+    }
+    distances
+}
+
+// A shortest path from [start] to a node where [is_goal] is true, including
+// both ends. Of the shortest paths, the one found first by following the
+// edges in the order the graph gives them is used
+pub fn bfs<G: Graph>(graph: &G, start: &G::Node, is_goal: impl Fn(&G::Node) -> bool)
+        -> Option<Vec<G::Node>> {
+    let mut nodes = vec![start.clone()];
+    let mut parents = vec![None];
+    let mut seen = HashMap::default();
+    seen.insert(start.clone(), 0);
+
+    let mut i = 0;
+    while i < nodes.len() {
+        if is_goal(&nodes[i]) {
+            return Some(path_to(&nodes, &parents, i));
+        }
+        for (next, _) in graph.edges(&nodes[i]) {
+            if let Entry::Vacant(e) = seen.entry(next) {
+                nodes.push(e.key().clone());
+                parents.push(Some(i));
+                e.insert(nodes.len() - 1);
+            }
+        }
+        i += 1;
+    }
+    None
+}
+
+// The closest node to [start] where [is_goal] is true, and how many steps away
+// it is. Ties between goals the same distance away are broken by picking the
+// one with the smallest [key], e.g. the first in reading order
+pub fn bfs_nearest_by_key<G: Graph, K: Ord>(graph: &G, start: &G::Node,
+                                            is_goal: impl Fn(&G::Node) -> bool,
+                                            key: impl Fn(&G::Node) -> K)
+        -> Option<(G::Node, usize)> {
+    let mut seen = HashMap::default();
+    seen.insert(start.clone(), ());
+    let mut level = vec![start.clone()];
+    let mut distance = 0;
+
+    // Search a whole level at a time, so that every goal at the smallest
+    // distance is found before picking one
+    while !level.is_empty() {
+        if let Some(goal) = level.iter().filter(|n| is_goal(n)).min_by_key(|n| key(n)) {
+            return Some((goal.clone(), distance));
+        }
+
+        let mut next_level = vec![];
+        for node in &level {
+            for (next, _) in graph.edges(node) {
+                if let Entry::Vacant(e) = seen.entry(next) {
+                    next_level.push(e.key().clone());
+                    e.insert(());
+                }
+            }
+        }
+        level = next_level;
+        distance += 1;
+    }
+    None
+}
+
+// The cheapest path from [start] to a node where [is_goal] is true, including
+// both ends, and its cost
+pub fn dijkstra<G: Graph>(graph: &G, start: &G::Node, is_goal: impl Fn(&G::Node) -> bool)
+        -> Option<(Vec<G::Node>, usize)> {
+    astar(graph, start, |_| 0, is_goal)
+}
+
+// As for dijkstra, but guided towards the goal by [heuristic], which must never
+// overestimate the remaining cost
+pub fn astar<G: Graph>(graph: &G, start: &G::Node, heuristic: impl Fn(&G::Node) -> usize,
+                       is_goal: impl Fn(&G::Node) -> bool)
+        -> Option<(Vec<G::Node>, usize)> {
+    // Nodes are referred to by their index in [nodes], so the heap doesn't
+    // need them to be Ord or to clone them
+    let mut nodes = vec![start.clone()];
+    let mut parents = vec![None];
+    let mut costs = vec![0];
+    let mut indexes = HashMap::default();
+    indexes.insert(start.clone(), 0);
+
+    // Ordered by the estimated total cost, and then the cost so far. Finding
+    // a cheaper way to a queued node lowers its priority in place
+    let mut heap = IndexedHeap::new();
+    heap.push(0, (heuristic(start), 0));
+
+    while let Some((i, (_, cost))) = heap.pop() {
+        if is_goal(&nodes[i]) {
+            return Some((path_to(&nodes, &parents, i), cost));
+        }
+
+        for (next, step) in graph.edges(&nodes[i]) {
+            let next_cost = cost + step;
+            let j = match indexes.entry(next) {
+                Entry::Vacant(e) => {
+                    nodes.push(e.key().clone());
+                    parents.push(Some(i));
+                    costs.push(next_cost);
+                    *e.insert(nodes.len() - 1)
+                },
+                Entry::Occupied(e) => {
+                    let j = *e.get();
+                    if next_cost >= costs[j] {
+                        continue;
+                    }
+                    parents[j] = Some(i);
+                    costs[j] = next_cost;
+                    j
+                },
+            };
+            heap.push(j, (next_cost + heuristic(&nodes[j]), next_cost));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point::Point;
+
+    // Each node's edges, as (node, cost)
+    struct Edges(Vec<Vec<(usize, usize)>>);
+
+    impl Graph for Edges {
+        type Node = usize;
+
+        fn edges(&self, node: &usize) -> Vec<(usize, usize)> {
+            self.0[*node].clone()
+        }
+    }
+
+    // Two ways from 0 to 3, the one through 1 cheap to start with but dearer
+    // in total. 5 leads to 0 but can't be reached from it
+    fn diamond() -> Edges {
+        Edges(vec![
+            vec![(1, 1), (2, 5)],
+            vec![(3, 10)],
+            vec![(3, 1)],
+            vec![(4, 1)],
+            vec![],
+            vec![(0, 1)],
+        ])
+    }
+
+    // Open squares on a map, with walls as #
+    struct Maze(&'static [&'static str]);
+
+    impl Graph for Maze {
+        type Node = Point;
+
+        fn edges(&self, p: &Point) -> Vec<(Point, usize)> {
+            p.neighbours().filter(|n| {
+                n.x >= 0 && n.y >= 0 && self.0.get(n.y as usize)
+                    .and_then(|row| row.as_bytes().get(n.x as usize))
+                    .is_some_and(|&c| c != b'#')
+            }).map(|n| (n, 1)).collect()
+        }
+    }
+
+    // The top right corner is walled in
+    const MAZE: Maze = Maze(&[
+        ".....#.#.",
+        ".###.#.##",
+        ".#...#...",
+        ".#.#.#.#.",
+        "...#.....",
+    ]);
+
+    #[test]
+    fn bfs_distances_counts_steps() {
+        let distances = bfs_distances(&diamond(), &0);
+        let expected = [(0, 0), (1, 1), (2, 1), (3, 2), (4, 3)].iter().copied().collect();
+        assert_eq!(distances, expected);
+    }
+
+    #[test]
+    fn bfs_follows_the_edge_order() {
+        let mut graph = diamond();
+        assert_eq!(bfs(&graph, &0, |&n| n == 4), Some(vec![0, 1, 3, 4]));
+        graph.0[0].reverse();
+        assert_eq!(bfs(&graph, &0, |&n| n == 4), Some(vec![0, 2, 3, 4]));
+        assert_eq!(bfs(&graph, &0, |&n| n == 0), Some(vec![0]));
+    }
+
+    #[test]
+    fn bfs_nearest_breaks_ties_by_key() {
+        // (4, 0) and (4, 4) are both two steps from (4, 2)
+        let start = Point::new(4, 2);
+        let is_goal = |p: &Point| p.x == 4 && (p.y == 0 || p.y == 4);
+        assert_eq!(bfs_nearest_by_key(&MAZE, &start, is_goal, |&p| p),
+                   Some((Point::new(4, 0), 2)));
+        assert_eq!(bfs_nearest_by_key(&MAZE, &start, is_goal, |&p| std::cmp::Reverse(p)),
+                   Some((Point::new(4, 4), 2)));
+    }
+
+    #[test]
+    fn dijkstra_finds_the_cheapest_path() {
+        // 3 is first queued through 1, then found cheaper through 2
+        assert_eq!(dijkstra(&diamond(), &0, |&n| n == 4), Some((vec![0, 2, 3, 4], 7)));
+        assert_eq!(dijkstra(&diamond(), &5, |&n| n == 3), Some((vec![5, 0, 2, 3], 7)));
+    }
+
+    #[test]
+    fn astar_finds_a_shortest_path() {
+        let goal = Point::new(8, 2);
+        let (path, cost) = astar(&MAZE, &Point::ORIGIN, |p| p.distance(goal) as usize,
+                                 |&p| p == goal).unwrap();
+        assert_eq!(cost, 14);
+        assert_eq!(path.len(), cost + 1);
+        assert_eq!((path[0], path[cost]), (Point::ORIGIN, goal));
+        assert!(path.windows(2).all(|w| w[0].distance(w[1]) == 1));
+        assert_eq!(bfs(&MAZE, &Point::ORIGIN, |&p| p == goal).unwrap().len(), cost + 1);
+    }
+
+    #[test]
+    fn unreachable_goals() {
+        assert_eq!(bfs(&diamond(), &0, |&n| n == 5), None);
+        assert_eq!(dijkstra(&diamond(), &0, |&n| n == 5), None);
+        assert_eq!(astar(&diamond(), &0, |_| 0, |&n| n == 5), None);
+
+        let goal = Point::new(8, 0);
+        assert_eq!(bfs(&MAZE, &Point::ORIGIN, |&p| p == goal), None);
+        assert_eq!(bfs_nearest_by_key(&MAZE, &Point::ORIGIN, |&p| p == goal, |&p| p), None);
+        assert_eq!(astar(&MAZE, &Point::ORIGIN, |p| p.distance(goal) as usize, |&p| p == goal),
+                   None);
+    }
+}
@@ -0,0 +1,118 @@
+// Puzzles which play out step by step, like carts going round a track or a
+// battle, round by round. Implementing Simulation lets them share the loops
+// which run them, and lets them be watched by rendering each step
+
+use crate::AocError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepResult {
+    // Something happened, so there may be more to come
+    Changed,
+    // Nothing happened, and as the next step would start from the same state
+    // nothing ever will
+    Unchanged,
+    // The simulation has reached its end, e.g. the battle is over. This step
+    // may have been cut short
+    Finished,
+}
+
+pub trait Simulation {
+    fn step(&mut self) -> Result<StepResult, AocError>;
+
+    // A picture of the current state, one line per row
+    fn render(&self) -> String;
+
+    // Where the action is in the picture render gives, as a column and a row,
+    // so that a viewer can keep it on the screen when the picture is bigger
+    // than that, like the water falling through day 17's scan
+    fn focus(&self) -> Option<(usize, usize)> {
+        None
+    }
+
+    // Whether the current state is close to something worth slowing down to
+    // watch, like the first crash on day 13
+    fn is_eventful(&self) -> bool {
+        false
+    }
+}
+
+// Days which can be watched have a function like this, which sets up the
+// simulation for an input
+pub type SimulationBuilder = fn(&str) -> Result<Box<dyn Simulation>, AocError>;
+
+// Take up to [n] steps, stopping early if the simulation finishes or stops
+// changing. Gives the number of steps which changed something
+pub fn run_n<S: Simulation + ?Sized>(sim: &mut S, n: usize) -> Result<usize, AocError> {
+    for i in 0..n {
+        if sim.step()? != StepResult::Changed {
+            return Ok(i);
+        }
+    }
+    Ok(n)
+}
+
+// Step until the simulation finishes or reaches a fixed point. Gives the
+// number of steps which changed something, so a step which is cut short by
+// the end of the simulation isn't counted
+pub fn run_until_stable<S: Simulation + ?Sized>(sim: &mut S) -> Result<usize, AocError> {
+    let mut steps = 0;
+    while sim.step()? == StepResult::Changed {
+        steps += 1;
+    }
+    Ok(steps)
+}
+
+// Step until [done] is true of the simulation, or it finishes or stops
+// changing. Gives whether [done] became true
+pub fn run_until<S: Simulation + ?Sized>(sim: &mut S, mut done: impl FnMut(&S) -> bool)
+        -> Result<bool, AocError> {
+    while !done(sim) {
+        if sim.step()? != StepResult::Changed {
+            return Ok(done(sim));
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Counts down to zero, and then keeps going or stops
+    struct Countdown {
+        n: u32,
+        finishes: bool,
+    }
+
+    impl Simulation for Countdown {
+        fn step(&mut self) -> Result<StepResult, AocError> {
+            Ok(match self.n {
+                0 if self.finishes => StepResult::Finished,
+                0 => StepResult::Unchanged,
+                _ => {
+                    self.n -= 1;
+                    StepResult::Changed
+                },
+            })
+        }
+
+        fn render(&self) -> String {
+            self.n.to_string()
+        }
+    }
+
+    #[test]
+    fn drivers() {
+        let mut sim = Countdown { n: 5, finishes: false };
+        assert_eq!(run_n(&mut sim, 2).unwrap(), 2);
+        assert_eq!(sim.render(), "3");
+        assert_eq!(run_until_stable(&mut sim).unwrap(), 3);
+        assert_eq!(run_n(&mut sim, 10).unwrap(), 0);
+
+        let mut sim = Countdown { n: 5, finishes: true };
+        assert!(run_until(&mut sim, |s| s.n == 1).unwrap());
+        assert_eq!(sim.n, 1);
+        assert!(!run_until(&mut sim, |s| s.n == 7).unwrap());
+        assert_eq!(sim.n, 0);
+    }
+}
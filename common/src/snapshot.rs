@@ -0,0 +1,111 @@
+// Snapshot tests for how things are shown, like day 15's battle or day 22's
+// cave, so that a refactor can't change a picture without anyone noticing.
+// What's shown is compared with what was saved in the snapshots directory of
+// the crate being tested. If it's different, or nothing's been saved yet, the
+// test fails and writes what it got next to the saved snapshot as .snap.new,
+// to be looked over and then renamed, or accepted all at once by running the
+// tests again with UPDATE_SNAPSHOTS=1
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+// Check that [actual] matches the snapshot called [name] in the crate's
+// snapshots directory, e.g. `assert_snapshot!("example_cave", cave)`
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:expr, $actual:expr) => {
+        $crate::snapshot::check(env!("CARGO_MANIFEST_DIR"), $name, &$actual.to_string())
+    };
+}
+
+// Lines which differ between [expected] and [actual], marked - and +, with
+// each compared with the line at the same place in the other
+fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<_> = expected.lines().collect();
+    let actual: Vec<_> = actual.lines().collect();
+    let mut lines = vec![];
+    for i in 0..expected.len().max(actual.len()) {
+        match (expected.get(i), actual.get(i)) {
+            (Some(e), Some(a)) if e == a => {},
+            (e, a) => {
+                lines.extend(e.map(|e| format!("{:4} -{}", i + 1, e)));
+                lines.extend(a.map(|a| format!("{:4} +{}", i + 1, a)));
+            },
+        }
+    }
+    lines.join("\n")
+}
+
+pub fn check(crate_dir: &str, name: &str, actual: &str) {
+    compare(crate_dir, name, actual, env::var_os("UPDATE_SNAPSHOTS").is_some());
+}
+
+// Compare [actual] with the saved snapshot, or save it if [update]
+fn compare(crate_dir: &str, name: &str, actual: &str, update: bool) {
+    let dir = Path::new(crate_dir).join("snapshots");
+    let path = dir.join(format!("{}.snap", name));
+    let new_path = dir.join(format!("{}.snap.new", name));
+    // Snapshots always end with a newline, whether or not what's shown does
+    let actual = format!("{}\n", actual.trim_end_matches('\n'));
+    let write = |path: &Path| {
+        fs::create_dir_all(&dir)
+            .and_then(|_| fs::write(path, &actual))
+            .unwrap_or_else(|e| panic!("Failed to write {}: {}", path.display(), e));
+    };
+
+    if update {
+        write(&path);
+        let _ = fs::remove_file(&new_path);
+        return;
+    }
+    match fs::read_to_string(&path) {
+        Ok(expected) if expected == actual => {
+            let _ = fs::remove_file(&new_path);
+        },
+        Ok(expected) => {
+            write(&new_path);
+            panic!("{} doesn't match the snapshot, what it is now is in {}:\n{}", name,
+                   new_path.display(), diff(&expected, &actual));
+        },
+        Err(_) => {
+            write(&new_path);
+            panic!("There's no snapshot of {} yet. Check {} and rename it to {}, or run \
+                    the tests with UPDATE_SNAPSHOTS=1", name, new_path.display(),
+                   path.display());
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diffs_changed_lines() {
+        assert_eq!(diff("a\nb\nc", "a\nB\nc\nd"), "   2 -b\n   2 +B\n   4 +d");
+        assert_eq!(diff("same", "same"), "");
+    }
+
+    #[test]
+    fn writes_new_snapshots_for_review() {
+        let dir = env::temp_dir().join(format!("aoc2018-snapshot-{}", std::process::id()));
+        let crate_dir = dir.to_str().unwrap();
+        let snapshots = dir.join("snapshots");
+
+        // With nothing saved the test fails, leaving what it got to look at
+        let missing = std::panic::catch_unwind(|| compare(crate_dir, "picture", "#.#", false));
+        assert!(missing.is_err());
+        assert_eq!(fs::read_to_string(snapshots.join("picture.snap.new")).unwrap(), "#.#\n");
+
+        // Once it's been accepted it passes, and the .snap.new is tidied up
+        fs::rename(snapshots.join("picture.snap.new"), snapshots.join("picture.snap")).unwrap();
+        compare(crate_dir, "picture", "#.#\n", false);
+        assert!(!snapshots.join("picture.snap.new").exists());
+
+        let changed = std::panic::catch_unwind(|| compare(crate_dir, "picture", "###", false));
+        assert!(changed.is_err());
+        assert_eq!(fs::read_to_string(snapshots.join("picture.snap")).unwrap(), "#.#\n");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,21 @@
+// Ways of looking at puzzles rather than just getting the answer: pictures of
+// a day's input or solution as SVGs, graphs for Graphviz, sparklines,
+// heatmaps and animations, and, with the viz feature, watching simulations in
+// the terminal, recording them as GIFs, PNGs or casts and writing heatmaps and
+// animations out as images. The feature is there because those need extra
+// crates which only the runner uses
+
+pub mod animation;
+pub mod dot;
+pub mod heatmap;
+#[cfg(feature = "viz")]
+mod history;
+#[cfg(feature = "viz")]
+mod record;
+pub mod sparkline;
+pub mod svg;
+#[cfg(feature = "viz")]
+mod terminal;
+
+#[cfg(feature = "viz")]
+pub use terminal::{Color, ColourScheme, Glyphs, Palette, Viewer};
@@ -0,0 +1,220 @@
+// Animations drawn a pixel at a time, for days where a picture says more than
+// their characters can, like day 15's battle with each unit shaded by the hit
+// points it has left. With the viz feature they can be written as GIFs
+
+#[cfg(feature = "viz")]
+use std::error::Error;
+#[cfg(feature = "viz")]
+use std::io::Write;
+
+use crate::AocError;
+
+// Days with an animation have a function like this, which animates an input
+pub type AnimationExport = fn(&str) -> Result<Animation, AocError>;
+
+pub type Rgb = [u8; 3];
+
+// A 3x5 pixel font for writing on pictures, with a column between characters.
+// Lower case letters are drawn as capitals, and anything else is a space
+const FONT: &[(char, [&str; 5])] = &[
+    ('A', [".#.", "#.#", "###", "#.#", "#.#"]),
+    ('B', ["##.", "#.#", "##.", "#.#", "##."]),
+    ('C', [".##", "#..", "#..", "#..", ".##"]),
+    ('D', ["##.", "#.#", "#.#", "#.#", "##."]),
+    ('E', ["###", "#..", "##.", "#..", "###"]),
+    ('F', ["###", "#..", "##.", "#..", "#.."]),
+    ('G', [".##", "#..", "#.#", "#.#", ".##"]),
+    ('H', ["#.#", "#.#", "###", "#.#", "#.#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..#", "..#", "..#", "#.#", ".#."]),
+    ('K', ["#.#", "#.#", "##.", "#.#", "#.#"]),
+    ('L', ["#..", "#..", "#..", "#..", "###"]),
+    ('M', ["#.#", "###", "###", "#.#", "#.#"]),
+    ('N', ["##.", "#.#", "#.#", "#.#", "#.#"]),
+    ('O', [".#.", "#.#", "#.#", "#.#", ".#."]),
+    ('P', ["##.", "#.#", "##.", "#..", "#.."]),
+    ('Q', [".#.", "#.#", "#.#", "##.", ".##"]),
+    ('R', ["##.", "#.#", "##.", "#.#", "#.#"]),
+    ('S', [".##", "#..", ".#.", "..#", "##."]),
+    ('T', ["###", ".#.", ".#.", ".#.", ".#."]),
+    ('U', ["#.#", "#.#", "#.#", "#.#", "###"]),
+    ('V', ["#.#", "#.#", "#.#", "#.#", ".#."]),
+    ('W', ["#.#", "#.#", "###", "###", "#.#"]),
+    ('X', ["#.#", "#.#", ".#.", "#.#", "#.#"]),
+    ('Y', ["#.#", "#.#", ".#.", ".#.", ".#."]),
+    ('Z', ["###", "..#", ".#.", "#..", "###"]),
+    ('0', ["###", "#.#", "#.#", "#.#", "###"]),
+    ('1', [".#.", "##.", ".#.", ".#.", "###"]),
+    ('2', ["##.", "..#", ".#.", "#..", "###"]),
+    ('3', ["##.", "..#", ".#.", "..#", "##."]),
+    ('4', ["#.#", "#.#", "###", "..#", "..#"]),
+    ('5', ["###", "#..", "##.", "..#", "##."]),
+    ('6', [".##", "#..", "###", "#.#", "###"]),
+    ('7', ["###", "..#", ".#.", ".#.", ".#."]),
+    ('8', ["###", "#.#", "###", "#.#", "###"]),
+    ('9', ["###", "#.#", "###", "..#", "##."]),
+    (':', ["...", ".#.", "...", ".#.", "..."]),
+    ('-', ["...", "...", "###", "...", "..."]),
+    ('/', ["..#", "..#", ".#.", "#..", "#.."]),
+];
+
+// How many pixels high text is, and how many across each character takes
+// with the gap after it, before scaling
+pub const TEXT_HEIGHT: usize = 5;
+const TEXT_ADVANCE: usize = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Picture {
+    width: usize,
+    height: usize,
+    // Row by row
+    pixels: Vec<Rgb>,
+}
+
+impl Picture {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Picture {
+        Picture { width, height, pixels: vec![background; width * height] }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    // Fill a [width] by [height] rectangle with its top left corner at [x],
+    // [y]. Anything off the edge of the picture is left out
+    pub fn rect(&mut self, x: usize, y: usize, width: usize, height: usize, colour: Rgb) {
+        for row in y..(y + height).min(self.height) {
+            let start = row * self.width;
+            let (from, to) = (x.min(self.width), (x + width).min(self.width));
+            self.pixels[start + from..start + to].fill(colour);
+        }
+    }
+
+    // Write [text] with its top left corner at [x], [y], with each pixel of
+    // the font a [scale] pixel square
+    pub fn text(&mut self, x: usize, y: usize, text: &str, scale: usize, colour: Rgb) {
+        for (i, c) in text.chars().enumerate() {
+            let c = c.to_ascii_uppercase();
+            let Some((_, rows)) = FONT.iter().find(|(f, _)| *f == c) else { continue };
+            let left = x + i * TEXT_ADVANCE * scale;
+            for (dy, row) in rows.iter().enumerate() {
+                for (dx, _) in row.chars().enumerate().filter(|&(_, p)| p == '#') {
+                    self.rect(left + dx * scale, y + dy * scale, scale, scale, colour);
+                }
+            }
+        }
+    }
+}
+
+// How many pixels across [text] is when written at [scale]
+pub fn text_width(text: &str, scale: usize) -> usize {
+    (text.chars().count() * TEXT_ADVANCE).saturating_sub(1) * scale
+}
+
+// Pictures to be shown one after another, each for its own time
+#[derive(Debug, Clone, Default)]
+pub struct Animation {
+    // Each picture, and how long it's shown for in hundredths of a second
+    frames: Vec<(Picture, u16)>,
+}
+
+impl Animation {
+    pub fn new() -> Animation {
+        Animation::default()
+    }
+
+    pub fn push(&mut self, picture: Picture, delay: u16) {
+        self.frames.push((picture, delay));
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    // Write the animation to [out] as a GIF which goes round forever. Every
+    // picture has to be the same size
+    #[cfg(feature = "viz")]
+    pub fn write_gif(&self, out: impl Write) -> Result<(), Box<dyn Error>> {
+        let (width, height) = match self.frames.first() {
+            Some((picture, _)) => (picture.width, picture.height),
+            None => return Err("the animation is empty".into()),
+        };
+        if self.frames.iter().any(|(p, _)| (p.width, p.height) != (width, height)) {
+            return Err("the pictures in the animation aren't all the same size".into());
+        }
+        if width > u16::MAX as usize || height > u16::MAX as usize {
+            return Err(format!("the pictures are too big for a GIF, at {}x{}",
+                               width, height).into());
+        }
+
+        let mut encoder = gif::Encoder::new(out, width as u16, height as u16, &[])?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+        for (picture, delay) in &self.frames {
+            let rgb: Vec<u8> = picture.pixels.iter().flatten().copied().collect();
+            // Pictures with 256 colours or fewer, like those drawn here, keep
+            // their colours exactly
+            let mut frame = gif::Frame::from_rgb_speed(width as u16, height as u16, &rgb, 10);
+            frame.delay = *delay;
+            encoder.write_frame(&frame)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK: Rgb = [0, 0, 0];
+    const RED: Rgb = [255, 0, 0];
+
+    #[test]
+    fn drawing() {
+        let mut picture = Picture::new(4, 3, BLACK);
+        picture.rect(2, 1, 5, 5, RED);
+        assert_eq!((picture.pixel(1, 1), picture.pixel(2, 1), picture.pixel(3, 2)),
+                   (BLACK, RED, RED));
+
+        let mut picture = Picture::new(20, 10, BLACK);
+        picture.text(1, 2, "e1", 1, RED);
+        // The E's top bar, then the middle of the 1 a character along
+        assert_eq!((picture.pixel(1, 2), picture.pixel(3, 2), picture.pixel(4, 2)),
+                   (RED, RED, BLACK));
+        assert_eq!((picture.pixel(6, 2), picture.pixel(6, 6)), (RED, RED));
+        assert_eq!(text_width("e1", 2), 14);
+    }
+
+    #[cfg(feature = "viz")]
+    #[test]
+    fn writes_a_gif() {
+        let mut animation = Animation::new();
+        animation.push(Picture::new(3, 2, BLACK), 10);
+        animation.push(Picture::new(3, 2, RED), 300);
+        let mut gif = vec![];
+        animation.write_gif(&mut gif).unwrap();
+
+        let mut decoder = gif::DecodeOptions::new();
+        decoder.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = decoder.read_info(&gif[..]).unwrap();
+        let mut frames = vec![];
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            frames.push((frame.delay, frame.buffer[..3].to_vec()));
+        }
+        assert_eq!(frames, vec![(10, vec![0, 0, 0]), (300, vec![255, 0, 0])]);
+
+        animation.push(Picture::new(1, 1, RED), 10);
+        assert!(animation.write_gif(vec![]).is_err());
+    }
+}
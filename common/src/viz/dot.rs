@@ -0,0 +1,113 @@
+// Writing graphs in Graphviz's DOT language, so that graph shaped puzzles like
+// day 7's steps or day 20's rooms can be looked at with dot, xdot and so on
+
+use std::fmt;
+
+use crate::AocError;
+
+// Days with a graph in them have a function like this, which gives the graph
+// for an input
+pub type GraphExport = fn(&str) -> Result<Dot, AocError>;
+
+// Attributes are (name, value) pairs, like ("label", "A") or ("color", "red")
+pub type Attributes<'a> = &'a [(&'a str, &'a str)];
+
+#[derive(Debug, Clone)]
+pub struct Dot {
+    directed: bool,
+    // Attributes for the whole graph, then ones which every node gets unless
+    // it says otherwise
+    graph_attributes: Vec<String>,
+    node_attributes: Vec<String>,
+    // The nodes and edges, in the order they were added
+    statements: Vec<String>,
+}
+
+impl Dot {
+    fn new(directed: bool) -> Dot {
+        Dot { directed, graph_attributes: vec![], node_attributes: vec![], statements: vec![] }
+    }
+
+    // A graph where each edge goes from one node to another
+    pub fn directed() -> Dot {
+        Dot::new(true)
+    }
+
+    pub fn undirected() -> Dot {
+        Dot::new(false)
+    }
+
+    pub fn graph_attribute(mut self, name: &str, value: &str) -> Dot {
+        self.graph_attributes.push(format!("{}={}", name, quote(value)));
+        self
+    }
+
+    pub fn node_attribute(mut self, name: &str, value: &str) -> Dot {
+        self.node_attributes.push(format!("{}={}", name, quote(value)));
+        self
+    }
+
+    pub fn node(&mut self, id: &str, attributes: Attributes) -> &mut Dot {
+        self.statements.push(format!("{}{}", quote(id), attribute_list(attributes)));
+        self
+    }
+
+    pub fn edge(&mut self, from: &str, to: &str, attributes: Attributes) -> &mut Dot {
+        let arrow = if self.directed { "->" } else { "--" };
+        self.statements.push(format!("{} {} {}{}", quote(from), arrow, quote(to),
+                                     attribute_list(attributes)));
+        self
+    }
+}
+
+// Every ID and value is quoted, so that they can be anything
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+}
+
+fn attribute_list(attributes: Attributes) -> String {
+    if attributes.is_empty() {
+        return String::new();
+    }
+    let attributes: Vec<_> = attributes.iter()
+        .map(|(name, value)| format!("{}={}", name, quote(value)))
+        .collect();
+    format!(" [{}]", attributes.join(", "))
+}
+
+impl fmt::Display for Dot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} {{", if self.directed { "digraph" } else { "graph" })?;
+        for attribute in &self.graph_attributes {
+            writeln!(f, "    {};", attribute)?;
+        }
+        if !self.node_attributes.is_empty() {
+            writeln!(f, "    node [{}];", self.node_attributes.join(", "))?;
+        }
+        for statement in &self.statements {
+            writeln!(f, "    {};", statement)?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directed_graph() {
+        let mut dot = Dot::directed().graph_attribute("rankdir", "LR")
+            .node_attribute("shape", "box");
+        dot.node("A", &[("label", "first\n\"A\"")]).edge("A", "B", &[]);
+        assert_eq!(dot.to_string(), "digraph {\n    rankdir=\"LR\";\n    node [shape=\"box\"];\n    \
+                                     \"A\" [label=\"first\\n\\\"A\\\"\"];\n    \"A\" -> \"B\";\n}\n");
+    }
+
+    #[test]
+    fn undirected_graph() {
+        let mut dot = Dot::undirected();
+        dot.edge("0,0", "0,1", &[("color", "red")]);
+        assert_eq!(dot.to_string(), "graph {\n    \"0,0\" -- \"0,1\" [color=\"red\"];\n}\n");
+    }
+}
@@ -0,0 +1,153 @@
+// Heatmaps, pictures of a grid of numbers with each cell shaded by its value,
+// like the erosion levels across day 22's cave. With the viz feature they can
+// be written as PNGs, where each cell is a square of pixels
+
+use std::collections::HashSet;
+#[cfg(feature = "viz")]
+use std::error::Error;
+#[cfg(feature = "viz")]
+use std::io::Write;
+
+use crate::AocError;
+use crate::grid::Grid;
+
+// Days with a heatmap have a function like this, which gives the heatmap for
+// an input
+pub type HeatmapExport = fn(&str) -> Result<Heatmap, AocError>;
+
+// How values are turned into colours. Either way the lowest value is black
+// and the highest is white
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shading {
+    Grey,
+    // Black through red and yellow to white, which makes small differences
+    // easier to see
+    Heat,
+}
+
+#[derive(Debug, Clone)]
+pub struct Heatmap {
+    values: Grid<u64>,
+    // The lowest and highest values
+    range: (u64, u64),
+    // Cells drawn in a colour of their own rather than shaded, like the route
+    // taken through the cave
+    highlighted: HashSet<(usize, usize)>,
+}
+
+// The colour highlighted cells are drawn in
+#[cfg(feature = "viz")]
+const HIGHLIGHT: [u8; 3] = [0, 160, 255];
+
+// Each cell is drawn as big as it can be without the longer side of the image
+// going over [MAX_SIDE] pixels, up to [MAX_SCALE] pixels across
+#[cfg(feature = "viz")]
+const MAX_SIDE: usize = 1600;
+#[cfg(feature = "viz")]
+const MAX_SCALE: usize = 8;
+
+impl Heatmap {
+    pub fn new(values: Grid<u64>) -> Heatmap {
+        let min = values.iter().copied().min().unwrap_or(0);
+        let max = values.iter().copied().max().unwrap_or(0);
+        Heatmap { values, range: (min, max), highlighted: HashSet::new() }
+    }
+
+    pub fn highlight(mut self, cells: impl IntoIterator<Item = (usize, usize)>) -> Heatmap {
+        self.highlighted.extend(cells);
+        self
+    }
+
+    pub fn is_highlighted(&self, x: usize, y: usize) -> bool {
+        self.highlighted.contains(&(x, y))
+    }
+
+    // How far the value at [x], [y] is from the lowest value to the highest,
+    // from 0 to 255
+    pub fn level(&self, x: usize, y: usize) -> u8 {
+        let (min, max) = self.range;
+        if max == min {
+            return 0;
+        }
+        ((self.values[(x, y)] - min) * 255 / (max - min)) as u8
+    }
+
+    #[cfg(feature = "viz")]
+    fn colour(&self, x: usize, y: usize, shading: Shading) -> [u8; 3] {
+        if self.is_highlighted(x, y) {
+            return HIGHLIGHT;
+        }
+        let level = self.level(x, y);
+        match shading {
+            Shading::Grey => [level, level, level],
+            Shading::Heat => heat(level),
+        }
+    }
+
+    // Write the heatmap to [out] as a PNG
+    #[cfg(feature = "viz")]
+    pub fn write_png(&self, out: impl Write, shading: Shading) -> Result<(), Box<dyn Error>> {
+        let (width, height) = (self.values.width(), self.values.height());
+        if width == 0 || height == 0 {
+            return Err("the heatmap is empty".into());
+        }
+        let scale = (MAX_SIDE / width.max(height)).clamp(1, MAX_SCALE);
+
+        let mut pixels = Vec::with_capacity(width * height * scale * scale * 3);
+        for y in 0..height {
+            let row: Vec<u8> = (0..width)
+                .flat_map(|x| {
+                    let colour = self.colour(x, y, shading);
+                    std::iter::repeat_n(colour, scale).flatten()
+                })
+                .collect();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&row);
+            }
+        }
+
+        let mut encoder = png::Encoder::new(out, (width * scale) as u32, (height * scale) as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&pixels)?;
+        writer.finish()?;
+        Ok(())
+    }
+}
+
+// Black to red for the first third, then red to yellow, then yellow to white
+#[cfg(feature = "viz")]
+fn heat(level: u8) -> [u8; 3] {
+    let third = |from: u8| (u32::from(level.saturating_sub(from)) * 3).min(255) as u8;
+    [third(0), third(85), third(170)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels() {
+        let heatmap = Heatmap::new(Grid::from_fn(3, 1, |x, _| 10 + 5 * x as u64));
+        assert_eq!((heatmap.level(0, 0), heatmap.level(1, 0), heatmap.level(2, 0)), (0, 127, 255));
+        let flat = Heatmap::new(Grid::from_fn(2, 2, |_, _| 7));
+        assert_eq!(flat.level(1, 1), 0);
+    }
+
+    #[cfg(feature = "viz")]
+    #[test]
+    fn writes_a_png() {
+        let heatmap = Heatmap::new(Grid::from_fn(4, 2, |x, y| (x + y) as u64))
+            .highlight(vec![(0, 0)]);
+        assert_eq!(heatmap.colour(0, 0, Shading::Grey), HIGHLIGHT);
+        assert_eq!(heatmap.colour(3, 1, Shading::Grey), [255, 255, 255]);
+        assert_eq!(heat(0), [0, 0, 0]);
+        assert_eq!(heat(255), [255, 255, 255]);
+
+        let mut png = vec![];
+        heatmap.write_png(&mut png, Shading::Heat).unwrap();
+        let reader = png::Decoder::new(&png[..]).read_info().unwrap();
+        assert_eq!((reader.info().width, reader.info().height), (32, 16));
+    }
+}
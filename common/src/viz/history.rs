@@ -0,0 +1,139 @@
+// The frames a viewer has shown, so that the person watching can go back
+// through them. Simulations can't be wound back, so what's kept is what they
+// rendered rather than their state. Frames are only kept up to a total size,
+// after which the oldest ones are forgotten
+
+use std::collections::VecDeque;
+
+// Roughly how many bytes of frames are kept
+const MAX_BYTES: usize = 256 << 20;
+
+// A frame, and what's known about it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Snapshot {
+    // The step the frame was rendered after
+    pub(super) step: usize,
+    pub(super) frame: String,
+    // Where the action is in the frame, if the simulation says
+    pub(super) focus: Option<(usize, usize)>,
+}
+
+pub(super) struct History {
+    // Oldest first
+    frames: VecDeque<Snapshot>,
+    bytes: usize,
+    max_bytes: usize,
+    // Which frame is being looked at
+    at: usize,
+}
+
+impl History {
+    pub(super) fn new(first: Snapshot) -> History {
+        History::with_limit(first, MAX_BYTES)
+    }
+
+    fn with_limit(first: Snapshot, max_bytes: usize) -> History {
+        let bytes = first.frame.len();
+        History { frames: VecDeque::from(vec![first]), bytes, max_bytes, at: 0 }
+    }
+
+    // Keep [snapshot], which is from after every frame so far, and look at it
+    pub(super) fn record(&mut self, snapshot: Snapshot) {
+        self.at = self.frames.len() - 1;
+        // E.g. when asked for more steps after the simulation has ended
+        if self.frames.back() == Some(&snapshot) {
+            return;
+        }
+        self.bytes += snapshot.frame.len();
+        self.frames.push_back(snapshot);
+        while self.bytes > self.max_bytes && self.frames.len() > 1 {
+            if let Some(oldest) = self.frames.pop_front() {
+                self.bytes -= oldest.frame.len();
+            }
+        }
+        self.at = self.frames.len() - 1;
+    }
+
+    // The frame being looked at
+    pub(super) fn current(&self) -> &Snapshot {
+        &self.frames[self.at]
+    }
+
+    // Whether the latest frame is being looked at, rather than an older one
+    pub(super) fn is_latest(&self) -> bool {
+        self.at + 1 == self.frames.len()
+    }
+
+    // Look at the frame before this one, if there's one. Gives whether there
+    // was
+    pub(super) fn back(&mut self) -> bool {
+        if self.at == 0 {
+            return false;
+        }
+        self.at -= 1;
+        true
+    }
+
+    // Look at the frame after this one, if it's been recorded. Gives whether
+    // it had
+    pub(super) fn forward(&mut self) -> bool {
+        if self.is_latest() {
+            return false;
+        }
+        self.at += 1;
+        true
+    }
+
+    // Look at the last frame rendered at or before [step], or the oldest one
+    // kept if they're all after it
+    pub(super) fn seek(&mut self, step: usize) {
+        self.at = self.frames.partition_point(|s| s.step <= step).saturating_sub(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(step: usize, frame: &str) -> Snapshot {
+        Snapshot { step, frame: frame.to_string(), focus: None }
+    }
+
+    fn current(history: &History) -> (usize, &str) {
+        (history.current().step, &history.current().frame)
+    }
+
+    #[test]
+    fn going_back_and_forth() {
+        let mut history = History::new(snapshot(0, "a"));
+        history.record(snapshot(2, "b"));
+        history.record(snapshot(4, "c"));
+        history.back();
+        history.record(snapshot(4, "c"));
+        assert_eq!((current(&history), history.is_latest()), ((4, "c"), true));
+
+        assert!(history.back() && history.back() && !history.back());
+        assert_eq!(current(&history), (0, "a"));
+        assert!(history.forward());
+        assert_eq!((current(&history), history.is_latest()), ((2, "b"), false));
+
+        history.seek(3);
+        assert_eq!(current(&history), (2, "b"));
+        history.seek(100);
+        assert_eq!(current(&history), (4, "c"));
+        assert!(!history.forward());
+    }
+
+    #[test]
+    fn old_frames_are_forgotten() {
+        let mut history = History::with_limit(snapshot(0, "aaaa"), 10);
+        history.record(snapshot(1, "bbbb"));
+        history.record(snapshot(2, "cccc"));
+        history.seek(0);
+        assert_eq!(current(&history), (1, "bbbb"));
+        // The latest frame is always kept, however big it is
+        history.record(snapshot(3, &"d".repeat(20)));
+        assert!(!history.back());
+        assert_eq!(history.current().step, 3);
+    }
+}
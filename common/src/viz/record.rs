@@ -0,0 +1,537 @@
+// Recording simulations as animated GIFs to share, or as a PNG for each frame,
+// with each character of a frame drawn as a square of pixels in its palette
+// colour. They can also be recorded as asciinema casts, which replay what the
+// terminal would have shown when watching, escape codes and all, or as a
+// plain text file for each frame. None of them need a terminal, so they can
+// be made by scripts
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crossterm::{cursor, queue};
+use gif::{DisposalMethod, Encoder, Frame, Repeat};
+
+use super::terminal::{visible_rows, Live, Screen, View};
+use super::{Color, Palette, Viewer};
+use crate::simulation::Simulation;
+
+// Each character is drawn as big as it can be without the longer side of the
+// GIF going over [MAX_SIDE] pixels, up to [MAX_SCALE] pixels across
+const MAX_SIDE: usize = 1600;
+const MAX_SCALE: usize = 8;
+
+// How long the last frame is shown, in hundredths of a second, before the GIF
+// starts again
+const LAST_FRAME_DELAY: u16 = 300;
+
+// Characters which aren't in the palette are grey, apart from spaces and dots
+// which are usually empty ground and are left as the background
+const BACKGROUND: [u8; 3] = [0, 0, 0];
+const FOREGROUND: [u8; 3] = [192, 192, 192];
+
+// The usual RGB values for the terminal colours
+fn rgb(colour: Color) -> [u8; 3] {
+    match colour {
+        Color::Reset => FOREGROUND,
+        Color::Black => [0, 0, 0],
+        Color::DarkGrey => [128, 128, 128],
+        Color::Red => [255, 85, 85],
+        Color::DarkRed => [170, 0, 0],
+        Color::Green => [85, 255, 85],
+        Color::DarkGreen => [0, 170, 0],
+        Color::Yellow => [255, 255, 85],
+        Color::DarkYellow => [170, 85, 0],
+        Color::Blue => [85, 85, 255],
+        Color::DarkBlue => [0, 0, 170],
+        Color::Magenta => [255, 85, 255],
+        Color::DarkMagenta => [170, 0, 170],
+        Color::Cyan => [85, 255, 255],
+        Color::DarkCyan => [0, 170, 170],
+        Color::White => [255, 255, 255],
+        Color::Grey => [170, 170, 170],
+        Color::Rgb { r, g, b } => [r, g, b],
+        Color::AnsiValue(n) => ansi_rgb(n),
+    }
+}
+
+// The 256 colour palette is the 16 named colours, then a 6x6x6 colour cube,
+// then 24 shades of grey
+fn ansi_rgb(n: u8) -> [u8; 3] {
+    const NAMED: [Color; 16] = [
+        Color::Black, Color::DarkRed, Color::DarkGreen, Color::DarkYellow,
+        Color::DarkBlue, Color::DarkMagenta, Color::DarkCyan, Color::Grey,
+        Color::DarkGrey, Color::Red, Color::Green, Color::Yellow,
+        Color::Blue, Color::Magenta, Color::Cyan, Color::White,
+    ];
+    let level = |i: u8| if i == 0 { 0 } else { 55 + 40 * i };
+    match n {
+        0..=15 => rgb(NAMED[n as usize]),
+        16..=231 => {
+            let i = n - 16;
+            [level(i / 36), level(i / 6 % 6), level(i % 6)]
+        },
+        _ => {
+            let grey = 8 + 10 * (n - 232);
+            [grey, grey, grey]
+        },
+    }
+}
+
+// The GIF's colour table, and where each character's colour is in it
+struct ColourTable {
+    colours: Vec<[u8; 3]>,
+    indexes: HashMap<char, u8>,
+}
+
+impl ColourTable {
+    fn new(palette: &Palette) -> ColourTable {
+        let mut colours = vec![BACKGROUND, FOREGROUND];
+        let mut indexes = HashMap::new();
+        // In order, so that recording the same thing twice gives the same GIF
+        let mut chars: Vec<_> = palette.colours.iter().collect();
+        chars.sort();
+        for (&c, &colour) in chars {
+            let colour = rgb(colour);
+            let i = match colours.iter().position(|&known| known == colour) {
+                Some(i) => i,
+                // A GIF can only have 256 colours, one of which is kept for
+                // transparency, so any more are left grey
+                None if colours.len() == 255 => 1,
+                None => {
+                    colours.push(colour);
+                    colours.len() - 1
+                },
+            };
+            indexes.insert(c, i as u8);
+        }
+        ColourTable { colours, indexes }
+    }
+
+    fn index(&self, c: char) -> u8 {
+        match self.indexes.get(&c) {
+            Some(&i) => i,
+            None if c == ' ' || c == '.' => 0,
+            None => 1,
+        }
+    }
+
+    // [frame] as indexes, row by row
+    fn rows(&self, frame: &str) -> Rows {
+        frame.lines().map(|line| line.chars().map(|c| self.index(c)).collect()).collect()
+    }
+
+    // The index which is see through, for the parts of a frame which haven't
+    // changed. It comes after all the real colours
+    fn transparent(&self) -> u8 {
+        self.colours.len() as u8
+    }
+
+    // The table as the GIF stores it, with room for the transparent colour.
+    // Its size has to be a power of two
+    fn bytes(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = self.colours.iter().flatten().copied().collect();
+        bytes.resize(3 * (self.colours.len() + 1).next_power_of_two(), 0);
+        bytes
+    }
+}
+
+// A frame as colour table indexes, row by row
+type Rows = Vec<Vec<u8>>;
+
+// The colour at [x], [y] in a frame, where anything off the end of the frame
+// is background
+fn cell(rows: &[Vec<u8>], x: usize, y: usize) -> u8 {
+    rows.get(y).and_then(|row| row.get(x)).copied().unwrap_or(0)
+}
+
+// The top left and bottom right corners of the smallest rectangle holding
+// every cell which is different in [before] and [after]
+fn changed_area(before: &[Vec<u8>], after: &[Vec<u8>]) -> Option<((usize, usize), (usize, usize))> {
+    let height = before.len().max(after.len());
+    let mut area: Option<((usize, usize), (usize, usize))> = None;
+    for y in 0..height {
+        let width = [before, after].iter().filter_map(|rows| rows.get(y)).map(|row| row.len())
+            .max().unwrap_or(0);
+        for x in (0..width).filter(|&x| cell(before, x, y) != cell(after, x, y)) {
+            area = Some(match area {
+                Some((min, max)) => ((min.0.min(x), min.1), (max.0.max(x), y)),
+                None => ((x, y), (x, y)),
+            });
+        }
+    }
+    area
+}
+
+// A frame of a recording
+struct Recorded {
+    // The step the frame was rendered after
+    step: usize,
+    frame: String,
+    // Whether it's to be shown in slow motion
+    slow: bool,
+}
+
+impl Viewer {
+    // Play [sim] without showing it, keeping every frame. Stops when the
+    // simulation does or after [max_frames] frames. The frames aren't all the
+    // same size, e.g. when a growing pattern spreads, so they're all kept
+    // until the size of the recording is known
+    fn frames(&self, sim: &mut dyn Simulation, max_frames: usize)
+            -> Result<Vec<Recorded>, Box<dyn Error>> {
+        let mut live = Live::start(sim, self.start_at)?;
+        let mut frames = vec![Recorded { step: live.steps, frame: sim.render(),
+                                         slow: self.is_slow(sim) }];
+        while live.ended.is_none() && frames.len() < max_frames {
+            let n = if self.is_slow(sim) { 1 } else { self.steps_per_frame };
+            live.take(sim, n)?;
+            // The last step might not have changed anything
+            let frame = sim.render();
+            if frames.last().map(|f| &f.frame) != Some(&frame) {
+                frames.push(Recorded { step: live.steps, frame, slow: self.is_slow(sim) });
+            }
+        }
+        Ok(frames)
+    }
+
+    // Play [sim] without showing it, recording every frame as an animated GIF
+    // written to [out], at the viewer's speed and in its colours. Stops when
+    // the simulation does or after [max_frames] frames. Gives the number of
+    // frames recorded
+    pub fn record_gif(&self, sim: &mut dyn Simulation, out: impl Write, max_frames: usize)
+            -> Result<usize, Box<dyn Error>> {
+        let table = ColourTable::new(&self.palette);
+        let recorded = self.frames(sim, max_frames)?;
+        let frames: Vec<_> = recorded.iter().map(|r| table.rows(&r.frame)).collect();
+        let (width, height, scale) = size(&frames)?;
+        let (pixel_width, pixel_height) = (width * scale, height * scale);
+        if pixel_width > u16::MAX as usize || pixel_height > u16::MAX as usize {
+            return Err(format!("the frames are too big for a GIF, at {}x{}",
+                               width, height).into());
+        }
+
+        let mut encoder = Encoder::new(out, pixel_width as u16, pixel_height as u16,
+                                       &table.bytes())?;
+        encoder.set_repeat(Repeat::Infinite)?;
+        let empty = vec![];
+        for (i, rows) in frames.iter().enumerate() {
+            // Only the part which has changed since the last frame is drawn,
+            // on top of the last frame, and anything in that part which hasn't
+            // changed is see through. That keeps the GIF small
+            let before = if i == 0 { &empty } else { &frames[i - 1] };
+            let transparent = if i == 0 { None } else { Some(table.transparent()) };
+            // A frame always differs from the last one, but the first frame
+            // might be blank, in which case one cell is drawn
+            let (min, max) = changed_area(before, rows).unwrap_or_default();
+            let pixels = pixels(min, max, scale, |x, y| match transparent {
+                Some(t) if cell(before, x, y) == cell(rows, x, y) => t,
+                _ => cell(rows, x, y),
+            });
+            let (w, h) = ((max.0 - min.0 + 1) * scale, (max.1 - min.1 + 1) * scale);
+
+            let mut frame = Frame::from_indexed_pixels(w as u16, h as u16, pixels, transparent);
+            frame.left = (min.0 * scale) as u16;
+            frame.top = (min.1 * scale) as u16;
+            frame.dispose = DisposalMethod::Keep;
+            frame.delay = if i + 1 == frames.len() {
+                LAST_FRAME_DELAY
+            }
+            else {
+                let delay = (100 / self.fps).max(1);
+                (if recorded[i].slow { delay * self.slow_motion } else { delay }) as u16
+            };
+            encoder.write_frame(&frame)?;
+        }
+        Ok(frames.len())
+    }
+
+    // Play [sim] without showing it, writing every frame to [dir] as a PNG,
+    // named frame00000.png, frame00001.png and so on, in the viewer's
+    // colours. Every picture is the same size, so that they can be flicked
+    // through or stitched into a video. Stops when the simulation does or
+    // after [max_frames] frames. Gives the number of frames written
+    pub fn record_pngs(&self, sim: &mut dyn Simulation, dir: &Path, max_frames: usize)
+            -> Result<usize, Box<dyn Error>> {
+        let table = ColourTable::new(&self.palette);
+        let frames: Vec<_> = self.frames(sim, max_frames)?.iter()
+            .map(|r| table.rows(&r.frame))
+            .collect();
+        let (width, height, scale) = size(&frames)?;
+        fs::create_dir_all(dir)?;
+        for (i, rows) in frames.iter().enumerate() {
+            let path = dir.join(format!("frame{:05}.png", i));
+            let file = File::create(&path)
+                .map_err(|e| format!("failed to create {}: {}", path.display(), e))?;
+            let mut encoder = png::Encoder::new(BufWriter::new(file), (width * scale) as u32,
+                                                (height * scale) as u32);
+            encoder.set_color(png::ColorType::Indexed);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder.set_palette(table.colours.iter().flatten().copied().collect::<Vec<_>>());
+            let mut writer = encoder.write_header()?;
+            writer.write_image_data(&pixels((0, 0), (width - 1, height - 1), scale,
+                                            |x, y| cell(rows, x, y)))?;
+            writer.finish()?;
+        }
+        Ok(frames.len())
+    }
+
+    // Play [sim] without showing it, writing every frame to [dir] as a text
+    // file, named frame00000.txt, frame00001.txt and so on, drawn with the
+    // viewer's glyphs but without colours or any escape codes. Stops when the
+    // simulation does or after [max_frames] frames. Gives the number of
+    // frames written
+    pub fn record_text(&self, sim: &mut dyn Simulation, dir: &Path, max_frames: usize)
+            -> Result<usize, Box<dyn Error>> {
+        let frames = self.frames(sim, max_frames)?;
+        fs::create_dir_all(dir)?;
+        for (i, recorded) in frames.iter().enumerate() {
+            let path = dir.join(format!("frame{:05}.txt", i));
+            fs::write(&path, self.glyphs.draw(&recorded.frame))
+                .map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+        }
+        Ok(frames.len())
+    }
+
+    // Play [sim] without showing it, recording what watching it would have
+    // drawn in the terminal as an asciinema cast written to [out], at the
+    // viewer's speed and in its colours and glyphs. The terminal is made big
+    // enough for the biggest frame, with the step it's at on the line under
+    // it. Stops when the simulation does or after [max_frames] frames. Gives
+    // the number of frames recorded
+    pub fn record_cast(&self, sim: &mut dyn Simulation, mut out: impl Write, max_frames: usize)
+            -> Result<usize, Box<dyn Error>> {
+        let frames = self.frames(sim, max_frames)?;
+        let statuses: Vec<_> = frames.iter().map(|f| format!("Step {}", f.step)).collect();
+        let width = frames.iter()
+            .flat_map(|f| f.frame.lines())
+            .map(|line| line.chars().count() * self.glyphs.scale(line))
+            .chain(statuses.iter().map(|s| s.len()))
+            .max().unwrap_or(0);
+        let height = frames.iter().map(|f| f.frame.lines().count()).max().unwrap_or(0);
+        if height == 0 {
+            return Err("the simulation didn't draw anything".into());
+        }
+
+        writeln!(out, r#"{{"version": 2, "width": {}, "height": {}}}"#, width, height + 1)?;
+        let mut screen = Screen::new(vec![], self.palette.clone());
+        queue!(screen.out, cursor::Hide)?;
+        screen.invalidate()?;
+        let view = View { left: 0, top: 0, width, height };
+        let mut time = 0.0;
+        for (frame, status) in frames.iter().zip(statuses) {
+            screen.draw(visible_rows(&frame.frame, view, &self.glyphs), status)?;
+            let output = String::from_utf8(std::mem::take(&mut screen.out))?;
+            writeln!(out, "[{:.3}, \"o\", {}]", time, json_string(&output))?;
+            let period = 1.0 / f64::from(self.fps);
+            time += if frame.slow { period * f64::from(self.slow_motion) } else { period };
+        }
+        // Hold the last frame for as long as a GIF does before the cursor
+        // comes back
+        queue!(screen.out, cursor::Show)?;
+        let output = String::from_utf8(std::mem::take(&mut screen.out))?;
+        time += f64::from(LAST_FRAME_DELAY) / 100.0 - 1.0 / f64::from(self.fps);
+        writeln!(out, "[{:.3}, \"o\", {}]", time, json_string(&output))?;
+        Ok(frames.len())
+    }
+}
+
+// [s] as a JSON string, quotes and all
+fn json_string(s: &str) -> String {
+    let mut json = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => json += "\\\"",
+            '\\' => json += "\\\\",
+            '\n' => json += "\\n",
+            '\r' => json += "\\r",
+            '\t' => json += "\\t",
+            c if u32::from(c) < 0x20 => json += &format!("\\u{:04x}", u32::from(c)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+// The size of the biggest frame in cells, and how many pixels across each
+// cell is drawn
+fn size(frames: &[Rows]) -> Result<(usize, usize, usize), Box<dyn Error>> {
+    let width = frames.iter().flatten().map(|row| row.len()).max().unwrap_or(0);
+    let height = frames.iter().map(|rows| rows.len()).max().unwrap_or(0);
+    if width == 0 || height == 0 {
+        return Err("the simulation didn't draw anything".into());
+    }
+    let scale = (MAX_SIDE / width.max(height)).clamp(1, MAX_SCALE);
+    Ok((width, height, scale))
+}
+
+// The pixels of the cells from [min] to [max] of a frame, row by row, with
+// each cell a [scale] pixel square in the colour [index] gives for it
+fn pixels(min: (usize, usize), max: (usize, usize), scale: usize,
+          index: impl Fn(usize, usize) -> u8) -> Vec<u8> {
+    let w = (max.0 - min.0 + 1) * scale;
+    let mut pixels = vec![0; w * (max.1 - min.1 + 1) * scale];
+    for y in min.1..=max.1 {
+        for x in min.0..=max.0 {
+            let index = index(x, y);
+            for dy in 0..scale {
+                let start = ((y - min.1) * scale + dy) * w + (x - min.0) * scale;
+                pixels[start..start + scale].fill(index);
+            }
+        }
+    }
+    pixels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AocError;
+    use crate::simulation::StepResult;
+    use crate::viz::Glyphs;
+
+    // A line of #s which grows by one each step, up to three long
+    struct Growing(usize);
+
+    impl Simulation for Growing {
+        fn step(&mut self) -> Result<StepResult, AocError> {
+            if self.0 == 3 {
+                return Ok(StepResult::Finished);
+            }
+            self.0 += 1;
+            Ok(StepResult::Changed)
+        }
+
+        fn render(&self) -> String {
+            "#".repeat(self.0) + "\n."
+        }
+    }
+
+    #[test]
+    fn records_every_frame() {
+        let viewer = Viewer::new().fps(20).palette(Palette::new().with("#", Color::Red));
+        let mut gif = vec![];
+        assert_eq!(viewer.record_gif(&mut Growing(1), &mut gif, 100).unwrap(), 3);
+
+        let mut decoder = gif::DecodeOptions::new().read_info(&gif[..]).unwrap();
+        // Three characters across is the widest frame, drawn as big as allowed
+        assert_eq!((decoder.width(), decoder.height()), (24, 16));
+        let mut delays = vec![];
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            delays.push(frame.delay);
+        }
+        assert_eq!(delays, vec![5, 5, LAST_FRAME_DELAY]);
+
+        let mut gif = vec![];
+        assert_eq!(viewer.record_gif(&mut Growing(1), &mut gif, 2).unwrap(), 2);
+    }
+
+    #[test]
+    fn starting_part_way_and_skipping_steps() {
+        let frames = |viewer: Viewer| -> Vec<_> {
+            let recorded = viewer.frames(&mut Growing(0), 100).unwrap();
+            recorded.into_iter().map(|r| r.step).collect()
+        };
+        assert_eq!(frames(Viewer::new()), vec![0, 1, 2, 3]);
+        assert_eq!(frames(Viewer::new().steps_per_frame(2)), vec![0, 2, 3]);
+        assert_eq!(frames(Viewer::new().start_at(1).steps_per_frame(2)), vec![1, 3]);
+        assert_eq!(frames(Viewer::new().start_at(10)), vec![3]);
+    }
+
+    // Growing, but with the middle step worth a closer look
+    struct Eventful(Growing);
+
+    impl Simulation for Eventful {
+        fn step(&mut self) -> Result<StepResult, AocError> {
+            self.0.step()
+        }
+
+        fn render(&self) -> String {
+            self.0.render()
+        }
+
+        fn is_eventful(&self) -> bool {
+            self.0 .0 == 2
+        }
+    }
+
+    #[test]
+    fn eventful_frames_are_slowed_down() {
+        let viewer = Viewer::new().fps(20).slow_motion(4);
+        let mut gif = vec![];
+        viewer.record_gif(&mut Eventful(Growing(1)), &mut gif, 100).unwrap();
+        let mut decoder = gif::DecodeOptions::new().read_info(&gif[..]).unwrap();
+        let mut delays = vec![];
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            delays.push(frame.delay);
+        }
+        assert_eq!(delays, vec![5, 20, LAST_FRAME_DELAY]);
+    }
+
+    #[test]
+    fn writes_a_png_for_each_frame() {
+        let dir = std::env::temp_dir().join(format!("aoc2018-frames-{}", std::process::id()));
+        let viewer = Viewer::new().palette(Palette::new().with("#", Color::Red));
+        assert_eq!(viewer.record_pngs(&mut Growing(1), &dir, 100).unwrap(), 3);
+
+        for i in 0..3 {
+            let file = File::open(dir.join(format!("frame{:05}.png", i))).unwrap();
+            let reader = png::Decoder::new(file).read_info().unwrap();
+            // Every frame is the size of the biggest one
+            assert_eq!((reader.info().width, reader.info().height), (24, 16));
+        }
+        assert!(!dir.join("frame00003.png").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn records_text() {
+        let dir = std::env::temp_dir().join(format!("aoc2018-text-{}", std::process::id()));
+        let viewer = Viewer::new().glyphs(Glyphs::new().with('#', "█"));
+        assert_eq!(viewer.record_text(&mut Growing(1), &dir, 100).unwrap(), 3);
+        let last = fs::read_to_string(dir.join("frame00002.txt")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(last, "███\n.\n");
+    }
+
+    #[test]
+    fn records_a_cast() {
+        let viewer = Viewer::new().fps(20).palette(Palette::new().with("#", Color::Red));
+        let mut cast = vec![];
+        assert_eq!(viewer.record_cast(&mut Growing(1), &mut cast, 100).unwrap(), 3);
+
+        let cast = String::from_utf8(cast).unwrap();
+        let lines: Vec<_> = cast.lines().collect();
+        // Wide enough for the status line, and tall enough for it under the
+        // frames
+        assert_eq!(lines[0], r#"{"version": 2, "width": 6, "height": 3}"#);
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].starts_with(r#"[0.000, "o", "\u001b"#));
+        assert!(lines[2].starts_with(r#"[0.050, "o", "#) && lines[2].contains("Step 1"));
+        assert!(lines[4].starts_with("[3.100, "));
+        assert_eq!(json_string("a\"\\\n\u{1b}"), r#""a\"\\\n\u001b""#);
+    }
+
+    #[test]
+    fn changed_areas() {
+        let before = vec![vec![0, 0, 0], vec![0, 1, 0]];
+        assert_eq!(changed_area(&before, &before), None);
+        let after = vec![vec![0, 0, 2], vec![1, 1, 0]];
+        assert_eq!(changed_area(&before, &after), Some(((0, 0), (2, 1))));
+        let after = vec![vec![0, 0, 0], vec![0, 1, 0], vec![0, 0, 0, 1]];
+        assert_eq!(changed_area(&before, &after), Some(((3, 2), (3, 2))));
+    }
+
+    #[test]
+    fn colour_table() {
+        let table = ColourTable::new(&Palette::new().with("ab", Color::Red).with("c", Color::Blue));
+        assert_eq!(table.index('a'), table.index('b'));
+        assert_ne!(table.index('a'), table.index('c'));
+        assert_eq!((table.index('.'), table.index('?')), (0, 1));
+        assert_eq!(table.transparent(), 4);
+        assert_eq!(table.bytes().len(), 3 * 8);
+        assert_eq!(ansi_rgb(196), [255, 0, 0]);
+    }
+}
@@ -0,0 +1,32 @@
+// Sparklines, tiny charts one character high made of block characters, for
+// showing how a number changes over time in a line of text, like the
+// resource value of day 18's lumber collection area going round its cycle
+
+const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+// One block per value, from the smallest block for the smallest value to the
+// biggest block for the biggest
+pub fn sparkline(values: &[usize]) -> String {
+    let (min, max) = match (values.iter().min(), values.iter().max()) {
+        (Some(&min), Some(&max)) => (min, max),
+        _ => return String::new(),
+    };
+    values.iter()
+        .map(|&v| match max - min {
+            0 => BLOCKS[0],
+            range => BLOCKS[(v - min) * (BLOCKS.len() - 1) / range],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scaled_to_fit() {
+        assert_eq!(sparkline(&[10, 20, 80, 45]), "▁▂█▄");
+        assert_eq!(sparkline(&[3, 3]), "▁▁");
+        assert_eq!(sparkline(&[]), "");
+    }
+}
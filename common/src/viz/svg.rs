@@ -0,0 +1,246 @@
+// Drawing pictures as SVGs, which stay sharp however far they're zoomed into,
+// like day 10's message in the stars or the route through day 22's cave.
+// Everything is drawn in grid units, where the cell at (x, y) is the square
+// from (x, y) to (x + 1, y + 1), and the picture is made big enough to show
+// everything which has been drawn
+
+use std::fmt;
+
+use crate::AocError;
+use crate::grid::Grid;
+use crate::point::Point;
+
+// Days which can be drawn have a function like this, which draws a picture
+// of an input
+pub type Drawing = fn(&str) -> Result<Svg, AocError>;
+
+// How something is drawn: what it's filled with, what its outline is and how
+// see through it is
+#[derive(Debug, Clone, Default)]
+pub struct Style {
+    fill: Option<String>,
+    stroke: Option<(String, f64)>,
+    opacity: Option<f64>,
+}
+
+impl Style {
+    pub fn fill(colour: &str) -> Style {
+        Style { fill: Some(colour.to_string()), ..Style::default() }
+    }
+
+    // An outline, or a line for a path, [width] grid units wide
+    pub fn stroke(colour: &str, width: f64) -> Style {
+        Style { stroke: Some((colour.to_string(), width)), ..Style::default() }
+    }
+
+    pub fn opacity(mut self, opacity: f64) -> Style {
+        self.opacity = Some(opacity);
+        self
+    }
+
+    fn attributes(&self) -> String {
+        let mut s = match &self.fill {
+            Some(colour) => format!(r#" fill="{}""#, escape(colour)),
+            None => r#" fill="none""#.to_string(),
+        };
+        if let Some((colour, width)) = &self.stroke {
+            s += &format!(r#" stroke="{}" stroke-width="{}" stroke-linejoin="round""#,
+                          escape(colour), n(*width));
+        }
+        if let Some(opacity) = self.opacity {
+            s += &format!(r#" opacity="{}""#, n(opacity));
+        }
+        s
+    }
+}
+
+// Round off the noise from adding up floats, so that 0.1 + 0.2 is written as
+// 0.3 rather than 0.30000000000000004
+fn n(x: f64) -> f64 {
+    let rounded = (x * 1000.0).round() / 1000.0;
+    // Avoid writing -0
+    if rounded == 0.0 { 0.0 } else { rounded }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[derive(Debug, Clone)]
+pub struct Svg {
+    elements: Vec<String>,
+    // The top left and bottom right corners of everything drawn so far
+    bounds: Option<((f64, f64), (f64, f64))>,
+    // How many pixels across a cell is when the picture is shown at its
+    // natural size
+    scale: f64,
+    background: String,
+}
+
+impl Default for Svg {
+    fn default() -> Svg {
+        Svg { elements: vec![], bounds: None, scale: 10.0, background: "white".to_string() }
+    }
+}
+
+impl Svg {
+    pub fn new() -> Svg {
+        Svg::default()
+    }
+
+    pub fn scale(mut self, pixels_per_cell: f64) -> Svg {
+        self.scale = pixels_per_cell;
+        self
+    }
+
+    pub fn background(mut self, colour: &str) -> Svg {
+        self.background = colour.to_string();
+        self
+    }
+
+    fn include(&mut self, (x1, y1): (f64, f64), (x2, y2): (f64, f64)) {
+        self.bounds = Some(match self.bounds {
+            Some(((min_x, min_y), (max_x, max_y))) => {
+                ((min_x.min(x1), min_y.min(y1)), (max_x.max(x2), max_y.max(y2)))
+            },
+            None => ((x1, y1), (x2, y2)),
+        });
+    }
+
+    // A [width] by [height] rectangle with its top left corner at [x], [y]
+    pub fn rect(&mut self, x: f64, y: f64, width: f64, height: f64, style: &Style) -> &mut Svg {
+        self.include((x, y), (x + width, y + height));
+        self.elements.push(format!(r#"<rect x="{}" y="{}" width="{}" height="{}"{}/>"#,
+                                   n(x), n(y), n(width), n(height), style.attributes()));
+        self
+    }
+
+    // Fill in the cell at [p]
+    pub fn cell(&mut self, p: Point, style: &Style) -> &mut Svg {
+        self.rect(f64::from(p.x), f64::from(p.y), 1.0, 1.0, style)
+    }
+
+    // Fill in each cell of [grid] which [colour] gives a colour for, with the
+    // grid's top left cell at [origin]. Cells of the same colour next to each
+    // other in a row are drawn as one rectangle, to keep the file small
+    pub fn grid<T>(&mut self, grid: &Grid<T>, origin: Point,
+                   colour: impl Fn(&T) -> Option<&'static str>) -> &mut Svg {
+        for y in 0..grid.height() {
+            let mut x = 0;
+            while x < grid.width() {
+                let fill = colour(&grid[(x, y)]);
+                let start = x;
+                while x < grid.width() && colour(&grid[(x, y)]) == fill {
+                    x += 1;
+                }
+                if let Some(fill) = fill {
+                    self.rect(f64::from(origin.x) + start as f64, f64::from(origin.y) + y as f64,
+                              (x - start) as f64, 1.0, &Style::fill(fill));
+                }
+            }
+        }
+        self
+    }
+
+    // A line through the middles of the cells at [points], in order
+    pub fn path(&mut self, points: impl IntoIterator<Item = Point>, style: &Style)
+            -> &mut Svg {
+        let mut d = String::new();
+        for p in points {
+            let (x, y) = (f64::from(p.x) + 0.5, f64::from(p.y) + 0.5);
+            self.include((x, y), (x, y));
+            d += &format!("{}{} {}", if d.is_empty() { "M" } else { " L" }, n(x), n(y));
+        }
+        if !d.is_empty() {
+            self.elements.push(format!(r#"<path d="{}"{}/>"#, d, style.attributes()));
+        }
+        self
+    }
+
+    // A dot [radius] grid units across in the middle of each cell in [points]
+    pub fn points(&mut self, points: impl IntoIterator<Item = Point>, radius: f64,
+                  style: &Style) -> &mut Svg {
+        for p in points {
+            let (x, y) = (f64::from(p.x) + 0.5, f64::from(p.y) + 0.5);
+            self.include((x - radius, y - radius), (x + radius, y + radius));
+            self.elements.push(format!(r#"<circle cx="{}" cy="{}" r="{}"{}/>"#,
+                                       n(x), n(y), n(radius), style.attributes()));
+        }
+        self
+    }
+
+    // [text], a cell high, in the middle of the cell at [p]
+    pub fn label(&mut self, p: Point, text: &str, style: &Style) -> &mut Svg {
+        let (x, y) = (f64::from(p.x) + 0.5, f64::from(p.y) + 0.5);
+        self.include((x - 0.5, y - 0.5), (x + 0.5, y + 0.5));
+        self.elements.push(format!(
+            r#"<text x="{}" y="{}" font-size="1" font-family="sans-serif" text-anchor="middle" dominant-baseline="central"{}>{}</text>"#,
+            n(x), n(y), style.attributes(), escape(text)));
+        self
+    }
+
+    // [text], a cell high, starting at the left of the cell at [p]. How wide
+    // it is depends on the font, so it's taken to be a bit over half a cell
+    // a character for working out how big the picture needs to be
+    pub fn text(&mut self, p: Point, text: &str, style: &Style) -> &mut Svg {
+        let (x, y) = (f64::from(p.x), f64::from(p.y) + 0.5);
+        self.include((x, y - 0.5), (x + 0.6 * text.chars().count() as f64, y + 0.5));
+        self.elements.push(format!(
+            r#"<text x="{}" y="{}" font-size="0.8" font-family="monospace" xml:space="preserve" dominant-baseline="central"{}>{}</text>"#,
+            n(x), n(y), style.attributes(), escape(text)));
+        self
+    }
+}
+
+// The whole SVG document. A cell's margin is left around what's been drawn
+impl fmt::Display for Svg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ((min_x, min_y), (max_x, max_y)) = self.bounds.unwrap_or(((0.0, 0.0), (0.0, 0.0)));
+        let (x, y) = (min_x - 1.0, min_y - 1.0);
+        let (width, height) = (max_x - min_x + 2.0, max_y - min_y + 2.0);
+        writeln!(f, r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}" width="{}" height="{}">"#,
+                 n(x), n(y), n(width), n(height), n(width * self.scale), n(height * self.scale))?;
+        writeln!(f, r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                 n(x), n(y), n(width), n(height), escape(&self.background))?;
+        for element in &self.elements {
+            writeln!(f, "{}", element)?;
+        }
+        writeln!(f, "</svg>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sized_to_fit() {
+        let mut svg = Svg::new().scale(2.0);
+        svg.cell(Point::new(3, -2), &Style::fill("red"))
+            .path(vec![Point::new(0, 0), Point::new(5, 1)], &Style::stroke("blue", 0.2));
+        let text = svg.to_string();
+        assert!(text.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="-0.5 -3 7 5.5" width="14" height="11">"#));
+        assert!(text.contains(r#"<rect x="3" y="-2" width="1" height="1" fill="red"/>"#));
+        assert!(text.contains(r#"<path d="M0.5 0.5 L5.5 1.5" fill="none" stroke="blue""#));
+        assert!(text.trim_end().ends_with("</svg>"));
+    }
+
+    #[test]
+    fn grid_rows_are_merged() {
+        let grid = Grid::parse("##.#\n....", |c| Some(c == '#')).unwrap();
+        let mut svg = Svg::new();
+        svg.grid(&grid, Point::new(10, 0), |&wall| if wall { Some("black") } else { None });
+        let rects: Vec<_> = svg.elements.iter().map(|e| e.as_str()).collect();
+        assert_eq!(rects, vec![r#"<rect x="10" y="0" width="2" height="1" fill="black"/>"#,
+                               r#"<rect x="13" y="0" width="1" height="1" fill="black"/>"#]);
+    }
+
+    #[test]
+    fn labels_are_escaped() {
+        let mut svg = Svg::new();
+        svg.label(Point::ORIGIN, "<&>", &Style::fill("black"));
+        assert!(svg.to_string().contains(">&lt;&amp;&gt;</text>"));
+        svg.text(Point::new(0, 2), "a < b", &Style::fill("black"));
+        assert!(svg.to_string().contains(r#"<text x="0" y="2.5""#));
+    }
+}
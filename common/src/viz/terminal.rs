@@ -0,0 +1,760 @@
+// Watching simulations play out in the terminal, like the carts going round
+// day 13's tracks or the water filling day 17's ground. The viewer takes over
+// the terminal, draws each frame the simulation renders and lets the person
+// watching pause, step forwards and back, jump to a step, change the speed
+// and scroll around frames bigger than the screen
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, execute, queue, style, terminal};
+
+pub use crossterm::style::Color;
+
+use super::history::{History, Snapshot};
+use crate::AocError;
+use crate::simulation::{Simulation, StepResult};
+
+// The fastest the screen is redrawn. Going faster than this takes more steps
+// per frame instead
+const MAX_FPS: u32 = 60;
+
+// Colours for the characters in a simulation's frames. Characters without a
+// colour are drawn in the terminal's default colour
+#[derive(Debug, Clone, Default)]
+pub struct Palette {
+    pub(super) colours: HashMap<char, Color>,
+}
+
+impl Palette {
+    pub fn new() -> Palette {
+        Palette::default()
+    }
+
+    // Draw each of [chars] in [colour]
+    pub fn with(mut self, chars: &str, colour: Color) -> Palette {
+        for c in chars.chars() {
+            self.colours.insert(c, colour);
+        }
+        self
+    }
+
+    fn colour(&self, c: char) -> Option<Color> {
+        self.colours.get(&c).copied()
+    }
+
+    // The same palette with its colours changed to suit [scheme]
+    pub fn scheme(mut self, scheme: ColourScheme) -> Palette {
+        self.colours = self.colours.into_iter()
+            .filter_map(|(c, colour)| scheme.colour(colour).map(|colour| (c, colour)))
+            .collect();
+        self
+    }
+}
+
+// Ways of changing the colours each day picks, for people who find them hard
+// to tell apart or to see, or who don't want colours at all
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColourScheme {
+    // The colours as the days pick them
+    #[default]
+    Default,
+    // The bright version of each colour, and white for greys
+    HighContrast,
+    // The colours from Okabe and Ito's palette, which can be told apart with
+    // any kind of colour blindness. Most importantly, red and green become
+    // vermillion and bluish green
+    Colourblind,
+    // No colours, just the terminal's own
+    Monochrome,
+}
+
+impl ColourScheme {
+    pub const NAMES: [(&'static str, ColourScheme); 4] = [
+        ("default", ColourScheme::Default),
+        ("high-contrast", ColourScheme::HighContrast),
+        ("colourblind", ColourScheme::Colourblind),
+        ("monochrome", ColourScheme::Monochrome),
+    ];
+
+    // Parse a scheme's name, e.g. "high-contrast"
+    pub fn parse(s: &str) -> Result<ColourScheme, String> {
+        ColourScheme::NAMES.iter().find(|(name, _)| *name == s).map(|&(_, scheme)| scheme)
+            .ok_or_else(|| {
+                let names: Vec<_> = ColourScheme::NAMES.iter().map(|(name, _)| *name).collect();
+                format!("'{}' isn't a colour scheme, the schemes are {}", s, names.join(", "))
+            })
+    }
+
+    // What [colour] is drawn as, if it's drawn in a colour at all
+    fn colour(self, colour: Color) -> Option<Color> {
+        let rgb = |r, g, b| Some(Color::Rgb { r, g, b });
+        match (self, colour) {
+            (ColourScheme::Default, _) => Some(colour),
+            (ColourScheme::Monochrome, _) => None,
+            (ColourScheme::HighContrast, colour) => Some(match colour {
+                Color::DarkRed => Color::Red,
+                Color::DarkGreen => Color::Green,
+                Color::DarkYellow => Color::Yellow,
+                Color::DarkBlue => Color::Blue,
+                Color::DarkMagenta => Color::Magenta,
+                Color::DarkCyan => Color::Cyan,
+                Color::DarkGrey | Color::Grey => Color::White,
+                colour => colour,
+            }),
+            (ColourScheme::Colourblind, colour) => match colour {
+                Color::Red | Color::DarkRed => rgb(213, 94, 0),
+                Color::Green | Color::DarkGreen => rgb(0, 158, 115),
+                Color::Yellow => rgb(240, 228, 66),
+                Color::DarkYellow => rgb(230, 159, 0),
+                Color::Blue | Color::DarkBlue => rgb(0, 114, 178),
+                Color::Cyan | Color::DarkCyan => rgb(86, 180, 233),
+                Color::Magenta | Color::DarkMagenta => rgb(204, 121, 167),
+                colour => Some(colour),
+            },
+        }
+    }
+}
+
+// Characters to draw in place of the ones in a simulation's frames, like
+// emoji for the elves and goblins on day 15. Characters without a glyph are
+// drawn as they are
+#[derive(Debug, Clone, Default)]
+pub struct Glyphs {
+    glyphs: HashMap<char, String>,
+    // Whether the glyphs are two columns wide, like emoji
+    wide: bool,
+}
+
+impl Glyphs {
+    pub fn new() -> Glyphs {
+        Glyphs::default()
+    }
+
+    // Glyphs which are two columns wide. In lines with any of them in, every
+    // other character is padded out to two columns so that the rows still
+    // line up. Lines without any, like headings, are left as they are
+    pub fn wide() -> Glyphs {
+        Glyphs { wide: true, ..Glyphs::default() }
+    }
+
+    // Draw [c] as [glyph]
+    pub fn with(mut self, c: char, glyph: &str) -> Glyphs {
+        self.glyphs.insert(c, glyph.to_string());
+        self
+    }
+
+    // How many columns each character of [line] takes up
+    pub(super) fn scale(&self, line: &str) -> usize {
+        if self.wide && line.chars().any(|c| self.glyphs.contains_key(&c)) { 2 } else { 1 }
+    }
+
+    // The most columns a character of a frame takes up
+    fn max_scale(&self) -> usize {
+        if self.wide { 2 } else { 1 }
+    }
+
+    // [line], which is [scale] columns a character, drawn with the glyphs
+    fn apply(&self, line: &str, scale: usize) -> String {
+        let mut drawn = String::new();
+        for c in line.chars() {
+            match self.glyphs.get(&c) {
+                Some(glyph) => drawn += glyph,
+                None => {
+                    drawn.push(c);
+                    drawn.extend(std::iter::repeat_n(' ', scale - 1));
+                },
+            }
+        }
+        drawn
+    }
+
+    // The whole of [frame] drawn with the glyphs
+    pub(super) fn draw(&self, frame: &str) -> String {
+        frame.lines().map(|line| self.apply(line, self.scale(line)) + "\n").collect()
+    }
+}
+
+// The part of a frame which is on the screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct View {
+    pub(super) left: usize,
+    pub(super) top: usize,
+    pub(super) width: usize,
+    pub(super) height: usize,
+}
+
+// Cut the part of [frame] in [view] out, one string per row of the screen,
+// and draw it with [glyphs]. The view's left edge is in characters of the
+// frame and its width is in columns of the screen. Every row is padded out to
+// the width of the view so that drawing it covers up whatever was there before
+pub(super) fn visible_rows(frame: &str, view: View, glyphs: &Glyphs) -> Vec<String> {
+    let mut rows: Vec<String> = frame.lines()
+        .skip(view.top)
+        .take(view.height)
+        .map(|line| {
+            let scale = glyphs.scale(line);
+            let visible: String = line.chars().skip(view.left).take(view.width / scale).collect();
+            let columns = visible.chars().count() * scale;
+            glyphs.apply(&visible, scale) + &" ".repeat(view.width - columns)
+        })
+        .collect();
+    rows.resize(view.height, " ".repeat(view.width));
+    rows
+}
+
+// What's on the screen, so that drawing a frame only has to redraw the rows
+// which have changed since the last one
+pub(super) struct Screen<W: Write> {
+    pub(super) out: W,
+    shown: Vec<String>,
+    palette: Palette,
+}
+
+impl<W: Write> Screen<W> {
+    pub(super) fn new(out: W, palette: Palette) -> Screen<W> {
+        Screen { out, shown: vec![], palette }
+    }
+
+    // Forget what's on the screen, e.g. because the terminal has been resized,
+    // so the next frame is drawn in full
+    pub(super) fn invalidate(&mut self) -> io::Result<()> {
+        self.shown.clear();
+        queue!(self.out, terminal::Clear(terminal::ClearType::All))
+    }
+
+    // Draw the rows of a frame, with a status line under them which isn't
+    // coloured in
+    pub(super) fn draw(&mut self, mut rows: Vec<String>, status: String) -> io::Result<()> {
+        let frame_height = rows.len();
+        rows.push(status);
+        for (y, row) in rows.iter().enumerate() {
+            if self.shown.get(y) == Some(row) {
+                continue;
+            }
+            queue!(self.out, cursor::MoveTo(0, y as u16))?;
+            self.draw_row(row, y < frame_height)?;
+        }
+        self.out.flush()?;
+        self.shown = rows;
+        Ok(())
+    }
+
+    // Draw [row] in runs of characters of the same colour
+    fn draw_row(&mut self, row: &str, coloured: bool) -> io::Result<()> {
+        let Screen { out, palette, .. } = self;
+        let colour_of = |c| if coloured { palette.colour(c) } else { None };
+        let mut chars = row.chars().peekable();
+        while let Some(c) = chars.next() {
+            let colour = colour_of(c);
+            let mut run = c.to_string();
+            while let Some(&next) = chars.peek() {
+                if colour_of(next) != colour {
+                    break;
+                }
+                run.push(next);
+                chars.next();
+            }
+            match colour {
+                Some(colour) => queue!(out, style::SetForegroundColor(colour),
+                                       style::Print(run), style::ResetColor)?,
+                None => queue!(out, style::Print(run))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// Puts the terminal into the state the viewer needs for as long as it's
+// alive, and puts it back afterwards, even if there's an error or a panic
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> io::Result<TerminalGuard> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(TerminalGuard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+// Plays a simulation in the terminal, a frame at a time. The keys are
+//   space         pause or carry on
+//   right or n    step forward, pausing first if need be
+//   left or b     step back through the frames already shown
+//   g             go to a step, typed in and then confirmed with enter
+//   + and -       go faster or slower
+//   up and down   scroll (shift with the arrow keys scrolls in any direction,
+//                 page up and down scroll a screen at a time, home goes back
+//                 to the top left)
+//   f             follow where the action is, for simulations which say,
+//                 which is on until the screen is scrolled
+//   q or esc      stop watching
+// Going back shows the frames as they were drawn, so when more than one step
+// is taken between frames it goes back a frame at a time rather than a step.
+// Carrying on from an older frame replays the ones after it before the
+// simulation takes any more steps
+pub struct Viewer {
+    pub(super) fps: u32,
+    pub(super) steps_per_frame: usize,
+    pub(super) palette: Palette,
+    pub(super) glyphs: Glyphs,
+    // How many times slower eventful steps are shown
+    pub(super) slow_motion: u32,
+    // How many steps the simulation takes before the first frame
+    pub(super) start_at: usize,
+}
+
+impl Default for Viewer {
+    fn default() -> Viewer {
+        Viewer {
+            fps: 10,
+            steps_per_frame: 1,
+            palette: Palette::new(),
+            glyphs: Glyphs::new(),
+            slow_motion: 1,
+            start_at: 0,
+        }
+    }
+}
+
+impl Viewer {
+    pub fn new() -> Viewer {
+        Viewer::default()
+    }
+
+    // How many frames to draw each second, at most
+    pub fn fps(mut self, fps: u32) -> Viewer {
+        self.fps = fps.clamp(1, MAX_FPS);
+        self
+    }
+
+    // How many steps to take between frames, for simulations with lots of
+    // small steps
+    pub fn steps_per_frame(mut self, steps: usize) -> Viewer {
+        self.steps_per_frame = steps.max(1);
+        self
+    }
+
+    pub fn palette(mut self, palette: Palette) -> Viewer {
+        self.palette = palette;
+        self
+    }
+
+    // Change the colours of the viewer's palette to suit [scheme]
+    pub fn colour_scheme(mut self, scheme: ColourScheme) -> Viewer {
+        self.palette = self.palette.scheme(scheme);
+        self
+    }
+
+    // Draw frames with [glyphs] rather than the simulation's own characters.
+    // Casts are drawn with them too, but GIFs and PNGs aren't
+    pub fn glyphs(mut self, glyphs: Glyphs) -> Viewer {
+        self.glyphs = glyphs;
+        self
+    }
+
+    // Show the steps a simulation says are eventful, like those around the
+    // first crash on day 13, one step at a time and [factor] times slower
+    pub fn slow_motion(mut self, factor: u32) -> Viewer {
+        self.slow_motion = factor.max(1);
+        self
+    }
+
+    // Skip the first [step] steps without showing them, e.g. to get to where
+    // day 18's lumber collection area settles into a cycle. The simulation
+    // still takes them, so they can't be gone back to
+    pub fn start_at(mut self, step: usize) -> Viewer {
+        self.start_at = step;
+        self
+    }
+
+    pub(super) fn is_slow(&self, sim: &dyn Simulation) -> bool {
+        self.slow_motion > 1 && sim.is_eventful()
+    }
+
+    // Watch [sim] until the person watching quits. Gives the number of steps
+    // taken
+    pub fn play(&self, sim: &mut dyn Simulation) -> Result<usize, Box<dyn Error>> {
+        let _guard = TerminalGuard::enter()?;
+        let mut screen = Screen::new(io::stdout(), self.palette.clone());
+        let mut controls = Controls {
+            fps: self.fps,
+            steps_per_frame: self.steps_per_frame,
+            paused: false,
+            left: 0,
+            top: 0,
+            following: true,
+            typed_step: None,
+        };
+
+        let mut live = Live::start(sim, self.start_at)?;
+        let mut history = History::new(live.snapshot(sim));
+        let mut due = Instant::now();
+
+        loop {
+            let (columns, lines) = terminal::size()?;
+            let Snapshot { step, frame, focus } = history.current();
+            if let Some(focus) = focus.filter(|_| controls.following) {
+                controls.follow(focus, columns as usize / self.glyphs.max_scale(),
+                                (lines as usize).saturating_sub(1));
+            }
+            // The bottom line of the screen is for the status line
+            let view = View {
+                left: controls.left,
+                top: controls.top,
+                width: columns as usize,
+                height: (lines as usize).saturating_sub(1),
+            };
+            let rows = visible_rows(frame, view, &self.glyphs);
+            let ended = if history.is_latest() { live.ended } else { None };
+            let status_view = View { left: 0, top: 0, width: view.width, height: 1 };
+            let slow = history.is_latest() && self.is_slow(sim);
+            let status = controls.status(*step, live.steps, ended, slow);
+            screen.draw(rows, visible_rows(&status, status_view, &Glyphs::new()).remove(0))?;
+
+            // Wait for a key press, or until it's time for the next frame
+            let running = !controls.paused && controls.typed_step.is_none()
+                && !(history.is_latest() && live.ended.is_some());
+            let timeout = if running {
+                Some(due.saturating_duration_since(Instant::now()))
+            }
+            else {
+                None
+            };
+            if let Some(e) = next_event(timeout)? {
+                let request = match e {
+                    Event::Key(key) if key.kind == KeyEventKind::Press => {
+                        controls.handle(key, view)
+                    },
+                    Event::Resize(_, _) => {
+                        screen.invalidate()?;
+                        None
+                    },
+                    _ => None,
+                };
+                match request {
+                    Some(Request::Quit) => return Ok(live.steps),
+                    Some(Request::Forward) if history.is_latest() => {
+                        live.advance(sim, 1, &mut history)?;
+                    },
+                    Some(Request::Forward) => {
+                        history.forward();
+                    },
+                    Some(Request::Back) => {
+                        history.back();
+                    },
+                    Some(Request::GoTo(step)) if step > live.steps => {
+                        live.advance(sim, step - live.steps, &mut history)?;
+                    },
+                    Some(Request::GoTo(step)) => history.seek(step),
+                    None => (),
+                }
+                continue;
+            }
+
+            // Frames which have already been shown are replayed before the
+            // simulation goes any further
+            if !history.forward() {
+                let steps = if slow { 1 } else { controls.steps_per_frame };
+                live.advance(sim, steps, &mut history)?;
+            }
+            let mut period = Duration::from_secs(1) / controls.fps;
+            if history.is_latest() && self.is_slow(sim) {
+                period *= self.slow_motion;
+            }
+            due = (due + period).max(Instant::now());
+        }
+    }
+}
+
+// How far the simulation itself has got, as opposed to the frame being shown
+pub(super) struct Live {
+    pub(super) steps: usize,
+    // Why the simulation has stopped, if it has
+    pub(super) ended: Option<StepResult>,
+}
+
+impl Live {
+    // [sim] after up to [n] steps, for starting part of the way through
+    pub(super) fn start(sim: &mut dyn Simulation, n: usize) -> Result<Live, AocError> {
+        let mut live = Live { steps: 0, ended: None };
+        live.take(sim, n)?;
+        Ok(live)
+    }
+
+    // Take up to [n] more steps, stopping if the simulation does
+    pub(super) fn take(&mut self, sim: &mut dyn Simulation, n: usize) -> Result<(), AocError> {
+        for _ in 0..n {
+            if self.ended.is_some() {
+                break;
+            }
+            match sim.step()? {
+                StepResult::Changed => self.steps += 1,
+                result => self.ended = Some(result),
+            }
+        }
+        Ok(())
+    }
+
+    // Take up to [n] more steps and record the frame after them
+    fn advance(&mut self, sim: &mut dyn Simulation, n: usize, history: &mut History)
+            -> Result<(), AocError> {
+        self.take(sim, n)?;
+        history.record(self.snapshot(sim));
+        Ok(())
+    }
+
+    fn snapshot(&self, sim: &dyn Simulation) -> Snapshot {
+        Snapshot { step: self.steps, frame: sim.render(), focus: sim.focus() }
+    }
+}
+
+// What the person watching has asked for which the viewer has to act on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Request {
+    Quit,
+    Forward,
+    Back,
+    GoTo(usize),
+}
+
+// What the person watching has asked for which changes how the simulation is
+// shown
+struct Controls {
+    fps: u32,
+    steps_per_frame: usize,
+    paused: bool,
+    // Where the top left of the screen is in the frame
+    left: usize,
+    top: usize,
+    // Whether the screen moves to keep up with where the action is. Scrolling
+    // stops it, until it's turned back on
+    following: bool,
+    // The step to go to, while it's being typed in
+    typed_step: Option<String>,
+}
+
+impl Controls {
+    // Act on a key press, giving anything the viewer has to do about it
+    fn handle(&mut self, key: KeyEvent, view: View) -> Option<Request> {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        if let Some(typed) = &mut self.typed_step {
+            match key.code {
+                KeyCode::Char('c') if ctrl => return Some(Request::Quit),
+                KeyCode::Char(c) if c.is_ascii_digit() => typed.push(c),
+                KeyCode::Backspace => {
+                    typed.pop();
+                },
+                KeyCode::Enter => {
+                    let step = typed.parse().ok();
+                    self.typed_step = None;
+                    return step.map(Request::GoTo);
+                },
+                KeyCode::Esc => self.typed_step = None,
+                _ => (),
+            }
+            return None;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Some(Request::Quit),
+            KeyCode::Char('c') if ctrl => return Some(Request::Quit),
+            KeyCode::Char(' ') => self.paused = !self.paused,
+            KeyCode::Right | KeyCode::Char('n') | KeyCode::Char('.') if !shift => {
+                self.paused = true;
+                return Some(Request::Forward);
+            },
+            KeyCode::Left | KeyCode::Char('b') | KeyCode::Char(',') if !shift => {
+                self.paused = true;
+                return Some(Request::Back);
+            },
+            KeyCode::Char('g') => {
+                self.paused = true;
+                self.typed_step = Some(String::new());
+            },
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                if self.fps < MAX_FPS {
+                    self.fps = (self.fps * 2).min(MAX_FPS);
+                }
+                else {
+                    self.steps_per_frame *= 2;
+                }
+            },
+            KeyCode::Char('-') => {
+                if self.steps_per_frame > 1 {
+                    self.steps_per_frame /= 2;
+                }
+                else {
+                    self.fps = (self.fps / 2).max(1);
+                }
+            },
+            KeyCode::Char('f') => self.following = !self.following,
+            KeyCode::Left => self.scroll(|c| c.left = c.left.saturating_sub(1)),
+            KeyCode::Right => self.scroll(|c| c.left += 1),
+            KeyCode::Up => self.scroll(|c| c.top = c.top.saturating_sub(1)),
+            KeyCode::Down => self.scroll(|c| c.top += 1),
+            KeyCode::PageUp => self.scroll(|c| c.top = c.top.saturating_sub(view.height)),
+            KeyCode::PageDown => self.scroll(|c| c.top += view.height),
+            KeyCode::Home => self.scroll(|c| {
+                c.left = 0;
+                c.top = 0;
+            }),
+            _ => (),
+        }
+        None
+    }
+
+    fn scroll(&mut self, scroll: impl FnOnce(&mut Controls)) {
+        scroll(self);
+        self.following = false;
+    }
+
+    // Move the screen, [width] by [height], so that [focus] is on it. It's
+    // moved to put the focus in the middle whenever it gets near an edge,
+    // rather than every time the focus moves, so the frame doesn't judder
+    fn follow(&mut self, (x, y): (usize, usize), width: usize, height: usize) {
+        let recentre = |start: &mut usize, at: usize, size: usize| {
+            if at < *start + size / 4 || at >= *start + size - size / 4 {
+                *start = at.saturating_sub(size / 2);
+            }
+        };
+        recentre(&mut self.left, x, width);
+        recentre(&mut self.top, y, height);
+    }
+
+    // The status line when looking at the frame after [step], when the
+    // simulation has got to [latest] and is or isn't in slow motion
+    fn status(&self, step: usize, latest: usize, ended: Option<StepResult>, slow: bool)
+            -> String {
+        if let Some(typed) = &self.typed_step {
+            return format!("go to step: {}_ | enter go, esc cancel", typed);
+        }
+        let state = match ended {
+            Some(StepResult::Finished) => "finished",
+            Some(_) => "stopped changing",
+            None if self.paused => "paused",
+            None if step < latest => "replaying",
+            None if slow => "slow motion",
+            None => "running",
+        };
+        let step = if step < latest { format!("{} of {}", step, latest) } else { step.to_string() };
+        let follow = if self.following { "following" } else { "f follow" };
+        format!("step {} | {} fps x{} | {} | space pause, left/right step, g go to, \
+                 +/- speed, shift+arrows scroll, {}, q quit",
+                step, self.fps, self.steps_per_frame, state, follow)
+    }
+}
+
+// The next terminal event, waiting at most [timeout] for one
+fn next_event(timeout: Option<Duration>) -> io::Result<Option<Event>> {
+    match timeout {
+        Some(timeout) if !event::poll(timeout)? => Ok(None),
+        _ => event::read().map(Some),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn visible_rows_are_cropped_and_padded() {
+        let frame = "abcdef\nghij\nklmnop\nqr";
+        let view = View { left: 2, top: 1, width: 3, height: 4 };
+        assert_eq!(visible_rows(frame, view, &Glyphs::new()), vec!["ij ", "mno", "   ", "   "]);
+        let glyphs = Glyphs::new().with('m', "M").with('o', "0");
+        assert_eq!(visible_rows(frame, view, &glyphs)[1], "Mn0");
+    }
+
+    #[test]
+    fn wide_glyphs_are_two_columns() {
+        let glyphs = Glyphs::wide().with('E', "🧝").with('.', "  ");
+        let view = View { left: 1, top: 0, width: 8, height: 2 };
+        // The heading has no glyphs in, so it's drawn as it is
+        assert_eq!(visible_rows("Round 3\n#E.E#", view, &glyphs),
+                   vec!["ound 3  ", "🧝  🧝# "]);
+    }
+
+    #[test]
+    fn colour_schemes() {
+        let palette = || Palette::new().with("E", Color::Green).with("G", Color::Red)
+            .with("#", Color::DarkGrey);
+        let colourblind = palette().scheme(ColourScheme::Colourblind);
+        assert_eq!(colourblind.colour('E'), Some(Color::Rgb { r: 0, g: 158, b: 115 }));
+        assert_eq!(colourblind.colour('#'), Some(Color::DarkGrey));
+        let high_contrast = palette().scheme(ColourScheme::HighContrast);
+        assert_eq!((high_contrast.colour('G'), high_contrast.colour('#')),
+                   (Some(Color::Red), Some(Color::White)));
+        assert!(palette().scheme(ColourScheme::Monochrome).colours.is_empty());
+
+        assert_eq!(ColourScheme::parse("high-contrast"), Ok(ColourScheme::HighContrast));
+        assert!(ColourScheme::parse("sepia").unwrap_err().contains("colourblind"));
+    }
+
+    #[test]
+    fn only_changed_rows_are_redrawn() {
+        let mut screen = Screen::new(vec![], Palette::new().with("#", Color::Red));
+        let rows = |second: &str| vec!["#..".to_string(), second.to_string()];
+        screen.draw(rows("..."), "status".to_string()).unwrap();
+        let first = String::from_utf8(std::mem::take(&mut screen.out)).unwrap();
+        assert!(first.contains('#') && first.contains("...") && first.contains("status"));
+
+        screen.draw(rows("..."), "status".to_string()).unwrap();
+        assert!(screen.out.is_empty());
+
+        screen.draw(rows(".x."), "status".to_string()).unwrap();
+        let redrawn = String::from_utf8(std::mem::take(&mut screen.out)).unwrap();
+        assert!(redrawn.contains(".x.") && !redrawn.contains('#') && !redrawn.contains("status"));
+    }
+
+    #[test]
+    fn going_to_a_step() {
+        let mut controls = Controls {
+            fps: 10, steps_per_frame: 1, paused: false, left: 0, top: 0, following: true,
+            typed_step: None,
+        };
+        let view = View { left: 0, top: 0, width: 80, height: 24 };
+        let mut press = |code| controls.handle(KeyEvent::from(code), view);
+        assert_eq!(press(KeyCode::Char('g')), None);
+        for c in "1x23".chars() {
+            assert_eq!(press(KeyCode::Char(c)), None);
+        }
+        assert_eq!(press(KeyCode::Backspace), None);
+        assert_eq!(press(KeyCode::Enter), Some(Request::GoTo(12)));
+        // Typing nothing goes nowhere
+        press(KeyCode::Char('g'));
+        assert_eq!(press(KeyCode::Enter), None);
+        assert_eq!(press(KeyCode::Left), Some(Request::Back));
+        assert!(controls.paused && controls.typed_step.is_none());
+    }
+
+    #[test]
+    fn following_the_focus() {
+        let mut controls = Controls {
+            fps: 10, steps_per_frame: 1, paused: false, left: 0, top: 0, following: true,
+            typed_step: None,
+        };
+        // Near the middle of the screen, so it doesn't move
+        controls.follow((45, 10), 80, 20);
+        assert_eq!((controls.left, controls.top), (0, 0));
+        controls.follow((45, 16), 80, 20);
+        assert_eq!((controls.left, controls.top), (0, 6));
+
+        let view = View { left: 0, top: 0, width: 80, height: 20 };
+        controls.handle(KeyEvent::from(KeyCode::Down), view);
+        assert!(!controls.following);
+        controls.handle(KeyEvent::from(KeyCode::Char('f')), view);
+        assert!(controls.following);
+    }
+}
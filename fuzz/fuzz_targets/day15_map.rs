@@ -0,0 +1,8 @@
+// Day 15's map of the battle
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day15::parse(input);
+});
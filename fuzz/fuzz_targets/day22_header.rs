@@ -0,0 +1,8 @@
+// Day 22's depth and target
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day22::parse(input);
+});
@@ -0,0 +1,9 @@
+// Days 19 and 21's programs. Anything which isn't a program should be an
+// error rather than a panic
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = common::elfcode::parse_program(input);
+});